@@ -5,8 +5,9 @@
 // -----------------------------------------------------------------------------
 
 use num_bigint::{BigInt, RandBigInt, Sign};
-use num_traits::{One, Zero};
+use num_traits::One;
 use rand::rngs::OsRng;
+use crate::group::utils::montgomery::modpow_bigint;
 
 pub struct ClassGroupCiphertext(pub BigInt);
 
@@ -26,17 +27,7 @@ impl ClassGroupEngineImpl {
     }
 
     fn modexp(&self, base: BigInt, exp: BigInt) -> BigInt {
-        let mut result = BigInt::one();
-        let mut base = self.mod_positive(base);
-        let mut exp = exp;
-        while exp > BigInt::zero() {
-            if &exp & BigInt::one() == BigInt::one() {
-                result = self.mod_positive(result * &base);
-            }
-            base = self.mod_positive(&base * &base);
-            exp >>= 1;
-        }
-        result
+        modpow_bigint(&base, &exp, &self.n)
     }
 
     fn sample_random(&self) -> BigInt {
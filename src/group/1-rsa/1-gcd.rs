@@ -1,3 +1,5 @@
+use crate::group::utils::number_theory;
+
 ///! 
 /// 
 ///  Euclid's Division Theorem: returns (quotient - q, remainder - r)
@@ -15,30 +17,20 @@ pub fn divmod(a: i64, b: i64) -> (i64, i64){
 
 ///! Euclid's GCD - Greatest Common Divider
 /// 500BC, Back then there was no steady concept of modular arithmetic. 
-/// What Greek's would do is using geometry and ratio concept to 
-
-pub fn gcd(mut a: i64, mut b: i64) -> i64 {
-    while b != 0 {
-        let (_, r) = divmod (a,b);
-        a= b;
-        b=r;
-    }
-
-    a.abs()
+/// What Greek's would do is using geometry and ratio concept to measure two
+/// lengths against each other: take the shorter away from the longer until
+/// one length measures both.
+/// The loop is a = bq + r repeated: (a, b) <- (b, r) until r = 0.
+/// Here it runs through group::utils::number_theory, shared with the BigInt code.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    number_theory::gcd(&a, &b)
 }
 
 /// Extended Euclidean Algorithm
-/// Returns (g, x, y) such that ax + by = g = gcd (a,b)
+/// Returns (g, x, y) such that ax + by = g = gcd (a,b), g >= 0
 /// 
 pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64){
-    if b == 0 {
-        return (a,1,0);
-    }
-
-    let (g, x1, y1) = extended_gcd(b, a %b);
-    let x = y1;
-    let y = x1 - (a/b) * y1;
-    (g, x, y)
+    number_theory::extended_gcd(&a, &b)
 }
 
 #[cfg(test)]
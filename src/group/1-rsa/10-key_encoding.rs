@@ -0,0 +1,315 @@
+// src/group/1-rsa/10-key_encoding.rs
+
+//! Import/export of super::rsa_bigint keys in the formats OpenSSL speaks.
+//! - PKCS#1 (RFC 8017 App. A.1): RSAPublicKey, RSAPrivateKey (two-prime,
+//!   version 0 only)
+//! - SPKI (RFC 5280 §4.1.2.7): SubjectPublicKeyInfo around RSAPublicKey
+//! - PKCS#8 (RFC 5208): PrivateKeyInfo around RSAPrivateKey, unencrypted
+//! - PEM labels: "RSA PUBLIC KEY", "RSA PRIVATE KEY", "PUBLIC KEY", "PRIVATE KEY"
+//!
+//! DER parsing goes through group::utils::der, so malformed or non-canonical
+//! input is rejected. Imported private keys are re-validated, and the stored
+//! CRT values must match the ones derived from (d, p, q).
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+use thiserror::Error;
+use crate::group::utils::der::{DerError, DerReader, DerWriter};
+use crate::group::utils::pem::{self, PemError};
+use super::rsa_bigint::{RsaError, RsaPrivateKey, RsaPublicKey};
+
+/// rsaEncryption, 1.2.840.113549.1.1.1
+const OID_RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+
+pub const PEM_PKCS1_PUBLIC: &str = "RSA PUBLIC KEY";
+pub const PEM_PKCS1_PRIVATE: &str = "RSA PRIVATE KEY";
+pub const PEM_SPKI: &str = "PUBLIC KEY";
+pub const PEM_PKCS8: &str = "PRIVATE KEY";
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum KeyEncodingError {
+    #[error(transparent)]
+    Der(#[from] DerError),
+    #[error(transparent)]
+    Pem(#[from] PemError),
+    #[error("unsupported version")]
+    UnsupportedVersion,
+    #[error("algorithm is not rsaEncryption")]
+    UnsupportedAlgorithm,
+    #[error("stored CRT parameters do not match the key")]
+    InconsistentCrt,
+    #[error(transparent)]
+    Key(#[from] RsaError),
+}
+
+impl RsaPublicKey {
+    pub fn to_pkcs1_der(&self) -> Vec<u8> {
+        let mut w = DerWriter::new();
+        w.put_sequence(|s| {
+            s.put_uint(&self.n);
+            s.put_uint(&self.e);
+        });
+        w.finish()
+    }
+
+    pub fn from_pkcs1_der(der: &[u8]) -> Result<Self, KeyEncodingError> {
+        let mut r = DerReader::new(der);
+        let mut seq = r.get_sequence()?;
+        let (n, e) = (seq.get_uint()?, seq.get_uint()?);
+        seq.finish()?;
+        r.finish()?;
+        Ok(RsaPublicKey::new(n, e)?)
+    }
+
+    pub fn to_public_key_der(&self) -> Vec<u8> {
+        let mut w = DerWriter::new();
+        w.put_sequence(|s| {
+            put_algorithm(s);
+            s.put_bit_string(&self.to_pkcs1_der());
+        });
+        w.finish()
+    }
+
+    pub fn from_public_key_der(der: &[u8]) -> Result<Self, KeyEncodingError> {
+        let mut r = DerReader::new(der);
+        let mut seq = r.get_sequence()?;
+        get_algorithm(&mut seq)?;
+        let key = seq.get_bit_string()?;
+        seq.finish()?;
+        r.finish()?;
+        Self::from_pkcs1_der(key)
+    }
+
+    pub fn to_pkcs1_pem(&self) -> String {
+        pem::encode(PEM_PKCS1_PUBLIC, &self.to_pkcs1_der())
+    }
+
+    pub fn from_pkcs1_pem(s: &str) -> Result<Self, KeyEncodingError> {
+        Self::from_pkcs1_der(&pem::decode(PEM_PKCS1_PUBLIC, s)?)
+    }
+
+    pub fn to_public_key_pem(&self) -> String {
+        pem::encode(PEM_SPKI, &self.to_public_key_der())
+    }
+
+    pub fn from_public_key_pem(s: &str) -> Result<Self, KeyEncodingError> {
+        Self::from_public_key_der(&pem::decode(PEM_SPKI, s)?)
+    }
+}
+
+impl RsaPrivateKey {
+    pub fn to_pkcs1_der(&self) -> Vec<u8> {
+        let mut w = DerWriter::new();
+        w.put_sequence(|s| {
+            s.put_uint(&BigUint::zero());
+            for x in [&self.public.n, &self.public.e, &self.d, &self.p, &self.q, &self.dp, &self.dq, &self.qinv] {
+                s.put_uint(x);
+            }
+        });
+        w.finish()
+    }
+
+    pub fn from_pkcs1_der(der: &[u8]) -> Result<Self, KeyEncodingError> {
+        let mut r = DerReader::new(der);
+        let mut seq = r.get_sequence()?;
+        // version 1 is multi-prime, not supported here
+        if !seq.get_uint()?.is_zero() {
+            return Err(KeyEncodingError::UnsupportedVersion);
+        }
+        let mut next = || seq.get_uint();
+        let (n, e, d, p, q) = (next()?, next()?, next()?, next()?, next()?);
+        let (dp, dq, qinv) = (next()?, next()?, next()?);
+        seq.finish()?;
+        r.finish()?;
+
+        let key = RsaPrivateKey::from_components(n, e, d, p, q)?;
+        if key.dp != dp || key.dq != dq || key.qinv != qinv {
+            return Err(KeyEncodingError::InconsistentCrt);
+        }
+        Ok(key)
+    }
+
+    pub fn to_pkcs8_der(&self) -> Vec<u8> {
+        let mut w = DerWriter::new();
+        w.put_sequence(|s| {
+            s.put_uint(&BigUint::zero());
+            put_algorithm(s);
+            s.put_octet_string(&self.to_pkcs1_der());
+        });
+        w.finish()
+    }
+
+    /// PrivateKeyInfo without attributes; EncryptedPrivateKeyInfo is rejected
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<Self, KeyEncodingError> {
+        let mut r = DerReader::new(der);
+        let mut seq = r.get_sequence()?;
+        if !seq.get_uint()?.is_zero() {
+            return Err(KeyEncodingError::UnsupportedVersion);
+        }
+        get_algorithm(&mut seq)?;
+        let key = seq.get_octet_string()?;
+        seq.finish()?;
+        r.finish()?;
+        Self::from_pkcs1_der(key)
+    }
+
+    pub fn to_pkcs1_pem(&self) -> String {
+        pem::encode(PEM_PKCS1_PRIVATE, &self.to_pkcs1_der())
+    }
+
+    pub fn from_pkcs1_pem(s: &str) -> Result<Self, KeyEncodingError> {
+        Self::from_pkcs1_der(&pem::decode(PEM_PKCS1_PRIVATE, s)?)
+    }
+
+    pub fn to_pkcs8_pem(&self) -> String {
+        pem::encode(PEM_PKCS8, &self.to_pkcs8_der())
+    }
+
+    pub fn from_pkcs8_pem(s: &str) -> Result<Self, KeyEncodingError> {
+        Self::from_pkcs8_der(&pem::decode(PEM_PKCS8, s)?)
+    }
+}
+
+// AlgorithmIdentifier { rsaEncryption, NULL }
+fn put_algorithm(w: &mut DerWriter) {
+    w.put_sequence(|a| {
+        a.put_oid(OID_RSA_ENCRYPTION);
+        a.put_null();
+    });
+}
+
+fn get_algorithm(r: &mut DerReader) -> Result<(), KeyEncodingError> {
+    let mut alg = r.get_sequence()?;
+    if alg.get_oid()? != OID_RSA_ENCRYPTION {
+        return Err(KeyEncodingError::UnsupportedAlgorithm);
+    }
+    // RFC 3279 §2.3.1: parameters MUST be NULL
+    alg.get_null()?;
+    alg.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group::utils::der::{TAG_INTEGER, TAG_SEQUENCE};
+    use super::super::{oaep, pss};
+
+    // Generated offline with OpenSSL 3.5:
+    //   openssl genpkey -algorithm RSA -pkeyopt rsa_keygen_bits:2048 -out rsa2048-pkcs8.pem
+    //   openssl pkcs8 -topk8 -nocrypt -in rsa2048-pkcs8.pem -outform DER -out rsa2048-pkcs8.der
+    //   openssl rsa -in rsa2048-pkcs8.pem -traditional -out rsa2048-pkcs1.pem
+    //   openssl rsa -in rsa2048-pkcs8.pem -RSAPublicKey_out -out rsa2048-pub-pkcs1.pem
+    //   openssl pkey -in rsa2048-pkcs8.pem -pubout [-outform DER] -out rsa2048-spki.{pem,der}
+    //   openssl dgst -sha256 -sign rsa2048-pkcs8.pem -sigopt rsa_padding_mode:pss \
+    //       -sigopt rsa_pss_saltlen:32 -out rsa2048-pss-sha256.sig msg
+    //   openssl pkeyutl -encrypt -pubin -inkey rsa2048-spki.pem -pkeyopt rsa_padding_mode:oaep \
+    //       -pkeyopt rsa_oaep_md:sha256 -pkeyopt rsa_mgf1_md:sha256 -in msg -out rsa2048-oaep-sha256.bin
+    // where msg is the 16 bytes "custodial export".
+    const PKCS1_PEM: &str = include_str!("fixtures/rsa2048-pkcs1.pem");
+    const PKCS8_PEM: &str = include_str!("fixtures/rsa2048-pkcs8.pem");
+    const PKCS8_DER: &[u8] = include_bytes!("fixtures/rsa2048-pkcs8.der");
+    const PUB_PKCS1_PEM: &str = include_str!("fixtures/rsa2048-pub-pkcs1.pem");
+    const SPKI_PEM: &str = include_str!("fixtures/rsa2048-spki.pem");
+    const SPKI_DER: &[u8] = include_bytes!("fixtures/rsa2048-spki.der");
+    const PSS_SIG: &[u8] = include_bytes!("fixtures/rsa2048-pss-sha256.sig");
+    const OAEP_CT: &[u8] = include_bytes!("fixtures/rsa2048-oaep-sha256.bin");
+    const MSG: &[u8] = b"custodial export";
+
+    #[test]
+    fn openssl_fixtures_roundtrip() {
+        let from_pkcs1 = RsaPrivateKey::from_pkcs1_pem(PKCS1_PEM).unwrap();
+        let from_pkcs8 = RsaPrivateKey::from_pkcs8_pem(PKCS8_PEM).unwrap();
+        let from_der = RsaPrivateKey::from_pkcs8_der(PKCS8_DER).unwrap();
+        assert_eq!(from_pkcs1.public_key().n.bits(), 2048);
+        assert_eq!(from_pkcs1.to_pkcs8_der(), from_pkcs8.to_pkcs8_der());
+        assert_eq!(from_der.to_pkcs1_der(), from_pkcs1.to_pkcs1_der());
+
+        // byte-exact re-encoding
+        assert_eq!(from_pkcs1.to_pkcs1_pem(), PKCS1_PEM);
+        assert_eq!(from_pkcs8.to_pkcs8_pem(), PKCS8_PEM);
+        assert_eq!(from_der.to_pkcs8_der(), PKCS8_DER);
+
+        let pk = from_pkcs1.public_key();
+        assert_eq!(&RsaPublicKey::from_pkcs1_pem(PUB_PKCS1_PEM).unwrap(), pk);
+        assert_eq!(&RsaPublicKey::from_public_key_pem(SPKI_PEM).unwrap(), pk);
+        assert_eq!(&RsaPublicKey::from_public_key_der(SPKI_DER).unwrap(), pk);
+        assert_eq!(pk.to_pkcs1_pem(), PUB_PKCS1_PEM);
+        assert_eq!(pk.to_public_key_pem(), SPKI_PEM);
+        assert_eq!(pk.to_public_key_der(), SPKI_DER);
+    }
+
+    #[test]
+    fn interoperates_with_openssl_padding() {
+        let sk = RsaPrivateKey::from_pkcs8_pem(PKCS8_PEM).unwrap();
+        let pk = RsaPublicKey::from_public_key_pem(SPKI_PEM).unwrap();
+        pss::verify(&pk, MSG, PSS_SIG).unwrap();
+        assert_eq!(oaep::decrypt(&sk, OAEP_CT, None).unwrap(), MSG);
+    }
+
+    #[test]
+    fn rejects_malformed_encodings() {
+        let sk = RsaPrivateKey::from_pkcs1_pem(PKCS1_PEM).unwrap();
+        let der = sk.to_pkcs1_der();
+
+        // truncated, trailing bytes, wrong outer tag
+        assert!(matches!(RsaPrivateKey::from_pkcs1_der(&der[..der.len() - 1]), Err(KeyEncodingError::Der(_))));
+        let mut trailing = der.clone();
+        trailing.push(0);
+        assert_eq!(RsaPrivateKey::from_pkcs1_der(&trailing).err(), Some(KeyEncodingError::Der(DerError::TrailingBytes)));
+        let mut bad_tag = der.clone();
+        bad_tag[0] = 0x31;
+        assert_eq!(
+            RsaPrivateKey::from_pkcs1_der(&bad_tag).err(),
+            Some(KeyEncodingError::Der(DerError::UnexpectedTag { expected: TAG_SEQUENCE, found: 0x31 }))
+        );
+
+        // fields re-encoded by hand: version, CRT consistency, negative modulus
+        let fields = [&sk.public.n, &sk.public.e, &sk.d, &sk.p, &sk.q, &sk.dp, &sk.dq, &sk.qinv];
+        let encode = |version: u32, tweak: usize| {
+            let mut w = DerWriter::new();
+            w.put_sequence(|s| {
+                s.put_uint(&BigUint::from(version));
+                for (i, x) in fields.iter().enumerate() {
+                    if i == tweak { s.put_uint(&(*x + 2u32)) } else { s.put_uint(x) }
+                }
+            });
+            w.finish()
+        };
+        assert_eq!(RsaPrivateKey::from_pkcs1_der(&encode(0, usize::MAX)).unwrap().to_pkcs1_der(), der);
+        assert_eq!(RsaPrivateKey::from_pkcs1_der(&encode(1, usize::MAX)).err(), Some(KeyEncodingError::UnsupportedVersion));
+        assert_eq!(RsaPrivateKey::from_pkcs1_der(&encode(0, 7)).err(), Some(KeyEncodingError::InconsistentCrt));
+        assert!(matches!(RsaPrivateKey::from_pkcs1_der(&encode(0, 2)), Err(KeyEncodingError::Key(_))));
+        let pub_der = sk.public_key().to_pkcs1_der();
+        let mut negative = pub_der.clone();
+        let n_start = 4 + 4; // SEQUENCE header, INTEGER header
+        assert_eq!((negative[4], negative[n_start]), (TAG_INTEGER, 0x00));
+        negative[n_start] = 0xff;
+        assert_eq!(RsaPublicKey::from_pkcs1_der(&negative).err(), Some(KeyEncodingError::Der(DerError::NegativeInteger)));
+
+        // SPKI/PKCS#8 with another algorithm or without NULL parameters
+        let mut spki = SPKI_DER.to_vec();
+        let oid_at = spki.windows(OID_RSA_ENCRYPTION.len()).position(|w| w == OID_RSA_ENCRYPTION).unwrap();
+        spki[oid_at + 8] = 0x0b;
+        assert_eq!(RsaPublicKey::from_public_key_der(&spki).err(), Some(KeyEncodingError::UnsupportedAlgorithm));
+        let mut w = DerWriter::new();
+        w.put_sequence(|s| {
+            s.put_sequence(|a| a.put_oid(OID_RSA_ENCRYPTION));
+            s.put_bit_string(&pub_der);
+        });
+        assert!(matches!(RsaPublicKey::from_public_key_der(&w.finish()), Err(KeyEncodingError::Der(_))));
+
+        // PEM label must match the parser
+        assert!(matches!(RsaPrivateKey::from_pkcs8_pem(PKCS1_PEM), Err(KeyEncodingError::Pem(PemError::Label { .. }))));
+        assert!(matches!(RsaPublicKey::from_pkcs1_pem(SPKI_PEM), Err(KeyEncodingError::Pem(PemError::Label { .. }))));
+    }
+
+    #[test]
+    fn generated_key_roundtrip() {
+        let sk = super::super::rsa_bigint::test_key_1024();
+        let back = RsaPrivateKey::from_pkcs8_pem(&sk.to_pkcs8_pem()).unwrap();
+        assert_eq!(back.to_pkcs1_der(), sk.to_pkcs1_der());
+        let pk = RsaPublicKey::from_public_key_pem(&sk.public_key().to_public_key_pem()).unwrap();
+        assert_eq!(&pk, sk.public_key());
+    }
+}
@@ -0,0 +1,368 @@
+// src/group/1-rsa/11-threshold_rsa.rs
+
+//! Shoup's practical threshold RSA signatures (Eurocrypt 2000), dealer based.
+//! - Dealer: n = p·q with safe primes p = 2p'+1, q = 2q'+1, m = p'q'.
+//!   d = e^{-1} mod m is Shamir-shared with f over Z_m, f(0) = d; party i
+//!   gets s_i = f(i) and publishes v_i = v^(s_i) for a random square v.
+//! - Signature share on x: x_i = x^(2Δ s_i) mod n with Δ = l!, plus a proof
+//!   that log_{x^(4Δ)}(x_i^2) = log_v(v_i) (same shape as the share proofs in
+//!   secp256k1::threshold_paillier).
+//! - Combiner: for t+1 valid shares S, w = Π x_i^(2 μ_i) with
+//!   μ_i = Δ·Π_{j≠i} j/(j-i), so w^e = x^(4Δ²). With a·4Δ² + b·e = 1
+//!   (e is a prime > l) the signature is y = w^a · x^b and y^e = x.
+//!
+//! x is the EMSA-PSS encoding of SHA-256(msg) with an empty salt, so every
+//! party derives the same x on its own and the combined signature is an
+//! ordinary RSASSA-PSS signature: partners check it with super::pss::verify
+//! and never see the threshold structure.
+
+use num_bigint::{BigInt, BigUint, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use thiserror::Error;
+use crate::group::utils::montgomery::{modpow_bigint, modpow_signed};
+use crate::group::utils::primes::{is_prime, random_safe_prime_with_rng};
+use crate::group::utils::transcript::Transcript;
+use super::pss;
+use super::rsa_bigint::{i2osp, os2ip, RsaError, RsaPrivateKey, RsaPublicKey, DEFAULT_E};
+
+// statistical hiding slack for proof responses
+const STAT_BITS: u64 = 128;
+// Fiat–Shamir challenge length
+const CHALLENGE_BITS: u64 = 256;
+
+#[derive(Error, Debug)]
+pub enum ThresholdRsaError {
+    #[error("invalid threshold parameters (need 1 <= t < l < e)")]
+    InvalidParameters,
+    #[error("threshold RSA needs safe primes p = 2p'+1, q = 2q'+1")]
+    NotSafePrimes,
+    #[error("not enough valid signature shares: need {needed}, got {got}")]
+    NotEnoughShares { needed: usize, got: usize },
+    #[error("message representative is not a unit mod n")]
+    InvalidMessage,
+    #[error("combined signature does not verify")]
+    InvalidSignature,
+    #[error(transparent)]
+    Rsa(#[from] RsaError),
+}
+
+/// Public data every signer and the combiner need
+#[derive(Debug, Clone)]
+pub struct ThresholdRsaPublicKey {
+    pub pk: RsaPublicKey,
+    pub t: usize,         // any t+1 shares sign
+    pub l: usize,         // number of parties, indices 1..=l
+    pub delta: BigInt,    // Δ = l!
+    pub v: BigInt,        // random square mod n
+    pub vks: Vec<BigInt>, // vks[i-1] = v^(s_i) mod n
+}
+
+/// Secret share s_i = f(i) held by party `index`
+#[derive(Debug)]
+pub struct KeyShare {
+    pub index: usize,
+    s_i: BigInt,
+}
+
+impl Drop for KeyShare {
+    fn drop(&mut self) {
+        // best-effort zeroization, as for the other key types
+        self.s_i = BigInt::zero();
+    }
+}
+
+/// Proof that log_{x^(4Δ)}(x_i^2) = log_v(v_i), as (challenge, response)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareProof {
+    pub e: BigInt,
+    pub z: BigInt,
+}
+
+/// Signature share x_i = x^(2Δ s_i) from party `index`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureShare {
+    pub index: usize,
+    pub x_i: BigInt,
+    pub proof: ShareProof,
+}
+
+/// Trusted-dealer key generation with safe primes of bits / 2 bits each
+pub fn dealer_keygen(bits: usize, t: usize, l: usize) -> Result<(ThresholdRsaPublicKey, Vec<KeyShare>), ThresholdRsaError> {
+    dealer_keygen_with_rng(bits, t, l, &mut OsRng)
+}
+
+/// As `dealer_keygen`, drawing the safe primes from `rng`
+pub fn dealer_keygen_with_rng(bits: usize, t: usize, l: usize, rng: &mut dyn RngCore) -> Result<(ThresholdRsaPublicKey, Vec<KeyShare>), ThresholdRsaError> {
+    check_params(t, l, DEFAULT_E as u64)?;
+    let half = bits / 2;
+    let p = random_safe_prime_with_rng(half, rng);
+    let mut q = random_safe_prime_with_rng(half, rng);
+    while p == q { q = random_safe_prime_with_rng(half, rng); }
+    let to_u = |x: BigInt| x.to_biguint().expect("primes are positive");
+    let sk = RsaPrivateKey::from_primes(to_u(p), to_u(q), BigUint::from(DEFAULT_E))?;
+    deal(&sk, t, l)
+}
+
+/// Trusted dealer: share an existing key. Its primes must be safe primes.
+pub fn deal(sk: &RsaPrivateKey, t: usize, l: usize) -> Result<(ThresholdRsaPublicKey, Vec<KeyShare>), ThresholdRsaError> {
+    let pk = sk.public_key().clone();
+    let e_small = u64::try_from(&pk.e).unwrap_or(u64::MAX);
+    check_params(t, l, e_small)?;
+    let to_i = |x: &BigUint| BigInt::from_biguint(Sign::Plus, x.clone());
+    let (p1, q1) = (to_i(&sk.p) >> 1usize, to_i(&sk.q) >> 1usize);
+    if !is_prime(&p1) || !is_prime(&q1) {
+        return Err(ThresholdRsaError::NotSafePrimes);
+    }
+    let m = p1 * q1;
+    let e = to_i(&pk.e);
+    if !is_prime(&e) {
+        return Err(ThresholdRsaError::InvalidParameters);
+    }
+    let ext = e.extended_gcd(&m);
+    if !ext.gcd.is_one() {
+        return Err(ThresholdRsaError::InvalidParameters);
+    }
+    let d = ext.x.mod_floor(&m);
+
+    let mut rng = OsRng;
+    let mut coeffs = vec![d];
+    for _ in 0..t {
+        coeffs.push(rng.gen_bigint_range(&BigInt::zero(), &m));
+    }
+    let shares: Vec<KeyShare> = (1..=l)
+        .map(|i| KeyShare { index: i, s_i: eval_poly(&coeffs, i).mod_floor(&m) })
+        .collect();
+    for c in coeffs.iter_mut() { *c = BigInt::zero(); }
+
+    let n = to_i(&pk.n);
+    let v = sample_square(&n);
+    let vks = shares.iter().map(|s| modpow_bigint(&v, &s.s_i, &n)).collect();
+    Ok((ThresholdRsaPublicKey { pk, t, l, delta: factorial(l), v, vks }, shares))
+}
+
+impl KeyShare {
+    /// Compute x_i = x^(2Δ s_i) for the representative of msg and prove it
+    pub fn sign_share(&self, tpk: &ThresholdRsaPublicKey, msg: &[u8]) -> Result<SignatureShare, ThresholdRsaError> {
+        let n = tpk.modulus();
+        let x = tpk.representative(msg)?;
+        let x_i = modpow_bigint(&x, &(&tpk.delta * &self.s_i * 2), &n);
+
+        // r hides e·s_i statistically, so it is CHALLENGE + STAT bits longer than n
+        let x_tilde = modpow_bigint(&x, &(&tpk.delta * 4), &n);
+        let xi2 = (&x_i * &x_i).mod_floor(&n);
+        let mut rng = OsRng;
+        let r = rng.gen_bigint_range(&BigInt::zero(), &(BigInt::one() << (n.bits() + CHALLENGE_BITS + STAT_BITS)));
+        let a = modpow_bigint(&tpk.v, &r, &n);
+        let b = modpow_bigint(&x_tilde, &r, &n);
+        let e = share_challenge(tpk, self.index, &x_tilde, &xi2, &a, &b);
+        let z = r + &e * &self.s_i;
+        Ok(SignatureShare { index: self.index, x_i, proof: ShareProof { e, z } })
+    }
+}
+
+impl ThresholdRsaPublicKey {
+    /// Check the proof attached to a signature share on msg
+    pub fn verify_share(&self, msg: &[u8], share: &SignatureShare) -> bool {
+        let i = share.index;
+        if i == 0 || i > self.l {
+            return false;
+        }
+        let Ok(x) = self.representative(msg) else { return false; };
+        let n = self.modulus();
+        let ShareProof { e, z } = &share.proof;
+        if z.sign() == Sign::Minus || e.sign() == Sign::Minus || e.bits() > CHALLENGE_BITS {
+            return false;
+        }
+        if share.x_i.sign() != Sign::Plus || share.x_i >= n {
+            return false;
+        }
+        let x_tilde = modpow_bigint(&x, &(&self.delta * 4), &n);
+        let xi2 = (&share.x_i * &share.x_i).mod_floor(&n);
+        // a = v^z / v_i^e, b = x~^z / (x_i^2)^e
+        let neg_e = -e;
+        let (Some(vi_e), Some(xi2_e)) = (modpow_signed(&self.vks[i - 1], &neg_e, &n), modpow_signed(&xi2, &neg_e, &n)) else {
+            return false;
+        };
+        let a = (modpow_bigint(&self.v, z, &n) * vi_e).mod_floor(&n);
+        let b = (modpow_bigint(&x_tilde, z, &n) * xi2_e).mod_floor(&n);
+        share_challenge(self, i, &x_tilde, &xi2, &a, &b) == *e
+    }
+
+    /// Combine signature shares on msg into an RSASSA-PSS signature. Shares
+    /// with bad proofs, unknown or duplicate indices are skipped; the first
+    /// t+1 valid ones are used.
+    pub fn combine(&self, msg: &[u8], shares: &[SignatureShare]) -> Result<Vec<u8>, ThresholdRsaError> {
+        let x = self.representative(msg)?;
+        let mut seen = BTreeSet::new();
+        let valid: Vec<&SignatureShare> = shares
+            .iter()
+            .filter(|s| !seen.contains(&s.index) && self.verify_share(msg, s) && seen.insert(s.index))
+            .take(self.t + 1)
+            .collect();
+        if valid.len() < self.t + 1 {
+            return Err(ThresholdRsaError::NotEnoughShares { needed: self.t + 1, got: valid.len() });
+        }
+
+        let n = self.modulus();
+        let set: Vec<usize> = valid.iter().map(|s| s.index).collect();
+        let mut w = BigInt::one();
+        for s in &valid {
+            let mu = self.lagrange_at_zero(s.index, &set);
+            let term = modpow_signed(&s.x_i, &(mu * 2), &n).ok_or(ThresholdRsaError::InvalidMessage)?;
+            w = (w * term).mod_floor(&n);
+        }
+        // w^e = x^(4Δ²); a·4Δ² + b·e = 1 gives y = w^a · x^b with y^e = x
+        let four_delta2 = BigInt::from(4) * &self.delta * &self.delta;
+        let e = BigInt::from_biguint(Sign::Plus, self.pk.e.clone());
+        let ext = four_delta2.extended_gcd(&e);
+        let wa = modpow_signed(&w, &ext.x, &n).ok_or(ThresholdRsaError::InvalidMessage)?;
+        let xb = modpow_signed(&x, &ext.y, &n).ok_or(ThresholdRsaError::InvalidMessage)?;
+        let y = (wa * xb).mod_floor(&n).to_biguint().expect("reduced mod n");
+        if self.pk.public_op(&y)? != x.to_biguint().expect("reduced mod n") {
+            return Err(ThresholdRsaError::InvalidSignature);
+        }
+        Ok(i2osp(&y, self.pk.size())?)
+    }
+
+    /// EMSA-PSS(SHA-256(msg), empty salt) as an integer
+    pub fn representative(&self, msg: &[u8]) -> Result<BigInt, ThresholdRsaError> {
+        let em_bits = self.pk.n.bits() as usize - 1;
+        let em = pss::encode(&Sha256::digest(msg), &[], em_bits)?;
+        let x = BigInt::from_biguint(Sign::Plus, os2ip(&em));
+        if !x.gcd(&self.modulus()).is_one() {
+            return Err(ThresholdRsaError::InvalidMessage);
+        }
+        Ok(x)
+    }
+
+    fn modulus(&self) -> BigInt {
+        BigInt::from_biguint(Sign::Plus, self.pk.n.clone())
+    }
+
+    // μ_i = Δ · prod_{j in S, j != i} j / (j - i), exact over the integers
+    fn lagrange_at_zero(&self, i: usize, set: &[usize]) -> BigInt {
+        let mut num = self.delta.clone();
+        let mut den = BigInt::one();
+        for &j in set.iter().filter(|&&j| j != i) {
+            num *= BigInt::from(j);
+            den *= BigInt::from(j as i64 - i as i64);
+        }
+        num / den
+    }
+}
+
+// e must be a prime larger than l so gcd(e, 4Δ²) = 1
+fn check_params(t: usize, l: usize, e: u64) -> Result<(), ThresholdRsaError> {
+    if t == 0 || t >= l || (l as u64) >= e {
+        return Err(ThresholdRsaError::InvalidParameters);
+    }
+    Ok(())
+}
+
+// random square mod n; generates Q_n w.h.p. for safe primes
+fn sample_square(n: &BigInt) -> BigInt {
+    let mut rng = OsRng;
+    loop {
+        let r = rng.gen_bigint_range(&BigInt::from(2), n);
+        if r.gcd(n).is_one() {
+            return (&r * &r).mod_floor(n);
+        }
+    }
+}
+
+fn factorial(l: usize) -> BigInt {
+    (1..=l).fold(BigInt::one(), |acc, k| acc * BigInt::from(k))
+}
+
+fn eval_poly(coeffs: &[BigInt], x: usize) -> BigInt {
+    let x = BigInt::from(x);
+    coeffs.iter().rev().fold(BigInt::zero(), |acc, a| acc * &x + a)
+}
+
+fn share_challenge(tpk: &ThresholdRsaPublicKey, i: usize, x_tilde: &BigInt, xi2: &BigInt, a: &BigInt, b: &BigInt) -> BigInt {
+    let mut tr = Transcript::new(b"threshold-rsa/signature-share");
+    tr.append_int(b"n", &tpk.modulus());
+    tr.append_int(b"v", &tpk.v);
+    tr.append_u64(b"i", i as u64);
+    tr.append_int(b"v_i", &tpk.vks[i - 1]);
+    tr.append_int(b"x~", x_tilde);
+    tr.append_int(b"x_i^2", xi2);
+    tr.append_int(b"a", a);
+    tr.append_int(b"b", b);
+    tr.challenge_bits(b"e", CHALLENGE_BITS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MSG: &[u8] = b"release wrapped key 7";
+
+    fn sign_all(tpk: &ThresholdRsaPublicKey, shares: &[KeyShare], msg: &[u8]) -> Vec<SignatureShare> {
+        shares.iter().map(|s| s.sign_share(tpk, msg).unwrap()).collect()
+    }
+
+    #[test]
+    fn any_t_plus_one_subset_signs() {
+        let (tpk, shares) = dealer_keygen(512, 2, 5).unwrap();
+        let all = sign_all(&tpk, &shares, MSG);
+        assert!(all.iter().all(|s| tpk.verify_share(MSG, s)));
+
+        for subset in [[0usize, 1, 2], [2, 3, 4], [0, 2, 4], [4, 1, 3]] {
+            let chosen: Vec<_> = subset.iter().map(|&k| all[k].clone()).collect();
+            let sig = tpk.combine(MSG, &chosen).unwrap();
+            // a plain RSA-PSS verifier accepts it
+            pss::verify(&tpk.pk, MSG, &sig).unwrap();
+            assert!(pss::verify(&tpk.pk, b"other", &sig).is_err());
+        }
+        assert!(matches!(
+            tpk.combine(MSG, &all[..2]),
+            Err(ThresholdRsaError::NotEnoughShares { needed: 3, got: 2 })
+        ));
+    }
+
+    #[test]
+    fn corrupted_shares_are_rejected() {
+        let (tpk, shares) = dealer_keygen(512, 1, 4).unwrap();
+        let mut all = sign_all(&tpk, &shares, MSG);
+        let n = tpk.modulus();
+
+        // party 1 sends a wrong share with its old proof
+        all[0].x_i = (&all[0].x_i * BigInt::from(4)).mod_floor(&n);
+        // party 2 claims to be party 3
+        all[1].index = 3;
+        // party 3 tampers with its response
+        all[2].proof.z += 1;
+        for s in &all[..3] {
+            assert!(!tpk.verify_share(MSG, s));
+        }
+        // an honest share for another message does not count either
+        let other = shares[3].sign_share(&tpk, b"other").unwrap();
+        assert!(!tpk.verify_share(MSG, &other));
+        assert!(matches!(
+            tpk.combine(MSG, &[all[0].clone(), all[1].clone(), all[2].clone(), other]),
+            Err(ThresholdRsaError::NotEnoughShares { needed: 2, got: 0 })
+        ));
+
+        // two honest shares among the noise still sign
+        all.push(shares[0].sign_share(&tpk, MSG).unwrap());
+        let sig = tpk.combine(MSG, &all).unwrap();
+        pss::verify(&tpk.pk, MSG, &sig).unwrap();
+
+        // a duplicate of one honest share counts once
+        let dup = vec![all[3].clone(), all[3].clone()];
+        assert!(matches!(tpk.combine(MSG, &dup), Err(ThresholdRsaError::NotEnoughShares { needed: 2, got: 1 })));
+    }
+
+    #[test]
+    fn deal_requires_safe_primes() {
+        let sk = super::super::rsa_bigint::test_key_1024();
+        assert!(matches!(deal(&sk, 1, 3), Err(ThresholdRsaError::NotSafePrimes)));
+        assert!(matches!(dealer_keygen(512, 3, 3), Err(ThresholdRsaError::InvalidParameters)));
+    }
+}
@@ -0,0 +1,171 @@
+// src/group/1-rsa/13-garner.rs
+
+//! Chinese remaindering on `BigUint` with Garner's algorithm.
+//! For pairwise coprime m_1..m_k and residues a_i, the solution mod
+//! M = m_1···m_k is built in mixed radix:
+//!     x = v_1 + v_2·m_1 + v_3·m_1·m_2 + ... + v_k·m_1···m_{k-1}
+//!     v_i = (a_i - x_{i-1}) · t_i  mod m_i,   t_i = (m_1···m_{i-1})^{-1} mod m_i
+//! Every step works mod a single m_i, so nothing is ever reduced mod M.
+//!
+//! `CrtBasis` precomputes the t_i and prefix products once for repeated
+//! recombination (RSA decryption with fixed primes). The t_i are exactly the
+//! coefficients RFC 8017 stores for multi-prime keys (see super::multiprime).
+//! The small `i64` version in super::crt stays as the textbook walkthrough.
+
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CrtError {
+    #[error("no moduli given")]
+    Empty,
+    #[error("expected {expected} residues, got {got}")]
+    LengthMismatch { expected: usize, got: usize },
+    #[error("moduli {0} and {1} are not coprime")]
+    NotCoprime(usize, usize),
+    #[error("modulus {0} is smaller than 2")]
+    InvalidModulus(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrtBasis {
+    moduli: Vec<BigUint>,
+    coeffs: Vec<BigUint>, // coeffs[i] = t_i; coeffs[0] = 1
+    prefix: Vec<BigUint>, // prefix[i] = m_1···m_i (0-based: product of moduli[..i])
+    modulus: BigUint,
+}
+
+impl CrtBasis {
+    pub fn new(moduli: &[BigUint]) -> Result<Self, CrtError> {
+        if moduli.is_empty() {
+            return Err(CrtError::Empty);
+        }
+        if let Some(i) = moduli.iter().position(|m| m < &BigUint::from(2u32)) {
+            return Err(CrtError::InvalidModulus(i));
+        }
+        let mut coeffs = Vec::with_capacity(moduli.len());
+        let mut prefix = Vec::with_capacity(moduli.len());
+        let mut acc = BigUint::one();
+        for (i, m) in moduli.iter().enumerate() {
+            let t = if i == 0 {
+                BigUint::one()
+            } else {
+                match (&acc % m).modinv(m) {
+                    Some(t) => t,
+                    // name the first earlier modulus sharing a factor with m
+                    None => {
+                        let j = moduli[..i].iter().position(|mj| !mj.gcd(m).is_one()).unwrap_or(0);
+                        return Err(CrtError::NotCoprime(j, i));
+                    }
+                }
+            };
+            coeffs.push(t);
+            prefix.push(acc.clone());
+            acc *= m;
+        }
+        Ok(CrtBasis { moduli: moduli.to_vec(), coeffs, prefix, modulus: acc })
+    }
+
+    pub fn moduli(&self) -> &[BigUint] {
+        &self.moduli
+    }
+
+    /// M = product of all moduli
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// t_i = (m_1···m_{i-1})^{-1} mod m_i (t_0 = 1)
+    pub fn coefficient(&self, i: usize) -> &BigUint {
+        &self.coeffs[i]
+    }
+
+    /// The unique x in [0, M) with x ≡ a_i (mod m_i). Residues need not be
+    /// reduced.
+    pub fn recombine(&self, residues: &[BigUint]) -> Result<BigUint, CrtError> {
+        if residues.len() != self.moduli.len() {
+            return Err(CrtError::LengthMismatch { expected: self.moduli.len(), got: residues.len() });
+        }
+        let mut x = BigUint::zero();
+        for (i, (a, m)) in residues.iter().zip(&self.moduli).enumerate() {
+            // v_i = (a_i - x) · t_i mod m_i
+            let a = a % m;
+            let xm = &x % m;
+            let diff = if a >= xm { a - xm } else { a + m - xm };
+            let v = diff * &self.coeffs[i] % m;
+            x += v * &self.prefix[i];
+        }
+        Ok(x)
+    }
+
+    /// x mod m_i for every modulus
+    pub fn reduce(&self, x: &BigUint) -> Vec<BigUint> {
+        self.moduli.iter().map(|m| x % m).collect()
+    }
+
+    // best-effort wipe for bases built from secret primes
+    pub(crate) fn clear(&mut self) {
+        for x in self.moduli.iter_mut().chain(&mut self.coeffs).chain(&mut self.prefix) {
+            x.set_zero();
+        }
+        self.modulus.set_zero();
+    }
+}
+
+/// One-shot CRT; returns (x, M)
+pub fn crt(residues: &[BigUint], moduli: &[BigUint]) -> Result<(BigUint, BigUint), CrtError> {
+    let basis = CrtBasis::new(moduli)?;
+    let x = basis.recombine(residues)?;
+    Ok((x, basis.modulus))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::crt as small;
+
+    fn big(xs: &[u64]) -> Vec<BigUint> {
+        xs.iter().map(|&x| BigUint::from(x)).collect()
+    }
+
+    #[test]
+    fn matches_small_crt() {
+        for (a, n) in [(vec![2i64, 3], vec![3i64, 5]), (vec![2, 3, 2], vec![3, 5, 7]), (vec![10, 4, 0, 6], vec![11, 9, 13, 7])] {
+            let (x_small, m_small) = small::crt(&a, &n).unwrap();
+            let a_big: Vec<u64> = a.iter().map(|&v| v as u64).collect();
+            let n_big: Vec<u64> = n.iter().map(|&v| v as u64).collect();
+            let (x, m) = crt(&big(&a_big), &big(&n_big)).unwrap();
+            assert_eq!((x, m), (BigUint::from(x_small as u64), BigUint::from(m_small as u64)));
+        }
+    }
+
+    #[test]
+    fn basis_reuse_and_large_moduli() {
+        // three Mersenne primes
+        let moduli: Vec<BigUint> = [61u32, 89, 107].iter().map(|&e| (BigUint::one() << e) - 1u32).collect();
+        let basis = CrtBasis::new(&moduli).unwrap();
+        for seed in 1u64..20 {
+            let x = BigUint::from(seed).pow(40) % basis.modulus();
+            let residues = basis.reduce(&x);
+            assert_eq!(basis.recombine(&residues).unwrap(), x);
+        }
+        // unreduced residues are accepted
+        let shifted: Vec<BigUint> = moduli.iter().map(|m| m * 3u32 + 5u32).collect();
+        assert_eq!(basis.recombine(&shifted).unwrap(), BigUint::from(5u32));
+        for i in 1..moduli.len() {
+            let prefix: BigUint = moduli[..i].iter().product();
+            assert!((prefix * basis.coefficient(i) % &moduli[i]).is_one());
+        }
+    }
+
+    #[test]
+    fn rejects_bad_inputs() {
+        assert_eq!(CrtBasis::new(&[]), Err(CrtError::Empty));
+        assert_eq!(CrtBasis::new(&big(&[5, 1])), Err(CrtError::InvalidModulus(1)));
+        assert_eq!(CrtBasis::new(&big(&[6, 7, 15])), Err(CrtError::NotCoprime(0, 2)));
+        let basis = CrtBasis::new(&big(&[3, 5])).unwrap();
+        assert_eq!(basis.recombine(&big(&[1])), Err(CrtError::LengthMismatch { expected: 2, got: 1 }));
+    }
+}
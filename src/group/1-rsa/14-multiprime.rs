@@ -0,0 +1,235 @@
+// src/group/1-rsa/14-multiprime.rs
+
+//! Multi-prime RSA (RFC 8017 §3.2, otherPrimeInfos): n = r_1·r_2···r_u.
+//! - Private key: d plus, for every prime, d_i = d mod (r_i - 1). RFC 8017
+//!   names the first two p = r_1, q = r_2 with qInv = q^{-1} mod p, and each
+//!   further prime carries t_i = (r_1···r_{i-1})^{-1} mod r_i.
+//! - Decryption (§5.1.2 step 2b): m_i = c^{d_i} mod r_i, then Garner.
+//!   Ordering the basis as [q, p, r_3, ..., r_u] makes super::garner's
+//!   coefficients exactly qInv and the t_i, so RFC recombination is a single
+//!   `CrtBasis::recombine`.
+//! - Same hardening as super::rsa_bigint: base blinding and an m^e = c check.
+//!
+//! Each exponentiation runs on |n|/u bits, so u primes cost roughly 1/u² of
+//! a plain c^d mod n. The prime count is capped by modulus size (as OpenSSL
+//! does) because small factors make ECM cheaper than the NFS.
+
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use crate::group::utils::montgomery::modpow;
+use super::garner::CrtBasis;
+use super::rsa_bigint::{prime_for_e, RsaError, RsaPublicKey, DEFAULT_E, MIN_KEY_BITS};
+
+/// Largest prime count `generate` allows for a modulus of `bits` bits
+pub fn max_primes(bits: usize) -> usize {
+    match bits {
+        0..=1023 => 2,
+        1024..=4095 => 3,
+        4096..=8191 => 4,
+        _ => 5,
+    }
+}
+
+/// One entry of RFC 8017 OtherPrimeInfo: (r_i, d_i, t_i)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtherPrimeInfo {
+    pub prime: BigUint,
+    pub exponent: BigUint,
+    pub coefficient: BigUint,
+}
+
+pub struct MultiPrimeRsaKey {
+    public: RsaPublicKey,
+    d: BigUint,
+    primes: Vec<BigUint>,    // r_1..r_u in RFC order
+    exponents: Vec<BigUint>, // d_i = d mod (r_i - 1)
+    basis: CrtBasis,         // over [r_2, r_1, r_3, ..., r_u]
+}
+
+impl Drop for MultiPrimeRsaKey {
+    fn drop(&mut self) {
+        self.d.set_zero();
+        for x in self.primes.iter_mut().chain(&mut self.exponents) {
+            x.set_zero();
+        }
+        self.basis.clear();
+    }
+}
+
+impl std::fmt::Debug for MultiPrimeRsaKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiPrimeRsaKey")
+            .field("public", &self.public)
+            .field("primes", &self.primes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl MultiPrimeRsaKey {
+    /// Fresh key with e = 65537
+    pub fn generate(bits: usize, count: usize) -> Result<Self, RsaError> {
+        Self::generate_with_rng(bits, count, DEFAULT_E, &mut OsRng)
+    }
+
+    pub fn generate_with_rng(bits: usize, count: usize, e: u32, rng: &mut dyn RngCore) -> Result<Self, RsaError> {
+        if bits < MIN_KEY_BITS {
+            return Err(RsaError::KeyTooSmall(MIN_KEY_BITS));
+        }
+        if count < 2 || count > max_primes(bits) {
+            return Err(RsaError::InvalidKey("prime count not allowed for this modulus size"));
+        }
+        let e_big = BigUint::from(e);
+        // spread the bits, larger primes first; with more than two primes the
+        // product can fall a bit short, so retry until n has exactly `bits`
+        let sizes: Vec<usize> = (0..count).map(|i| bits / count + usize::from(i < bits % count)).collect();
+        loop {
+            let primes: Vec<BigUint> = sizes.iter().map(|&b| prime_for_e(b, &e_big, rng)).collect();
+            let n: BigUint = primes.iter().product();
+            if n.bits() as usize != bits {
+                continue;
+            }
+            match Self::from_primes(primes, e_big.clone()) {
+                // equal primes, astronomically unlikely
+                Err(RsaError::InvalidKey(_)) => continue,
+                other => return other,
+            }
+        }
+    }
+
+    /// Build a key from its distinct prime factors, in RFC 8017 order
+    pub fn from_primes(primes: Vec<BigUint>, e: BigUint) -> Result<Self, RsaError> {
+        if primes.len() < 2 {
+            return Err(RsaError::InvalidKey("need at least two primes"));
+        }
+        if primes.iter().any(|r| r <= &BigUint::one()) {
+            return Err(RsaError::InvalidKey("primes must be greater than 1"));
+        }
+        let mut order = primes.clone();
+        order.swap(0, 1);
+        let basis = CrtBasis::new(&order).map_err(|_| RsaError::InvalidKey("primes must be distinct"))?;
+        let public = RsaPublicKey::new(basis.modulus().clone(), e)?;
+        let lambda = primes.iter().fold(BigUint::one(), |acc, r| acc.lcm(&(r - 1u32)));
+        let d = public.e.modinv(&lambda).ok_or(RsaError::InvalidKey("e is not invertible mod λ(n)"))?;
+        let exponents = primes.iter().map(|r| &d % (r - 1u32)).collect();
+        Ok(MultiPrimeRsaKey { public, d, primes, exponents, basis })
+    }
+
+    pub fn public_key(&self) -> &RsaPublicKey {
+        &self.public
+    }
+
+    pub fn size(&self) -> usize {
+        self.public.size()
+    }
+
+    pub fn prime_count(&self) -> usize {
+        self.primes.len()
+    }
+
+    /// qInv = q^{-1} mod p of the two-prime part
+    pub fn qinv(&self) -> &BigUint {
+        self.basis.coefficient(1)
+    }
+
+    /// (r_i, d_i, t_i) for i = 3..u, as stored in otherPrimeInfos
+    pub fn other_prime_infos(&self) -> Vec<OtherPrimeInfo> {
+        (2..self.primes.len())
+            .map(|i| OtherPrimeInfo {
+                prime: self.primes[i].clone(),
+                exponent: self.exponents[i].clone(),
+                coefficient: self.basis.coefficient(i).clone(),
+            })
+            .collect()
+    }
+
+    /// RSADP / RSASP1: c^d mod n via blinded multi-prime CRT
+    pub fn private_op(&self, c: &BigUint) -> Result<BigUint, RsaError> {
+        let n = &self.public.n;
+        if c >= n {
+            return Err(RsaError::OutOfRange);
+        }
+        let (r, r_inv) = loop {
+            let r = OsRng.gen_biguint_range(&BigUint::from(2u32), n);
+            if let Some(r_inv) = r.modinv(n) {
+                break (r, r_inv);
+            }
+        };
+        let blinded = c * modpow(&r, &self.public.e, n) % n;
+        let m = self.crt(&blinded) * r_inv % n;
+        if modpow(&m, &self.public.e, n) != *c {
+            return Err(RsaError::FaultDetected);
+        }
+        Ok(m)
+    }
+
+    fn crt(&self, c: &BigUint) -> BigUint {
+        // residues in basis order [q, p, r_3, ...]
+        let mut residues: Vec<BigUint> = self
+            .primes
+            .iter()
+            .zip(&self.exponents)
+            .map(|(r, d)| modpow(&(c % r), d, r))
+            .collect();
+        residues.swap(0, 1);
+        self.basis.recombine(&residues).expect("one residue per prime")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group::utils::primes::{gen_prime, BailliePsw};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn three_prime_matches_single_modulus() {
+        let mut rng = StdRng::seed_from_u64(44);
+        let key = MultiPrimeRsaKey::generate_with_rng(1024, 3, DEFAULT_E, &mut rng).unwrap();
+        let pk = key.public_key();
+        assert_eq!(pk.n.bits(), 1024);
+        assert_eq!(key.prime_count(), 3);
+        for m in [BigUint::zero(), BigUint::from(42u32), rng.gen_biguint_below(&pk.n), &pk.n - 1u32] {
+            let c = pk.public_op(&m).unwrap();
+            assert_eq!(key.crt(&c), modpow(&c, &key.d, &pk.n));
+            assert_eq!(key.private_op(&c).unwrap(), m);
+        }
+        assert_eq!(key.private_op(&pk.n), Err(RsaError::OutOfRange));
+    }
+
+    #[test]
+    fn rfc8017_coefficients() {
+        let mut rng = StdRng::seed_from_u64(8017);
+        let primes: Vec<BigUint> = (0..4)
+            .map(|_| gen_prime(320, &mut rng, &BailliePsw).to_biguint().unwrap())
+            .collect();
+        let key = MultiPrimeRsaKey::from_primes(primes.clone(), BigUint::from(DEFAULT_E)).unwrap();
+        let (p, q) = (&primes[0], &primes[1]);
+        assert!((key.qinv() * q % p).is_one());
+        let infos = key.other_prime_infos();
+        assert_eq!(infos.len(), 2);
+        for (i, info) in infos.iter().enumerate() {
+            let r = &primes[i + 2];
+            let prefix: BigUint = primes[..i + 2].iter().product();
+            assert_eq!(&info.prime, r);
+            assert_eq!(info.exponent, &key.d % (r - 1u32));
+            assert!((prefix * &info.coefficient % r).is_one());
+        }
+        let c = BigUint::from(0xc0ffee_u32).pow(30) % &key.public.n;
+        assert_eq!(key.crt(&c), modpow(&c, &key.d, &key.public.n));
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        let e = || BigUint::from(DEFAULT_E);
+        let r = |x: u64| BigUint::from(x);
+        assert!(MultiPrimeRsaKey::from_primes(vec![r(4294967291)], e()).is_err());
+        assert!(MultiPrimeRsaKey::from_primes(vec![r(4294967291), r(4294967279), r(4294967291)], e()).is_err());
+        assert_eq!(MultiPrimeRsaKey::generate(512, 2).err(), Some(RsaError::KeyTooSmall(MIN_KEY_BITS)));
+        assert!(MultiPrimeRsaKey::generate(2048, 4).is_err());
+        assert_eq!(max_primes(4096), 4);
+    }
+}
@@ -1,37 +1,11 @@
-use super::gcd::extended_gcd;
-
-/// Compute modular inverse of a modulo m using Extended GCD
-fn mod_inverse(a: i64, m: i64) -> Option<i64> {
-    let (g, x, _) = extended_gcd(a, m);
-    if g != 1 {
-        None // inverse does not exist
-    } else {
-        Some((x % m + m) % m) // normalize to positive
-    }
-}
+use crate::group::utils::number_theory;
 
 /// Chinese Remainder Theorem
-/// Given remainders `a` and moduli `n`, returns solution modulo N = product(n)
+/// Given remainders `a` and moduli `n`, returns solution modulo N = product(n).
+/// None when the moduli are not pairwise coprime or N does not fit in i64;
+/// group::rsa::garner has the precomputed BigUint version.
 pub fn crt(a: &[i64], n: &[i64]) -> Option<(i64, i64)> {
-    assert!(a.len() == n.len(), "Input arrays must have same length");
-    let k = a.len();
-
-    let big_n: i64 = n.iter().product();
-    let mut result = 0i64;
-
-    for i in 0..k {
-        let ni = n[i];
-        let ai = a[i];
-        let ni_hat = big_n / ni;
-
-        if let Some(mi) = mod_inverse(ni_hat, ni) {
-            result += ai * ni_hat * mi;
-        } else {
-            return None; // no inverse → CRT fails
-        }
-    }
-
-    Some(((result % big_n + big_n) % big_n, big_n))
+    number_theory::crt(a, n)
 }
 
 #[cfg(test)]
@@ -1,21 +1,12 @@
 use super::gcd::gcd;
+use crate::group::utils::number_theory;
 
-/// Fast modular exponentiation
-pub fn mod_pow(mut base: i64, mut exp: i64, modulus: i64) -> i64 {
-    if modulus == 1 {
-        return 0;
-    }
-    let mut result = 1i64;
-    base = ((base % modulus) + modulus) % modulus; // normalize base
-
-    while exp > 0 {
-        if exp % 2 == 1 {
-            result = (result * base) % modulus;
-        }
-        base = (base * base) % modulus;
-        exp /= 2;
-    }
-    ((result % modulus) + modulus) % modulus
+/// Fast modular exponentiation (square-and-multiply in Montgomery form)
+/// `exp` must be non-negative and `modulus` positive; a negative exponent
+/// needs an inverse first (see gcd::extended_gcd).
+pub fn mod_pow(base: i64, exp: i64, modulus: i64) -> i64 {
+    assert!(exp >= 0, "mod_pow: negative exponent {exp}");
+    number_theory::mod_pow(&base, &exp, &modulus)
 }
 
 /// Fermat's Little Theorem
@@ -42,4 +33,10 @@ mod tests {
     fn test_fermat_non_coprime() {
         assert!(!fermat_theorem(6, 9)); // gcd(6,9) != 1
     }
+
+    #[test]
+    #[should_panic(expected = "negative exponent")]
+    fn test_mod_pow_rejects_negative_exponent() {
+        mod_pow(3, -1, 7);
+    }
 }
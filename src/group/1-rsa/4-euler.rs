@@ -1,25 +1,12 @@
 use super::gcd::gcd;
 use super::fermat::mod_pow;
+use crate::group::utils::number_theory;
 
-/// Compute Euler's Totient function φ(n)
+/// Compute Euler's Totient function φ(n) for n >= 1
+/// φ(p1^e1···pk^ek) = ∏ pi^(ei-1)·(pi - 1), factoring n by trial division,
+/// Pollard p−1 and rho; every i64 factors within their budget
 pub fn phi(n: i64) -> i64 {
-    let mut result = n;
-    let mut p = 2;
-    let mut nn = n;
-
-    while p * p <= nn {
-        if nn % p == 0 {
-            while nn % p == 0 {
-                nn /= p;
-            }
-            result -= result / p;
-        }
-        p += 1;
-    }
-    if nn > 1 {
-        result -= result / nn;
-    }
-    result
+    number_theory::phi(&n).expect("i64 values always factor")
 }
 
 /// Euler's theorem check: a^φ(n) ≡ 1 (mod n)
@@ -1,19 +1,11 @@
-use super::gcd::gcd;
 use super::euler::phi;
-use super::fermat::mod_pow;
+use crate::group::utils::number_theory;
 
 /// Compute order of an element `a` in multiplicative group mod n
+/// (0 when a is not in the group). Divides φ(n) by each of its prime
+/// factors while a^(φ/q) stays 1, rather than trying every k.
 pub fn order_of_element(a: i64, n: i64) -> i64 {
-    if gcd(a, n) != 1 {
-        return 0; // not in multiplicative group
-    }
-    let phi_n = phi(n);
-    for k in 1..=phi_n {
-        if mod_pow(a, k, n) == 1 {
-            return k;
-        }
-    }
-    phi_n
+    number_theory::order_of_element(&a, &n).unwrap_or(0)
 }
 
 /// Check Lagrange’s theorem: order(a) | φ(n)
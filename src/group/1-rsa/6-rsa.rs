@@ -2,12 +2,16 @@
 
 //! Basic RSA implementation (educational).
 //! Reuses:
-//! - super::gcd::extended_gcd for modular inverse
-//! - super::fermat::mod_pow for modular exponentiation
+//! - super::gcd::extended_gcd for the gcd(e, φ) check
+//! - group::utils::number_theory for modular inverse and exponentiation
+//!   (Montgomery underneath, same engine as Paillier)
+//! - group::utils::primes for the primality check on p and q
 //! - super::euler::phi when needed (but here we compute phi directly from p,q)
 
+use num_bigint::BigInt;
 use super::gcd::extended_gcd;
-use super::fermat::mod_pow;
+use crate::group::utils::number_theory;
+use crate::group::utils::primes;
 
 /// Simple struct to hold RSA public/private values (small-demo)
 #[derive(Debug, Clone, Copy)]
@@ -19,42 +23,21 @@ pub struct KeyPair {
     pub q: i64, // prime 2
 }
 
-/// Compute modular inverse of `a` modulo `m` using extended_gcd.
-/// Returns Some(inv) where 0 <= inv < m, or None if inverse doesn't exist.
+/// Modular inverse of `a` modulo `m`, in [0, m); None if it doesn't exist.
 fn mod_inverse(a: i64, m: i64) -> Option<i64> {
-    let (g, x, _) = extended_gcd(a, m);
-    if g != 1 && g != -1 {
-        None
-    } else {
-        // normalize x to positive modulo m
-        let mut inv = x % m;
-        if inv < 0 {
-            inv += m.abs();
-        }
-        Some(inv)
-    }
+    number_theory::mod_inverse(&a, &m)
+}
+
+/// Modular exponentiation through the shared number-theory helpers.
+/// `base` may be negative; `exp` must be non-negative and `modulus` positive.
+fn mod_pow(base: i64, exp: i64, modulus: i64) -> i64 {
+    assert!(exp >= 0, "mod_pow: negative exponent {exp}");
+    number_theory::mod_pow(&base, &exp, &modulus)
 }
 
-/// Very small and simple primality check (trial division).
-/// Good enough for tiny demo primes; replace with Miller-Rabin for real use.
+/// Primality check through the shared Baillie–PSW test (exact for i64).
 fn is_prime(n: i64) -> bool {
-    if n <= 1 {
-        return false;
-    }
-    if n <= 3 {
-        return true;
-    }
-    if n % 2 == 0 {
-        return false;
-    }
-    let mut i = 3;
-    while i * i <= n {
-        if n % i == 0 {
-            return false;
-        }
-        i += 2;
-    }
-    true
+    n > 1 && primes::is_prime(&BigInt::from(n))
 }
 
 /// Generate an RSA keypair given two primes p and q and a public exponent e.
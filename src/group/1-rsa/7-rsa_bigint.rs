@@ -0,0 +1,300 @@
+// src/group/1-rsa/7-rsa_bigint.rs
+
+//! RSA at real key sizes (RFC 8017 §3–5) on `BigUint`.
+//! - Keys: n = p·q with p, q from the shared prime generator
+//!   (group::utils::primes), e = 65537 by default, d = e^{-1} mod λ(n).
+//! - Private operation: CRT with m = c^dp mod p, c^dq mod q recombined by
+//!   Garner's formula, base blinding (c·r^e, unblinded by r^{-1}) so timing
+//!   does not depend on c, and a final m^e = c check against fault attacks.
+//! - Padding schemes built on top: super::oaep (encryption) and super::pss
+//!   (signatures), both with SHA-256.
+//!
+//! The toy i64 version in super::rsa stays as the textbook walkthrough.
+
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use thiserror::Error;
+use crate::group::utils::montgomery::modpow;
+use crate::group::utils::primes::{gen_prime, BailliePsw};
+
+/// Default public exponent F4
+pub const DEFAULT_E: u32 = 65_537;
+
+/// Smallest modulus `generate` will produce
+pub const MIN_KEY_BITS: usize = 1024;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum RsaError {
+    #[error("invalid RSA key: {0}")]
+    InvalidKey(&'static str),
+    #[error("modulus must have at least {0} bits")]
+    KeyTooSmall(usize),
+    #[error("message too long")]
+    MessageTooLong,
+    #[error("integer out of range for this modulus")]
+    OutOfRange,
+    #[error("decryption error")]
+    Decryption,
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("private-key operation failed its consistency check")]
+    FaultDetected,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RsaPublicKey {
+    pub n: BigUint,
+    pub e: BigUint,
+}
+
+impl RsaPublicKey {
+    pub fn new(n: BigUint, e: BigUint) -> Result<Self, RsaError> {
+        if n.is_even() || n.bits() < 64 {
+            return Err(RsaError::InvalidKey("modulus must be odd and at least 64 bits"));
+        }
+        if e.is_even() || e < BigUint::from(3u32) || e >= n {
+            return Err(RsaError::InvalidKey("public exponent must be odd and in [3, n)"));
+        }
+        Ok(RsaPublicKey { n, e })
+    }
+
+    /// Modulus length in bytes (k in RFC 8017)
+    pub fn size(&self) -> usize {
+        (self.n.bits() as usize).div_ceil(8)
+    }
+
+    /// RSAEP / RSAVP1: m^e mod n
+    pub fn public_op(&self, m: &BigUint) -> Result<BigUint, RsaError> {
+        if m >= &self.n {
+            return Err(RsaError::OutOfRange);
+        }
+        Ok(modpow(m, &self.e, &self.n))
+    }
+}
+
+pub struct RsaPrivateKey {
+    pub(crate) public: RsaPublicKey,
+    pub(crate) d: BigUint,
+    pub(crate) p: BigUint,
+    pub(crate) q: BigUint,
+    pub(crate) dp: BigUint,   // d mod (p-1)
+    pub(crate) dq: BigUint,   // d mod (q-1)
+    pub(crate) qinv: BigUint, // q^{-1} mod p
+}
+
+impl Drop for RsaPrivateKey {
+    fn drop(&mut self) {
+        // best-effort, as for the Paillier key
+        for x in [&mut self.d, &mut self.p, &mut self.q, &mut self.dp, &mut self.dq, &mut self.qinv] {
+            x.set_zero();
+        }
+    }
+}
+
+impl std::fmt::Debug for RsaPrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RsaPrivateKey").field("public", &self.public).finish_non_exhaustive()
+    }
+}
+
+impl RsaPrivateKey {
+    /// Fresh key with e = 65537
+    pub fn generate(bits: usize) -> Result<Self, RsaError> {
+        Self::generate_with_rng(bits, DEFAULT_E, &mut OsRng)
+    }
+
+    pub fn generate_with_rng(bits: usize, e: u32, rng: &mut dyn RngCore) -> Result<Self, RsaError> {
+        if bits < MIN_KEY_BITS {
+            return Err(RsaError::KeyTooSmall(MIN_KEY_BITS));
+        }
+        let e_big = BigUint::from(e);
+        // top two bits of each prime are set, so n has exactly `bits` bits
+        let (p_bits, q_bits) = (bits.div_ceil(2), bits / 2);
+        let min_gap = BigUint::one() << (q_bits - 100);
+        loop {
+            let p = prime_for_e(p_bits, &e_big, rng);
+            let q = prime_for_e(q_bits, &e_big, rng);
+            // FIPS 186-4 B.3.1: |p - q| > 2^(nlen/2 - 100)
+            let gap = if p > q { &p - &q } else { &q - &p };
+            if gap <= min_gap {
+                continue;
+            }
+            return Self::from_primes(p, q, e_big);
+        }
+    }
+
+    /// Build a key from its prime factors
+    pub fn from_primes(p: BigUint, q: BigUint, e: BigUint) -> Result<Self, RsaError> {
+        let one = BigUint::one();
+        if p <= one || q <= one || p == q {
+            return Err(RsaError::InvalidKey("p and q must be distinct primes"));
+        }
+        let lambda = (&p - 1u32).lcm(&(&q - 1u32));
+        let d = e.modinv(&lambda).ok_or(RsaError::InvalidKey("e is not invertible mod λ(n)"))?;
+        Self::from_components(&p * &q, e, d, p, q)
+    }
+
+    /// Build a key from (n, e, d, p, q) and check that they fit together
+    pub fn from_components(n: BigUint, e: BigUint, d: BigUint, p: BigUint, q: BigUint) -> Result<Self, RsaError> {
+        if &p * &q != n {
+            return Err(RsaError::InvalidKey("n != p·q"));
+        }
+        let public = RsaPublicKey::new(n, e)?;
+        let lambda = (&p - 1u32).lcm(&(&q - 1u32));
+        if (&public.e * &d) % &lambda != BigUint::one() {
+            return Err(RsaError::InvalidKey("e·d != 1 mod λ(n)"));
+        }
+        let qinv = q.modinv(&p).ok_or(RsaError::InvalidKey("q is not invertible mod p"))?;
+        Ok(RsaPrivateKey {
+            dp: &d % (&p - 1u32),
+            dq: &d % (&q - 1u32),
+            qinv,
+            public, d, p, q,
+        })
+    }
+
+    pub fn public_key(&self) -> &RsaPublicKey {
+        &self.public
+    }
+
+    pub fn size(&self) -> usize {
+        self.public.size()
+    }
+
+    /// RSADP / RSASP1: c^d mod n via blinded CRT
+    pub fn private_op(&self, c: &BigUint) -> Result<BigUint, RsaError> {
+        let n = &self.public.n;
+        if c >= n {
+            return Err(RsaError::OutOfRange);
+        }
+        let (r, r_inv) = loop {
+            let r = OsRng.gen_biguint_range(&BigUint::from(2u32), n);
+            if let Some(r_inv) = r.modinv(n) {
+                break (r, r_inv);
+            }
+        };
+        let blinded = c * modpow(&r, &self.public.e, n) % n;
+        let m = self.crt(&blinded) * r_inv % n;
+        if modpow(&m, &self.public.e, n) != *c {
+            return Err(RsaError::FaultDetected);
+        }
+        Ok(m)
+    }
+
+    // Garner: m = m2 + q·(qinv·(m1 - m2) mod p)
+    fn crt(&self, c: &BigUint) -> BigUint {
+        let m1 = modpow(&(c % &self.p), &self.dp, &self.p);
+        let m2 = modpow(&(c % &self.q), &self.dq, &self.q);
+        let diff = (&m1 + &self.p - (&m2 % &self.p)) % &self.p;
+        let h = &self.qinv * diff % &self.p;
+        m2 + &self.q * h
+    }
+}
+
+// prime of `bits` bits with gcd(e, p - 1) = 1
+pub(crate) fn prime_for_e(bits: usize, e: &BigUint, rng: &mut dyn RngCore) -> BigUint {
+    loop {
+        let p = gen_prime(bits, rng, &BailliePsw).to_biguint().expect("primes are positive");
+        if (&p - 1u32).gcd(e).is_one() {
+            return p;
+        }
+    }
+}
+
+/// I2OSP: big-endian encoding of x in exactly `len` bytes
+pub fn i2osp(x: &BigUint, len: usize) -> Result<Vec<u8>, RsaError> {
+    let bytes = x.to_bytes_be();
+    if x.is_zero() {
+        return Ok(vec![0; len]);
+    }
+    if bytes.len() > len {
+        return Err(RsaError::OutOfRange);
+    }
+    let mut out = vec![0u8; len - bytes.len()];
+    out.extend_from_slice(&bytes);
+    Ok(out)
+}
+
+/// OS2IP: big-endian bytes to integer
+pub fn os2ip(bytes: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(bytes)
+}
+
+/// 1024-bit key from the mbed TLS PSA test suite, used by the OAEP/PSS vectors
+#[cfg(test)]
+pub(crate) fn test_key_1024() -> RsaPrivateKey {
+    let h = |s: &str| BigUint::parse_bytes(s.as_bytes(), 16).unwrap();
+    RsaPrivateKey::from_components(
+        h(TEST_N_1024),
+        BigUint::from(DEFAULT_E),
+        h(concat!(
+            "874bf0ffc2f2a71d14671ddd0171c954d7fdbf50281e4f6d99ea0e1ebcf82faa58e7b595ffb293d1abe17f110b37c48c",
+            "c0f36c37e84d876621d327f64bbe08457d3ec4098ba2fa0a319fba411c2841ed7be83196a8cdf9daa5d00694bc335fc4",
+            "c32217fe0488bce9cb7202e59468b1ead119000477db2ca797fac19eda3f58c1",
+        )),
+        h("e2ab760841bb9d30a81d222de1eb7381d82214407f1b975cbbfe4e1a9467fd98adbd78f607836ca5be1928b9d160d97fd45c12d6b52e2c9871a174c66b488113"),
+        h("c5ab27602159ae7d6f20c3c2ee851e46dc112e689e28d5fcbbf990a99ef8a90b8bb44fd36467e7fc1789ceb663abda338652c3c73f111774902e840565927091"),
+    )
+    .unwrap()
+}
+
+#[cfg(test)]
+pub(crate) const TEST_N_1024: &str = concat!(
+    "af057d396ee84fb75fdbb5c2b13c7fe5a654aa8aa2470b541ee1feb0b12d25c79711531249e1129628042dbbb6c120d1",
+    "443524ef4c0e6e1d8956eeb2077af12349ddeee54483bc06c2c61948cd02b202e796aebd94d3a7cbf859c2c1819c324c",
+    "b82b9cd34ede263a2abffe4733f077869e8660f7d6834da53d690ef7985f6bc3",
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn keygen_and_raw_roundtrip() {
+        let mut rng = StdRng::seed_from_u64(40);
+        let key = RsaPrivateKey::generate_with_rng(1024, DEFAULT_E, &mut rng).unwrap();
+        let pk = key.public_key();
+        assert_eq!(pk.n.bits(), 1024);
+        assert_eq!(pk.size(), 128);
+        for m in [BigUint::from(0u32), BigUint::from(42u32), &pk.n - 1u32] {
+            let c = pk.public_op(&m).unwrap();
+            assert_eq!(key.private_op(&c).unwrap(), m);
+        }
+        assert_eq!(pk.public_op(&pk.n), Err(RsaError::OutOfRange));
+        assert_eq!(key.private_op(&pk.n), Err(RsaError::OutOfRange));
+        assert_eq!(RsaPrivateKey::generate(512).err(), Some(RsaError::KeyTooSmall(MIN_KEY_BITS)));
+    }
+
+    #[test]
+    fn crt_matches_plain_exponentiation() {
+        let key = test_key_1024();
+        let n = &key.public.n;
+        let c = BigUint::from(0xdead_beef_u64).pow(20) % n;
+        assert_eq!(key.crt(&c), modpow(&c, &key.d, n));
+        assert_eq!(key.dp, &key.d % (&key.p - 1u32));
+    }
+
+    #[test]
+    fn rejects_inconsistent_components() {
+        let key = test_key_1024();
+        let k = |x: &BigUint| x.clone();
+        let bad_d = &key.d + 2u32;
+        assert!(RsaPrivateKey::from_components(k(&key.public.n), k(&key.public.e), bad_d, k(&key.p), k(&key.q)).is_err());
+        assert!(RsaPrivateKey::from_components(&key.public.n + 2u32, k(&key.public.e), k(&key.d), k(&key.p), k(&key.q)).is_err());
+        assert!(RsaPublicKey::new(k(&key.public.n), BigUint::from(4u32)).is_err());
+        assert!(RsaPublicKey::new(&key.public.n + 1u32, BigUint::from(3u32)).is_err());
+    }
+
+    #[test]
+    fn i2osp_os2ip() {
+        assert_eq!(i2osp(&BigUint::from(0x0102u32), 4).unwrap(), vec![0, 0, 1, 2]);
+        assert_eq!(i2osp(&BigUint::zero(), 2).unwrap(), vec![0, 0]);
+        assert_eq!(i2osp(&BigUint::from(0x10000u32), 2), Err(RsaError::OutOfRange));
+        assert_eq!(os2ip(&[0, 0, 1, 2]), BigUint::from(0x0102u32));
+    }
+}
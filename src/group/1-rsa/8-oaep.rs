@@ -0,0 +1,200 @@
+// src/group/1-rsa/8-oaep.rs
+
+//! RSAES-OAEP (RFC 8017 §7.1) with SHA-256 and MGF1-SHA-256.
+//! - encrypt: EM = 0x00 || maskedSeed || maskedDB with DB = lHash || PS || 0x01 || M
+//! - decrypt: every padding failure maps to the same `RsaError::Decryption`
+//!   and the checks run over the whole block, so a failing ciphertext does
+//!   not tell the caller where the padding broke (Manger's attack).
+//!
+//! The label is optional; `None` and `Some(b"")` are the same empty label.
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use super::rsa_bigint::{i2osp, os2ip, RsaError, RsaPrivateKey, RsaPublicKey};
+
+/// SHA-256 output length
+pub const HASH_LEN: usize = 32;
+
+/// MGF1 with SHA-256 (RFC 8017 §B.2.1)
+pub fn mgf1_sha256(seed: &[u8], len: usize) -> Vec<u8> {
+    mgf1::<Sha256>(seed, len)
+}
+
+/// MGF1 over any hash; super::blind uses it with SHA-384
+pub fn mgf1<D: Digest>(seed: &[u8], len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(len + <D as Digest>::output_size());
+    let mut counter = 0u32;
+    while out.len() < len {
+        let mut h = D::new();
+        h.update(seed);
+        h.update(counter.to_be_bytes());
+        out.extend_from_slice(&h.finalize());
+        counter += 1;
+    }
+    out.truncate(len);
+    out
+}
+
+/// Largest message `encrypt` accepts under this key
+pub fn max_message_len(pk: &RsaPublicKey) -> usize {
+    pk.size().saturating_sub(2 * HASH_LEN + 2)
+}
+
+pub fn encrypt(pk: &RsaPublicKey, msg: &[u8], label: Option<&[u8]>) -> Result<Vec<u8>, RsaError> {
+    encrypt_with_rng(pk, msg, label, &mut OsRng)
+}
+
+pub fn encrypt_with_rng(pk: &RsaPublicKey, msg: &[u8], label: Option<&[u8]>, rng: &mut dyn RngCore) -> Result<Vec<u8>, RsaError> {
+    let k = pk.size();
+    if k < 2 * HASH_LEN + 2 || msg.len() > max_message_len(pk) {
+        return Err(RsaError::MessageTooLong);
+    }
+    // DB = lHash || PS || 0x01 || M
+    let mut db = Vec::with_capacity(k - HASH_LEN - 1);
+    db.extend_from_slice(&Sha256::digest(label.unwrap_or_default()));
+    db.resize(k - msg.len() - HASH_LEN - 2, 0);
+    db.push(0x01);
+    db.extend_from_slice(msg);
+
+    let mut seed = [0u8; HASH_LEN];
+    rng.fill_bytes(&mut seed);
+    xor_in_place(&mut db, &mgf1_sha256(&seed, k - HASH_LEN - 1));
+    xor_in_place(&mut seed, &mgf1_sha256(&db, HASH_LEN));
+
+    let mut em = Vec::with_capacity(k);
+    em.push(0x00);
+    em.extend_from_slice(&seed);
+    em.extend_from_slice(&db);
+    let c = pk.public_op(&os2ip(&em))?;
+    i2osp(&c, k)
+}
+
+pub fn decrypt(sk: &RsaPrivateKey, ct: &[u8], label: Option<&[u8]>) -> Result<Vec<u8>, RsaError> {
+    let k = sk.size();
+    if ct.len() != k || k < 2 * HASH_LEN + 2 {
+        return Err(RsaError::Decryption);
+    }
+    let m = sk.private_op(&os2ip(ct)).map_err(|_| RsaError::Decryption)?;
+    let em = i2osp(&m, k).map_err(|_| RsaError::Decryption)?;
+
+    let (y, rest) = em.split_at(1);
+    let (masked_seed, masked_db) = rest.split_at(HASH_LEN);
+    let mut seed = masked_seed.to_vec();
+    xor_in_place(&mut seed, &mgf1_sha256(masked_db, HASH_LEN));
+    let mut db = masked_db.to_vec();
+    xor_in_place(&mut db, &mgf1_sha256(&seed, k - HASH_LEN - 1));
+
+    // accumulate all checks without branching on secret data
+    let l_hash = Sha256::digest(label.unwrap_or_default());
+    let mut bad = y[0];
+    for (a, b) in db[..HASH_LEN].iter().zip(l_hash.iter()) {
+        bad |= a ^ b;
+    }
+    // index of the 0x01 separator; anything but zeros before it is bad
+    let mut found = 0u8;
+    let mut index = 0usize;
+    for (i, &byte) in db[HASH_LEN..].iter().enumerate() {
+        let is_one = ct_eq(byte, 0x01);
+        let is_zero = ct_eq(byte, 0x00);
+        let first_one = is_one & !found;
+        index |= (first_one as usize).wrapping_neg() & i;
+        found |= is_one;
+        bad |= !found & !is_zero & 0x01;
+    }
+    bad |= !found & 0x01;
+    if bad != 0 {
+        return Err(RsaError::Decryption);
+    }
+    Ok(db[HASH_LEN + index + 1..].to_vec())
+}
+
+// 0x01 when a == b, else 0x00
+fn ct_eq(a: u8, b: u8) -> u8 {
+    let x = (a ^ b) as u16;
+    (((x.wrapping_sub(1)) >> 8) & 1) as u8
+}
+
+fn xor_in_place(dst: &mut [u8], mask: &[u8]) {
+    for (d, m) in dst.iter_mut().zip(mask) {
+        *d ^= m;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::rsa_bigint::test_key_1024;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    const LABEL: &[u8] = b"this\0is\0a\0label\0";
+    const MSG: &[u8] = b"this is no squeamish ossifrage";
+
+    // mbed TLS test_suite_psa_crypto.data, RSA OAEP-SHA-256 decrypt vectors
+    const CT_EMPTY: &str = concat!(
+        "3d3146b1c982004273a9ebb9b063e6ae53b1a85bfc802324bcdd04faa0f7211fb2bdeea40358095554df9c250866c736",
+        "1e738f0d270eaa27738e87928c5e31815506346727900ff03cef0be6f9dd6bba63ce89074e8194fe68b5a5739422d4f1",
+        "38bbbb61f49b76cf1f18def2c993e3113b08c191ea1da0feb94f8fd9b30109a1",
+    );
+    const CT_EMPTY_LABEL: &str = concat!(
+        "14e57648fbbd3c2c195d71fcb9b6c332e2ad9e3402aa701e7270b05775e9ddd025e2330d7b84e67866524c67f9c38b11",
+        "e4679e28a38574b47f8d218a1a04a7466754d6ea7f959ab1f5b85d066d3f90076e8219f66653f7b78a9789d76213505b",
+        "4e75ec28081608ed2f1ea1238e3eeab011ce4ec147327cd0ca029c2818133cb6",
+    );
+    const CT_MSG: &str = concat!(
+        "3fd3c81e3919a19014400d91098090f273312e0150e09eff7f66fb9624d2ec9764fc80befcb592e9d102493c882b8bc0",
+        "334a257e73aba23a0ee13f826cbc64f8200b9150784d004ccb2955c877c95ab888e3917f423dd52f3c8a49cb61c1966e",
+        "c04f336068729ae0bce7d7fb3e680f9d15d658db9b906efcbf2c2fae45e75429",
+    );
+    const CT_MSG_LABEL: &str = concat!(
+        "46edc9984a6d4b7c7fd88fda9ea91ddbd30b28a0793cc75a9fcdd94d867c69090a697d46a6f336a3e48a122dd3ee3b51",
+        "566b445ff78adb613d09b7d8c59c25a27d8cf7f5e36455f2e71ff6c6ee98d5740e66b23794acc72906561951c2be5064",
+        "f6a250646ab627ecbfa48c02f82c29fe9b8c8e6be8eb752432124974373b542c",
+    );
+
+    #[test]
+    fn decrypts_known_answer_vectors() {
+        let sk = test_key_1024();
+        assert_eq!(decrypt(&sk, &hex(CT_EMPTY), None).unwrap(), b"");
+        assert_eq!(decrypt(&sk, &hex(CT_EMPTY_LABEL), Some(LABEL)).unwrap(), b"");
+        assert_eq!(decrypt(&sk, &hex(CT_MSG), None).unwrap(), MSG);
+        assert_eq!(decrypt(&sk, &hex(CT_MSG_LABEL), Some(LABEL)).unwrap(), MSG);
+
+        // wrong label, corrupted ciphertext, wrong length
+        assert_eq!(decrypt(&sk, &hex(CT_MSG), Some(&[0])), Err(RsaError::Decryption));
+        let mut corrupt = hex(CT_MSG);
+        *corrupt.last_mut().unwrap() = 0x28;
+        assert_eq!(decrypt(&sk, &corrupt, None), Err(RsaError::Decryption));
+        assert_eq!(decrypt(&sk, &hex(CT_MSG)[1..], None), Err(RsaError::Decryption));
+    }
+
+    #[test]
+    fn encrypt_decrypt_roundtrip() {
+        let sk = test_key_1024();
+        let pk = sk.public_key();
+        let max = max_message_len(pk);
+        assert_eq!(max, 62);
+        for msg in [&b""[..], MSG, &vec![0xab; max]] {
+            let ct = encrypt(pk, msg, Some(LABEL)).unwrap();
+            assert_eq!(ct.len(), pk.size());
+            assert_eq!(decrypt(&sk, &ct, Some(LABEL)).unwrap(), msg);
+            assert_eq!(decrypt(&sk, &ct, None), Err(RsaError::Decryption));
+        }
+        // randomized: same message, different ciphertexts
+        assert_ne!(encrypt(pk, MSG, None).unwrap(), encrypt(pk, MSG, None).unwrap());
+        assert_eq!(encrypt(pk, &vec![0; max + 1], None), Err(RsaError::MessageTooLong));
+    }
+
+    #[test]
+    fn mgf1_prefix_property() {
+        let long = mgf1_sha256(b"seed", 100);
+        assert_eq!(&long[..40], &mgf1_sha256(b"seed", 40)[..]);
+        let mut h = Sha256::new();
+        h.update(b"seed");
+        h.update([0, 0, 0, 0]);
+        assert_eq!(&long[..HASH_LEN], &h.finalize()[..]);
+    }
+}
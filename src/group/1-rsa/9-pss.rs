@@ -0,0 +1,240 @@
+// src/group/1-rsa/9-pss.rs
+
+//! RSASSA-PSS (RFC 8017 §8.1, §9.1) with SHA-256 and MGF1-SHA-256.
+//! - sign: EM = maskedDB || H || 0xbc with H = SHA-256(0^8 || mHash || salt),
+//!   DB = PS || 0x01 || salt; salt length defaults to the hash length.
+//! - verify: the salt length is read back from DB unless the caller pins it,
+//!   so signatures from signers using other salt lengths (0, 94, ...) verify.
+//! - emBits = modBits - 1: the top 8·emLen - emBits bits of maskedDB are
+//!   cleared, and EM is one byte shorter than the signature when
+//!   modBits ≡ 1 (mod 8).
+//!
+//! `*_digest` variants take mHash directly, for callers that hash elsewhere;
+//! its length is not checked, matching the PSA "sign hash" vectors. The
+//! `*_with` functions take the hash as a type parameter (super::blind uses
+//! SHA-384); the public API here is SHA-256 only.
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use super::oaep::{mgf1, HASH_LEN};
+use super::rsa_bigint::{i2osp, os2ip, RsaError, RsaPrivateKey, RsaPublicKey};
+
+/// Salt length used by `sign`
+pub const SALT_LEN: usize = HASH_LEN;
+
+pub fn sign(sk: &RsaPrivateKey, msg: &[u8]) -> Result<Vec<u8>, RsaError> {
+    sign_digest(sk, &Sha256::digest(msg), &mut OsRng)
+}
+
+pub fn sign_digest(sk: &RsaPrivateKey, m_hash: &[u8], rng: &mut dyn RngCore) -> Result<Vec<u8>, RsaError> {
+    let mut salt = [0u8; SALT_LEN];
+    rng.fill_bytes(&mut salt);
+    sign_digest_with_salt(sk, m_hash, &salt)
+}
+
+/// Deterministic core of `sign`: the caller supplies the salt
+pub fn sign_digest_with_salt(sk: &RsaPrivateKey, m_hash: &[u8], salt: &[u8]) -> Result<Vec<u8>, RsaError> {
+    let em_bits = sk.public_key().n.bits() as usize - 1;
+    let em = encode(m_hash, salt, em_bits)?;
+    let s = sk.private_op(&os2ip(&em))?;
+    i2osp(&s, sk.size())
+}
+
+/// Verify with the salt length recovered from the signature
+pub fn verify(pk: &RsaPublicKey, msg: &[u8], sig: &[u8]) -> Result<(), RsaError> {
+    verify_digest(pk, &Sha256::digest(msg), sig, None)
+}
+
+/// Verify against mHash; `salt_len = Some(len)` rejects any other salt length
+pub fn verify_digest(pk: &RsaPublicKey, m_hash: &[u8], sig: &[u8], salt_len: Option<usize>) -> Result<(), RsaError> {
+    verify_digest_with::<Sha256>(pk, m_hash, sig, salt_len)
+}
+
+pub(crate) fn verify_digest_with<D: Digest>(pk: &RsaPublicKey, m_hash: &[u8], sig: &[u8], salt_len: Option<usize>) -> Result<(), RsaError> {
+    if sig.len() != pk.size() {
+        return Err(RsaError::InvalidSignature);
+    }
+    let m = pk.public_op(&os2ip(sig)).map_err(|_| RsaError::InvalidSignature)?;
+    let em_bits = pk.n.bits() as usize - 1;
+    let em = i2osp(&m, em_bits.div_ceil(8)).map_err(|_| RsaError::InvalidSignature)?;
+    if decode::<D>(m_hash, &em, em_bits, salt_len) {
+        Ok(())
+    } else {
+        Err(RsaError::InvalidSignature)
+    }
+}
+
+pub(crate) fn encode(m_hash: &[u8], salt: &[u8], em_bits: usize) -> Result<Vec<u8>, RsaError> {
+    encode_with::<Sha256>(m_hash, salt, em_bits)
+}
+
+// EMSA-PSS-ENCODE
+pub(crate) fn encode_with<D: Digest>(m_hash: &[u8], salt: &[u8], em_bits: usize) -> Result<Vec<u8>, RsaError> {
+    let h_len = <D as Digest>::output_size();
+    let em_len = em_bits.div_ceil(8);
+    if em_len < h_len + salt.len() + 2 {
+        return Err(RsaError::MessageTooLong);
+    }
+    let h = salted_hash::<D>(m_hash, salt);
+    let mut db = vec![0u8; em_len - salt.len() - h_len - 2];
+    db.push(0x01);
+    db.extend_from_slice(salt);
+    for (d, m) in db.iter_mut().zip(mgf1::<D>(&h, em_len - h_len - 1)) {
+        *d ^= m;
+    }
+    db[0] &= top_mask(em_len, em_bits);
+
+    let mut em = db;
+    em.extend_from_slice(&h);
+    em.push(0xbc);
+    Ok(em)
+}
+
+// EMSA-PSS-VERIFY
+fn decode<D: Digest>(m_hash: &[u8], em: &[u8], em_bits: usize, salt_len: Option<usize>) -> bool {
+    let h_len = <D as Digest>::output_size();
+    let em_len = em.len();
+    if em_len < h_len + salt_len.unwrap_or(0) + 2 {
+        return false;
+    }
+    if em[em_len - 1] != 0xbc {
+        return false;
+    }
+    let (masked_db, h) = em[..em_len - 1].split_at(em_len - h_len - 1);
+    let mask = top_mask(em_len, em_bits);
+    if masked_db[0] & !mask != 0 {
+        return false;
+    }
+    let mut db: Vec<u8> = masked_db.iter().zip(mgf1::<D>(h, masked_db.len())).map(|(a, b)| a ^ b).collect();
+    db[0] &= mask;
+
+    // DB = 0x00 .. 0x00 || 0x01 || salt
+    let Some(sep) = db.iter().position(|&b| b != 0) else { return false; };
+    if db[sep] != 0x01 {
+        return false;
+    }
+    let salt = &db[sep + 1..];
+    if salt_len.is_some_and(|len| len != salt.len()) {
+        return false;
+    }
+    salted_hash::<D>(m_hash, salt)[..] == *h
+}
+
+// H = Hash(0x00 * 8 || mHash || salt)
+fn salted_hash<D: Digest>(m_hash: &[u8], salt: &[u8]) -> Vec<u8> {
+    let mut hasher = D::new();
+    hasher.update([0u8; 8]);
+    hasher.update(m_hash);
+    hasher.update(salt);
+    hasher.finalize().to_vec()
+}
+
+// keeps the low emBits bits of the leading byte
+fn top_mask(em_len: usize, em_bits: usize) -> u8 {
+    0xff >> (8 * em_len - em_bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+    use super::super::rsa_bigint::{test_key_1024, TEST_N_1024};
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    fn public(n: &str) -> RsaPublicKey {
+        RsaPublicKey::new(BigUint::parse_bytes(n.as_bytes(), 16).unwrap(), BigUint::from(65_537u32)).unwrap()
+    }
+
+    #[test]
+    fn verifies_prehashed_vectors() {
+        // mbed TLS test_suite_psa_crypto.data, PSS-SHA-256 verify with mHash input
+        let pk = public(TEST_N_1024);
+        let cases = [
+            ("", concat!(
+                "34c011b625c32d992f4ab8fcfa52b616ea66270b5b75a4fc71af712f9b8806bcdd374ce50eafcbb489562b93347885f9",
+                "3c2de1d404c45cacccefceb112ff6ffdfe4264f91d66320bbbe09304b851b8ad6280bbccc571eebcd49c7db5dfa399a6",
+                "289e1978407904598751613d9870770cdd8507e3dc7b46851dbf05ae1df2988d",
+            )),
+            ("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad", concat!(
+                "1967ae568cc071dfebeeca76b11d40bd1ec5af241c50b3dcceff21f4536c0693a7179a8d5d163a7625fefd37c1611278",
+                "00edeebc24fa73ca772096827bd3f75e8ccf2c64f07b7171b5c99022a4d73b760f34a385ccff0bd5ed7997d2a29d2847",
+                "acb0767f93a2a404bc046c97de66d95dc9f7646fdb216b627b2ea0de8afcefb7",
+            )),
+            (&"aa".repeat(129), concat!(
+                "1491cead330b4ad5b092f8351518141ac11d0888591572669c1e79d6e932c488acd62d44479b0e14cd91a048778bc023",
+                "98a772ad6bdb4f7764780cf0afe70293d0cac86f2695a1dcb54568bb37d7086f9e86f95a6802d2ee5a4facaa762beff5",
+                "261bb2816b62cb5af86404974c3f6b67985ac1fbfdf46d6de54f6e29d9274308",
+            )),
+        ];
+        for (m_hash, sig) in &cases {
+            assert!(verify_digest(&pk, &hex(m_hash), &hex(sig), None).is_ok(), "mHash {m_hash:?}");
+        }
+        // SHA-256("abc") signed over the message directly
+        assert!(verify(&pk, b"abc", &hex(cases[1].1)).is_ok());
+        assert!(verify(&pk, b"abd", &hex(cases[1].1)).is_err());
+    }
+
+    #[test]
+    fn verifies_salt_length_vectors() {
+        // mbed TLS test_suite_pkcs1_v21.data, pkcs1_rsassa_pss_verify_ext
+        let n1024 = concat!(
+            "00dd118a9f99bab068ca2aea3b6a6d5997ed4ec954e40deecea07da01eaae80ec2bb1340db8a128e891324a5c5f5fad8",
+            "f590d7c8cacbc5fe931dafda1223735279461abaa0572b761631b3a8afe7389b088b63993a0a25ee45d21858bab9931a",
+            "edd4589a631b37fcf714089f856549f359326dd1e0e86dde52ed66b4a90bda4095",
+        );
+        let sig_salt94 = concat!(
+            "0d2bdb0456a3d651d5bd48a4204493898f72cf1aaddd71387cc058bc3f4c235ea6be4010fd61b28e1fbb275462b53775",
+            "c04be9022d38b6a2e0387dddba86a3f8554d2858044a59fddbd594753fc056fe33c8daddb85dc70d164690b1182209ff",
+            "84824e0be10e35c379f2f378bf176a9f7cb94d95e44d90276a298c8810f741c9",
+        );
+        let sig_salt0 = concat!(
+            "7fc506d26ca3b22922a1ce39faaedd273161b82d9443c56f1a034f131ae4a18cae1474271cb4b66a17d9707ca58b0bdb",
+            "d3c406b7e65bbcc9bbbce94dc45de807b4989b23b3e4db74ca29298137837eb90cc83d3219249bc7d480fceaf075203a",
+            "86e54c4ecfa4e312e39f8f69d76534089a36ed9049ca9cfd5ab1db1fa75fe5c8",
+        );
+        let pk = public(n1024);
+        let digest = Sha256::digest(b"Test message");
+        assert!(verify(&pk, b"Test message", &hex(sig_salt94)).is_ok());
+        assert!(verify(&pk, b"Test message", &hex(sig_salt0)).is_ok());
+        assert!(verify_digest(&pk, &digest, &hex(sig_salt94), Some(94)).is_ok());
+        assert!(verify_digest(&pk, &digest, &hex(sig_salt94), Some(SALT_LEN)).is_err());
+        assert!(verify_digest(&pk, &digest, &hex(sig_salt0), Some(0)).is_ok());
+
+        // 512-bit modulus, empty message; last byte flipped is rejected
+        let pk = public("00b076d23250816f9aab02307e452b97f0cae7598369b41624e8afc7971a59a13892f64b07eaa6ec928c160b2d6ec8f9d0dd5b63c8b3ac0767b4f65c892f56c10f");
+        let sig = "ace8b03347da1b9a7a5e94a0d76359bb39c819bb170bef38ea84995ed653446c0ae87ede434cdf9d0cb2d7bf164cf427892363e6855a1d24d0ce5dd72acaf246";
+        assert!(verify(&pk, b"", &hex(sig)).is_ok());
+        let mut bad = hex(sig);
+        *bad.last_mut().unwrap() = 0x47;
+        assert_eq!(verify(&pk, b"", &bad), Err(RsaError::InvalidSignature));
+
+        // 521-bit modulus: EM is one byte shorter than the signature
+        let pk = public("0131b69860f3cb9bf85ea358fdf2bd2990f1b77a80d6a4fdf817a43dd896bdf7dd26af8ac0237f526e0d33b105c971fdbd4ffa9ece99fc469f31ecf429e8f562c1c3");
+        let sig = "009c4941157fa36288e467310b198ab0c615c40963d611ffeef03000549ded809235955ecc57adba44782e9497c004f480ba2b3d58db8335fe0b391075c02c843a6d";
+        assert!(verify(&pk, b"A", &hex(sig)).is_ok());
+        assert!(verify(&pk, b"B", &hex(sig)).is_err());
+    }
+
+    #[test]
+    fn sign_verify_roundtrip() {
+        let sk = test_key_1024();
+        let pk = sk.public_key();
+        let sig = sign(&sk, b"wrap this key").unwrap();
+        assert_eq!(sig.len(), pk.size());
+        assert!(verify(pk, b"wrap this key", &sig).is_ok());
+        assert!(verify_digest(pk, &Sha256::digest(b"wrap this key"), &sig, Some(SALT_LEN)).is_ok());
+        assert!(verify(pk, b"wrap this kez", &sig).is_err());
+        assert!(verify(pk, b"wrap this key", &sig[1..]).is_err());
+
+        // fixed salt is deterministic; salts too long for the key are rejected
+        let digest = Sha256::digest(b"m");
+        let a = sign_digest_with_salt(&sk, &digest, &[7; 10]).unwrap();
+        assert_eq!(a, sign_digest_with_salt(&sk, &digest, &[7; 10]).unwrap());
+        assert!(verify_digest(pk, &digest, &a, Some(10)).is_ok());
+        assert!(sign_digest_with_salt(&sk, &digest, &[0; 95]).is_err());
+    }
+}
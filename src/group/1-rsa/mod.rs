@@ -15,3 +15,27 @@ pub mod lagrange;
 
 #[path = "6-rsa.rs"]
 pub mod rsa;
+
+#[path = "7-rsa_bigint.rs"]
+pub mod rsa_bigint;
+
+#[path = "8-oaep.rs"]
+pub mod oaep;
+
+#[path = "9-pss.rs"]
+pub mod pss;
+
+#[path = "10-key_encoding.rs"]
+pub mod key_encoding;
+
+#[path = "11-threshold_rsa.rs"]
+pub mod threshold_rsa;
+
+#[path = "12-blind.rs"]
+pub mod blind;
+
+#[path = "13-garner.rs"]
+pub mod garner;
+
+#[path = "14-multiprime.rs"]
+pub mod multiprime;
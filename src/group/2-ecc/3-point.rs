@@ -2,7 +2,7 @@
 use super::curve::Curve;
 use super::field::{mod_inverse, mod_pow};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point {
     pub x: i64,
     pub y: i64,
@@ -0,0 +1,296 @@
+//! src/group/2-ecc/6-dlog.rs
+//! Discrete logarithms in the toy groups: given g and h = g^x, find x.
+//!
+//! - Baby-step giant-step: tabulate g^j for j < m = ⌈√n⌉, then walk h·g^(-im)
+//!   until it hits the table. √n time and √n memory.
+//! - Pollard rho: a pseudo-random walk on elements g^a·h^b; a collision
+//!   g^a·h^b = g^A·h^B gives (b - B)·x ≡ A - a (mod n). √n time, O(1) memory.
+//! - Pohlig–Hellman: with n = ∏ q^e, solve x mod q^e one base-q digit at a
+//!   time in the subgroup of order q, then CRT. Cost is driven by the largest
+//!   prime factor of n, not by n, which is why DSA works in a subgroup of
+//!   prime order q and ECDSA wants G of prime order.
+//!
+//! The solvers are written once over `CyclicGroup`, implemented for Z_p^*
+//! (orders from super::super::rsa::lagrange) and for points on an
+//! `ecc::curve::Curve` (orders from super::counting). Toy sizes only: elements and exponents are i64.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use super::super::dsa::key::keygen as dsa_keygen;
+use super::super::dsa::params::DSAParams;
+use super::super::ecdsa::key::keygen as ecdsa_keygen;
+use super::super::ecdsa::params::ECDSAParams;
+use super::super::rsa::lagrange::order_of_element;
+use super::counting::point_order;
+use super::curve::Curve;
+use super::point::{point_add, Point};
+use super::scalar::scalar_mul;
+use crate::group::utils::number_theory::{crt, factor, gcd, mod_inverse};
+
+/// A group in which the solvers can work; written multiplicatively
+pub trait CyclicGroup {
+    type Elem: Copy + Eq + Hash + Debug;
+
+    fn identity(&self) -> Self::Elem;
+
+    fn op(&self, a: Self::Elem, b: Self::Elem) -> Self::Elem;
+
+    fn inverse(&self, a: Self::Elem) -> Self::Elem;
+
+    /// Order of the cyclic subgroup generated by g
+    fn order_of(&self, g: Self::Elem) -> i64;
+
+    /// g^k by square-and-multiply; negative k inverts first
+    fn pow(&self, g: Self::Elem, k: i64) -> Self::Elem {
+        let (mut base, mut k) = if k < 0 { (self.inverse(g), k.unsigned_abs()) } else { (g, k as u64) };
+        let mut result = self.identity();
+        while k > 0 {
+            if k & 1 == 1 {
+                result = self.op(result, base);
+            }
+            base = self.op(base, base);
+            k >>= 1;
+        }
+        result
+    }
+}
+
+/// Z_p^* for a prime p < 2^31
+#[derive(Debug, Clone, Copy)]
+pub struct MultiplicativeGroup {
+    pub p: i64,
+}
+
+impl CyclicGroup for MultiplicativeGroup {
+    type Elem = i64;
+
+    fn identity(&self) -> i64 {
+        1
+    }
+
+    fn op(&self, a: i64, b: i64) -> i64 {
+        (a * b).rem_euclid(self.p)
+    }
+
+    fn inverse(&self, a: i64) -> i64 {
+        mod_inverse(&a, &self.p).expect("elements of Z_p^* are units")
+    }
+
+    fn order_of(&self, g: i64) -> i64 {
+        order_of_element(g, self.p)
+    }
+}
+
+/// The points of a toy curve under point addition
+#[derive(Debug, Clone, Copy)]
+pub struct CurveGroup {
+    pub curve: Curve,
+}
+
+impl CyclicGroup for CurveGroup {
+    type Elem = Point;
+
+    fn identity(&self) -> Point {
+        Point { x: 0, y: 0, infinity: true }
+    }
+
+    fn op(&self, a: Point, b: Point) -> Point {
+        point_add(a, b, &self.curve)
+    }
+
+    fn inverse(&self, a: Point) -> Point {
+        if a.infinity { a } else { Point { x: a.x, y: (-a.y).rem_euclid(self.curve.p), infinity: false } }
+    }
+
+    fn order_of(&self, g: Point) -> i64 {
+        point_order(g, &self.curve)
+    }
+
+    fn pow(&self, g: Point, k: i64) -> Point {
+        if k < 0 { scalar_mul(-k, self.inverse(g), &self.curve) } else { scalar_mul(k, g, &self.curve) }
+    }
+}
+
+/// Baby-step giant-step: x in [0, n) with g^x = h, n the order of g or any
+/// upper bound on x
+pub fn bsgs<G: CyclicGroup>(group: &G, g: G::Elem, h: G::Elem, n: i64) -> Option<i64> {
+    let m = (n as f64).sqrt().ceil() as i64;
+    let mut table = HashMap::with_capacity(m as usize);
+    let mut e = group.identity();
+    for j in 0..m {
+        table.entry(e).or_insert(j);
+        e = group.op(e, g);
+    }
+    let giant = group.pow(g, -m);
+    let mut gamma = h;
+    for i in 0..m {
+        if let Some(&j) = table.get(&gamma) {
+            return Some(i * m + j);
+        }
+        gamma = group.op(gamma, giant);
+    }
+    None
+}
+
+/// Pollard rho with Floyd cycle finding; n is the order of g
+pub fn pollard_rho<G: CyclicGroup>(group: &G, g: G::Elem, h: G::Elem, n: i64) -> Option<i64> {
+    // partition by a hash of the element: multiply by h, square, multiply by g
+    let step = |(x, a, b): (G::Elem, i64, i64)| {
+        let mut hasher = DefaultHasher::new();
+        x.hash(&mut hasher);
+        match hasher.finish() % 3 {
+            0 => (group.op(x, h), a, (b + 1) % n),
+            1 => (group.op(x, x), 2 * a % n, 2 * b % n),
+            _ => (group.op(x, g), (a + 1) % n, b),
+        }
+    };
+    // a few deterministic starting points in case a walk is degenerate
+    for start in 0..8 {
+        let (a0, b0) = (start % n, (start + 1) % n);
+        let mut tortoise = (group.op(group.pow(g, a0), group.pow(h, b0)), a0, b0);
+        let mut hare = tortoise;
+        for _ in 0..4 * n + 4 {
+            tortoise = step(tortoise);
+            hare = step(step(hare));
+            if tortoise.0 == hare.0 {
+                break;
+            }
+        }
+        if tortoise.0 != hare.0 {
+            continue;
+        }
+        // g^a·h^b = g^A·h^B  =>  (b - B)·x ≡ A - a (mod n)
+        let r = (tortoise.2 - hare.2).rem_euclid(n);
+        let s = (hare.1 - tortoise.1).rem_euclid(n);
+        let d = gcd(&r, &n);
+        if r == 0 || s % d != 0 {
+            continue;
+        }
+        let nd = n / d;
+        let x0 = (s / d) * mod_inverse(&(r / d), &nd).expect("coprime after dividing by d") % nd;
+        // d candidates; for prime n, d = 1
+        if let Some(x) = (0..d).map(|k| x0 + k * nd).find(|&x| group.pow(g, x) == h) {
+            return Some(x);
+        }
+    }
+    None
+}
+
+/// Pohlig–Hellman: x in [0, n) with g^x = h, n the order of g; each prime
+/// power is solved digit by digit with BSGS in the subgroup of order q
+pub fn pohlig_hellman<G: CyclicGroup>(group: &G, g: G::Elem, h: G::Elem, n: i64) -> Option<i64> {
+    let mut residues = Vec::new();
+    let mut moduli = Vec::new();
+    for (q, e) in factor(&n)? {
+        // γ = g^(n/q) has order q
+        let gamma = group.pow(g, n / q);
+        let mut x = 0;
+        let mut qk = 1;
+        for _ in 0..e {
+            // strip the digits found so far, push what is left into ⟨γ⟩
+            let rest = group.op(h, group.pow(g, -x));
+            let hk = group.pow(rest, n / (qk * q));
+            let digit = bsgs(group, gamma, hk, q)?;
+            x += digit * qk;
+            qk *= q;
+        }
+        residues.push(x);
+        moduli.push(qk);
+    }
+    let (x, _) = crt(&residues, &moduli)?;
+    (group.pow(g, x) == h).then_some(x)
+}
+
+/// x with g^x = h, using the order of g and Pohlig–Hellman
+pub fn discrete_log<G: CyclicGroup>(group: &G, g: G::Elem, h: G::Elem) -> Option<i64> {
+    let n = group.order_of(g);
+    pohlig_hellman(group, g, h, n)
+}
+
+/// Recover the private keys behind the example DSA and ECDSA parameters
+pub fn demo() {
+    let params = DSAParams::example();
+    let keypair = dsa_keygen(&params, 7).expect("example parameters are valid");
+    let zp = MultiplicativeGroup { p: params.p };
+    let n = zp.order_of(params.g);
+    println!("DSA: g = {} has order {} mod {}, y = {}", params.g, n, params.p, keypair.public);
+    println!("  BSGS: x = {:?}", bsgs(&zp, params.g, keypair.public, n));
+    println!("  Pollard rho: x = {:?}", pollard_rho(&zp, params.g, keypair.public, n));
+    println!("  Pohlig–Hellman: x = {:?}", pohlig_hellman(&zp, params.g, keypair.public, n));
+
+    let params = ECDSAParams::example();
+    let keypair = ecdsa_keygen(&params, 3).expect("example parameters are valid");
+    let curve = CurveGroup { curve: params.curve };
+    let n = curve.order_of(params.G);
+    println!("ECDSA: G = ({}, {}) has order {}, Q = {:?}", params.G.x, params.G.y, n, keypair.public);
+    println!("  BSGS: d = {:?}", bsgs(&curve, params.G, keypair.public, n));
+    println!("  Pollard rho: d = {:?}", pollard_rho(&curve, params.G, keypair.public, n));
+    println!("  Pohlig–Hellman: d = {:?}", pohlig_hellman(&curve, params.G, keypair.public, n));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_solvers<G: CyclicGroup>(group: &G, g: G::Elem, x: i64) {
+        let n = group.order_of(g);
+        let h = group.pow(g, x);
+        let want = Some(x.rem_euclid(n));
+        assert_eq!(bsgs(group, g, h, n), want, "bsgs x = {x}");
+        assert_eq!(pollard_rho(group, g, h, n), want, "rho x = {x}");
+        assert_eq!(pohlig_hellman(group, g, h, n), want, "pohlig-hellman x = {x}");
+        assert_eq!(discrete_log(group, g, h), want);
+    }
+
+    #[test]
+    fn recovers_example_keys() {
+        let params = DSAParams::example();
+        let zp = MultiplicativeGroup { p: params.p };
+        for x in 1..params.q {
+            let y = dsa_keygen(&params, x).unwrap().public;
+            assert_eq!(discrete_log(&zp, params.g, y), Some(x));
+            all_solvers(&zp, params.g, x);
+        }
+        let params = ECDSAParams::example();
+        let curve = CurveGroup { curve: params.curve };
+        let n = curve.order_of(params.G);
+        for d in 1..n {
+            let q = ecdsa_keygen(&params, d).unwrap().public;
+            assert_eq!(discrete_log(&curve, params.G, q), Some(d));
+            all_solvers(&curve, params.G, d);
+        }
+    }
+
+    #[test]
+    fn composite_orders() {
+        // 11 generates Z_1009^*, order 1008 = 2^4·3^2·7
+        let zp = MultiplicativeGroup { p: 1009 };
+        assert_eq!(zp.order_of(11), 1008);
+        for x in [0, 1, 2, 500, 1007] {
+            all_solvers(&zp, 11, x);
+        }
+        // every point of y² = x³ + 2x + 3 over F_97, whatever its order
+        let group = CurveGroup { curve: Curve { a: 2, b: 3, p: 97 } };
+        let r = (0..97).flat_map(|x| (0..97).map(move |y| (x, y))).find(|&(x, y)| {
+            let pt = Point { x, y, infinity: false };
+            super::super::curve::is_on_curve(x, y, &group.curve) && group.order_of(pt) > 20
+        });
+        let (x, y) = r.unwrap();
+        let pt = Point { x, y, infinity: false };
+        for k in [1, 7, group.order_of(pt) - 1] {
+            all_solvers(&group, pt, k);
+        }
+    }
+
+    #[test]
+    fn larger_dsa_subgroup() {
+        // 28-bit p = kq + 1 with q = 65521, g = 2^((p-1)/q)
+        let params = DSAParams { p: 135_104_303, q: 65_521, g: 35_496_797 };
+        assert_eq!(params.validate(), Ok(()));
+        let zp = MultiplicativeGroup { p: params.p };
+        assert_eq!(zp.order_of(params.g), params.q);
+        all_solvers(&zp, params.g, params.q / 3);
+    }
+}
@@ -0,0 +1,176 @@
+//! src/group/2-ecc/7-counting.rs
+//! Point counting and subgroup structure for toy curves y² = x³ + ax + b mod p.
+//!
+//! - #E(F_p) = p + 1 + Σ_x (x³ + ax + b / p): each x gives 1 + Legendre
+//!   points, plus the point at infinity. Hasse: |#E - p - 1| <= 2√p.
+//! - The order of a point divides #E, so start from #E and divide out each
+//!   prime factor q while (#E/q)·P is still the identity.
+//! - A generator of prime order q | #E is h·R for any R with h·R != O, where
+//!   h = #E/q is the cofactor. Signatures need this: nonces and keys live mod
+//!   q, and a composite order lets Pohlig–Hellman (super::dlog) split the
+//!   discrete log.
+//!
+//! Enumeration is O(p); fine for the toy parameters, useless beyond that.
+
+use num_bigint::BigInt;
+use super::curve::{is_on_curve, Curve};
+use super::point::Point;
+use super::scalar::scalar_mul;
+use crate::group::utils::number_theory::factor;
+use crate::group::utils::primes::is_prime;
+use crate::group::utils::quadratic::{legendre, sqrt_mod_prime};
+
+/// A point of prime order and the subgroup it generates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimeSubgroup {
+    pub generator: Point,
+    pub order: i64,    // prime q
+    pub cofactor: i64, // #E / q
+}
+
+fn rhs(x: i64, curve: &Curve) -> i64 {
+    (x * x % curve.p * x + curve.a * x + curve.b).rem_euclid(curve.p)
+}
+
+/// 4a³ + 27b² ≡ 0 (mod p): the cubic has a repeated root and the points do
+/// not form a group
+pub fn is_singular(curve: &Curve) -> bool {
+    let p = curve.p;
+    let a = curve.a.rem_euclid(p);
+    let b = curve.b.rem_euclid(p);
+    (4 * (a * a % p) * a + 27 * (b * b % p)).rem_euclid(p) == 0
+}
+
+/// Every point of the curve: the identity, then affine points by x, then y
+pub fn points(curve: &Curve) -> Vec<Point> {
+    let p = BigInt::from(curve.p);
+    let mut out = vec![Point { x: 0, y: 0, infinity: true }];
+    for x in 0..curve.p {
+        let v = rhs(x, curve);
+        if let Some(y) = sqrt_mod_prime(&BigInt::from(v), &p) {
+            let y: i64 = y.try_into().expect("root is below p");
+            let other = (curve.p - y) % curve.p;
+            out.push(Point { x, y: y.min(other), infinity: false });
+            if y != other {
+                out.push(Point { x, y: y.max(other), infinity: false });
+            }
+        }
+    }
+    out
+}
+
+/// #E(F_p), identity included, from Legendre symbols
+pub fn group_order(curve: &Curve) -> i64 {
+    let p = BigInt::from(curve.p);
+    let sum: i64 = (0..curve.p).map(|x| legendre(&BigInt::from(rhs(x, curve)), &p) as i64).sum();
+    curve.p + 1 + sum
+}
+
+/// #E(F_p) as (prime, exponent)
+pub fn order_factorization(curve: &Curve) -> Vec<(i64, u32)> {
+    factor(&group_order(curve)).expect("toy orders always factor")
+}
+
+/// Order of P in E(F_p)
+pub fn point_order(point: Point, curve: &Curve) -> i64 {
+    let mut order = group_order(curve);
+    for (q, _) in order_factorization(curve) {
+        while order % q == 0 && scalar_mul(order / q, point, curve).infinity {
+            order /= q;
+        }
+    }
+    order
+}
+
+/// A generator of the subgroup of largest prime order, deterministic: the
+/// first point (in `points` order) whose cofactor multiple is not O
+pub fn prime_order_generator(curve: &Curve) -> Option<PrimeSubgroup> {
+    if is_singular(curve) {
+        return None;
+    }
+    let n = group_order(curve);
+    let (q, _) = *order_factorization(curve).last()?;
+    let cofactor = n / q;
+    points(curve)
+        .into_iter()
+        .map(|pt| scalar_mul(cofactor, pt, curve))
+        .find(|g| !g.infinity)
+        .map(|generator| PrimeSubgroup { generator, order: q, cofactor })
+}
+
+/// Order of G when the curve is nonsingular, G is an affine point on it and
+/// that order is prime; what signature parameters need
+pub fn prime_point_order(g: Point, curve: &Curve) -> Option<i64> {
+    if is_singular(curve) || g.infinity || !is_on_curve(g.x, g.y, curve) {
+        return None;
+    }
+    let n = point_order(g, curve);
+    is_prime(&BigInt::from(n)).then_some(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::point::point_add;
+
+    const TOY: Curve = Curve { a: 2, b: 3, p: 97 };
+
+    fn brute_points(curve: &Curve) -> Vec<Point> {
+        let mut v = vec![Point { x: 0, y: 0, infinity: true }];
+        for x in 0..curve.p {
+            for y in 0..curve.p {
+                if is_on_curve(x, y, curve) {
+                    v.push(Point { x, y, infinity: false });
+                }
+            }
+        }
+        v
+    }
+
+    fn brute_order(pt: Point, curve: &Curve) -> i64 {
+        let mut acc = pt;
+        let mut k = 1;
+        while !acc.infinity {
+            acc = point_add(acc, pt, curve);
+            k += 1;
+        }
+        k
+    }
+
+    #[test]
+    fn counts_match_brute_force() {
+        for curve in [TOY, Curve { a: 1, b: 2, p: 97 }, Curve { a: 0, b: 7, p: 101 }, Curve { a: -1, b: 1, p: 211 }] {
+            let pts = points(&curve);
+            assert_eq!(pts, brute_points(&curve));
+            let n = group_order(&curve);
+            assert_eq!(n, pts.len() as i64);
+            assert!((n - curve.p - 1).pow(2) <= 4 * curve.p, "Hasse bound");
+            for &pt in &pts {
+                assert_eq!(point_order(pt, &curve), brute_order(pt, &curve));
+            }
+        }
+        // y² = x³ + 2x + 3 over F_97: 100 points, and G = (3, 6) has order 5
+        assert_eq!(order_factorization(&TOY), vec![(2, 2), (5, 2)]);
+        assert_eq!(point_order(Point { x: 3, y: 6, infinity: false }, &TOY), 5);
+    }
+
+    #[test]
+    fn finds_prime_order_generators() {
+        for curve in [TOY, Curve { a: 1, b: 2, p: 97 }, Curve { a: -1, b: 1, p: 211 }] {
+            let sub = prime_order_generator(&curve).unwrap();
+            let n = group_order(&curve);
+            assert_eq!(sub.order * sub.cofactor, n);
+            assert!(is_prime(&BigInt::from(sub.order)));
+            assert_eq!(point_order(sub.generator, &curve), sub.order);
+            assert_eq!(prime_point_order(sub.generator, &curve), Some(sub.order));
+        }
+        // singular: y² = x³ (a = b = 0)
+        let cusp = Curve { a: 0, b: 0, p: 97 };
+        assert!(is_singular(&cusp));
+        assert_eq!(prime_order_generator(&cusp), None);
+        // off the curve, or of composite order
+        assert_eq!(prime_point_order(Point { x: 3, y: 5, infinity: false }, &TOY), None);
+        let composite = points(&TOY).into_iter().find(|&pt| point_order(pt, &TOY) == 10).unwrap();
+        assert_eq!(prime_point_order(composite, &TOY), None);
+    }
+}
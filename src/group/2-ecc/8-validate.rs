@@ -0,0 +1,144 @@
+//! src/group/2-ecc/8-validate.rs
+//! Elliptic-curve domain-parameter checks (SEC 1 §3.1.1.2.1), shared by the
+//! toy ECDSA / EdDSA parameters and secp256k1.
+//!
+//! - p prime and 4a³ + 27b² ≢ 0 (mod p), so the points form a group
+//! - G ≠ O on the curve, n prime and n·G = O, so G has order exactly n
+//! - cofactor h = #E / n: counted exactly on toy curves (super::counting);
+//!   large curves use SEC 1's h = ⌊(√p + 1)² / n⌋, which Hasse pins down
+//!   only when n > 4√p
+//! - n ≠ p: an anomalous curve falls to Smart's attack in linear time
+//! - p^k ≢ 1 (mod n) for small k: otherwise the MOV / Frey–Rück pairing maps
+//!   the discrete log into F_{p^k}^*. SEC 1 asks this for k < 100. A toy n
+//!   of a few bits always has k < n, so toy curves only reject k <= 2, the
+//!   supersingular and pairing-friendly cases.
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::One;
+use thiserror::Error;
+use super::counting::{group_order, is_singular};
+use super::curve::{is_on_curve, Curve};
+use super::point::Point;
+use super::scalar::scalar_mul;
+use crate::group::utils::primes::is_prime;
+
+/// SEC 1 bound on the embedding degree
+pub const MOV_BOUND: u32 = 100;
+/// Embedding-degree bound for toy curves, see the module docs
+pub const TOY_MOV_BOUND: u32 = 2;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CurveParamError {
+    #[error("field modulus p is not prime")]
+    FieldNotPrime,
+    #[error("singular curve: 4a^3 + 27b^2 = 0 mod p")]
+    Singular,
+    #[error("generator is the point at infinity")]
+    GeneratorAtInfinity,
+    #[error("generator is not on the curve")]
+    GeneratorNotOnCurve,
+    #[error("order n is not prime")]
+    OrderNotPrime,
+    #[error("n·G is not the point at infinity")]
+    WrongOrder,
+    #[error("n <= 4√p: the cofactor is not determined by n")]
+    SubgroupTooSmall,
+    #[error("cofactor does not match #E / n")]
+    CofactorMismatch,
+    #[error("anomalous curve: n = p")]
+    Anomalous,
+    #[error("embedding degree {0} is too small")]
+    SmallEmbeddingDegree(u32),
+}
+
+/// Least k <= bound with p^k ≡ 1 (mod n), if any
+pub fn embedding_degree(p: &BigInt, n: &BigInt, bound: u32) -> Option<u32> {
+    let base = p.mod_floor(n);
+    let mut acc = base.clone();
+    for k in 1..=bound {
+        if acc.is_one() {
+            return Some(k);
+        }
+        acc = (acc * &base).mod_floor(n);
+    }
+    None
+}
+
+/// The anomalous and MOV conditions for a subgroup of prime order n
+pub fn check_attacks(p: &BigInt, n: &BigInt, bound: u32) -> Result<(), CurveParamError> {
+    if p == n {
+        return Err(CurveParamError::Anomalous);
+    }
+    match embedding_degree(p, n, bound) {
+        Some(k) => Err(CurveParamError::SmallEmbeddingDegree(k)),
+        None => Ok(()),
+    }
+}
+
+/// Everything in the module docs for a toy curve with base point G of order n
+pub fn validate_toy_curve(curve: &Curve, g: Point, n: i64) -> Result<(), CurveParamError> {
+    let p = BigInt::from(curve.p);
+    if !is_prime(&p) {
+        return Err(CurveParamError::FieldNotPrime);
+    }
+    if is_singular(curve) {
+        return Err(CurveParamError::Singular);
+    }
+    if g.infinity {
+        return Err(CurveParamError::GeneratorAtInfinity);
+    }
+    if !is_on_curve(g.x, g.y, curve) {
+        return Err(CurveParamError::GeneratorNotOnCurve);
+    }
+    if !is_prime(&BigInt::from(n)) {
+        return Err(CurveParamError::OrderNotPrime);
+    }
+    if !scalar_mul(n, g, curve).infinity {
+        return Err(CurveParamError::WrongOrder);
+    }
+    if group_order(curve) % n != 0 {
+        return Err(CurveParamError::CofactorMismatch);
+    }
+    check_attacks(&p, &BigInt::from(n), TOY_MOV_BOUND)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOY: Curve = Curve { a: 2, b: 3, p: 97 };
+    const G: Point = Point { x: 3, y: 6, infinity: false };
+
+    #[test]
+    fn toy_checks() {
+        assert_eq!(validate_toy_curve(&TOY, G, 5), Ok(()));
+        assert_eq!(validate_toy_curve(&Curve { p: 99, ..TOY }, G, 5), Err(CurveParamError::FieldNotPrime));
+        assert_eq!(validate_toy_curve(&Curve { a: 0, b: 0, p: 97 }, G, 5), Err(CurveParamError::Singular));
+        let o = Point { x: 0, y: 0, infinity: true };
+        assert_eq!(validate_toy_curve(&TOY, o, 5), Err(CurveParamError::GeneratorAtInfinity));
+        assert_eq!(validate_toy_curve(&TOY, Point { y: 5, ..G }, 5), Err(CurveParamError::GeneratorNotOnCurve));
+        assert_eq!(validate_toy_curve(&TOY, G, 10), Err(CurveParamError::OrderNotPrime));
+        assert_eq!(validate_toy_curve(&TOY, G, 7), Err(CurveParamError::WrongOrder));
+    }
+
+    #[test]
+    fn attack_conditions() {
+        // y² = x³ + x over F_103, p ≡ 3 (mod 4): supersingular, #E = p + 1 =
+        // 104 = 8·13, so p ≡ -1 (mod 13) and the embedding degree is 2
+        let curve = Curve { a: 1, b: 0, p: 103 };
+        let sub = super::super::counting::prime_order_generator(&curve).unwrap();
+        assert_eq!(sub.order, 13);
+        assert_eq!(validate_toy_curve(&curve, sub.generator, 13), Err(CurveParamError::SmallEmbeddingDegree(2)));
+
+        // y² = x³ + x + 1 over F_97 has exactly 97 points
+        let curve = Curve { a: 1, b: 1, p: 97 };
+        let sub = super::super::counting::prime_order_generator(&curve).unwrap();
+        assert_eq!(sub.order, 97);
+        assert_eq!(validate_toy_curve(&curve, sub.generator, 97), Err(CurveParamError::Anomalous));
+
+        let big = |x: i64| BigInt::from(x);
+        assert_eq!(embedding_degree(&big(97), &big(5), MOV_BOUND), Some(4));
+        assert_eq!(embedding_degree(&big(97), &big(13), TOY_MOV_BOUND), None);
+    }
+}
@@ -12,3 +12,12 @@ pub mod scalar;
 
 #[path ="5-ecc.rs"]
 pub mod ecc; // ecc.rs
+
+#[path ="6-dlog.rs"]
+pub mod dlog;
+
+#[path ="7-counting.rs"]
+pub mod counting;
+
+#[path ="8-validate.rs"]
+pub mod validate;
@@ -1,4 +1,24 @@
 //! src/group/3-dsa/1-params.rs
+use num_bigint::BigInt;
+use num_traits::One;
+use thiserror::Error;
+use crate::group::utils::montgomery::modpow_bigint;
+use crate::group::utils::primes::is_prime;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum DSAParamError {
+    #[error("p is not prime")]
+    PNotPrime,
+    #[error("q is not prime")]
+    QNotPrime,
+    #[error("q does not divide p - 1")]
+    QDoesNotDivide,
+    #[error("g is not in [2, p)")]
+    GeneratorOutOfRange,
+    #[error("g^q is not 1 mod p")]
+    GeneratorWrongOrder,
+}
+
 /// Domain parameters for DSA
 #[derive(Debug, Clone, Copy)]
 pub struct DSAParams {
@@ -13,6 +33,29 @@ impl DSAParams {
         // p = 23, q = 11, g = 2 is simple toy example
         DSAParams { p: 23, q: 11, g: 2 }
     }
+
+    /// FIPS 186-4 A.1.1.1 / A.2.2 partial checks: p and q prime, q | p - 1,
+    /// and g in [2, p) of order q (g != 1 and q prime make the order exact)
+    pub fn validate(&self) -> Result<(), DSAParamError> {
+        let p = BigInt::from(self.p);
+        let q = BigInt::from(self.q);
+        if !is_prime(&p) {
+            return Err(DSAParamError::PNotPrime);
+        }
+        if !is_prime(&q) {
+            return Err(DSAParamError::QNotPrime);
+        }
+        if (self.p - 1) % self.q != 0 {
+            return Err(DSAParamError::QDoesNotDivide);
+        }
+        if self.g < 2 || self.g >= self.p {
+            return Err(DSAParamError::GeneratorOutOfRange);
+        }
+        if !modpow_bigint(&BigInt::from(self.g), &q, &p).is_one() {
+            return Err(DSAParamError::GeneratorWrongOrder);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -25,6 +68,19 @@ mod tests {
         assert_eq!(params.p, 23);
         assert_eq!(params.q, 11);
         assert_eq!(params.g, 2);
+        assert_eq!(params.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_rejects_bad_params() {
+        let ok = DSAParams::example();
+        assert_eq!(DSAParams { p: 25, ..ok }.validate(), Err(DSAParamError::PNotPrime));
+        assert_eq!(DSAParams { q: 9, ..ok }.validate(), Err(DSAParamError::QNotPrime));
+        assert_eq!(DSAParams { q: 7, ..ok }.validate(), Err(DSAParamError::QDoesNotDivide));
+        assert_eq!(DSAParams { g: 1, ..ok }.validate(), Err(DSAParamError::GeneratorOutOfRange));
+        assert_eq!(DSAParams { g: 23, ..ok }.validate(), Err(DSAParamError::GeneratorOutOfRange));
+        // 5 generates all of Z_23^*, order 22
+        assert_eq!(DSAParams { g: 5, ..ok }.validate(), Err(DSAParamError::GeneratorWrongOrder));
     }
 }
 
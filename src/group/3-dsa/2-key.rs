@@ -1,5 +1,5 @@
 //! src/group/3-dsa/2-key.rs
-use super::params::DSAParams;
+use super::params::{DSAParamError, DSAParams};
 use super::super::rsa::gcd::extended_gcd;
 use super::super::ecc::field::mod_pow;
 
@@ -10,11 +10,12 @@ pub struct DSAKeyPair {
     pub public: i64,
 }
 
-/// Generate DSA keypair
-pub fn keygen(params: &DSAParams, x: i64) -> DSAKeyPair {
+/// Generate DSA keypair; refuses parameters that fail `DSAParams::validate`
+pub fn keygen(params: &DSAParams, x: i64) -> Result<DSAKeyPair, DSAParamError> {
+    params.validate()?;
     // x = private key ∈ [1, q-1]
     let y = mod_pow(params.g, x, params.p); // public key
-    DSAKeyPair { private: x, public: y }
+    Ok(DSAKeyPair { private: x, public: y })
 }
 
 #[cfg(test)]
@@ -25,8 +26,14 @@ mod tests {
     #[test]
     fn test_keygen() {
         let params = DSAParams::example();
-        let keypair = keygen(&params, 3);
+        let keypair = keygen(&params, 3).unwrap();
         assert_eq!(keypair.private, 3);
         assert_eq!(keypair.public, 8); // 2^3 mod 23 = 8
     }
+
+    #[test]
+    fn test_keygen_rejects_bad_params() {
+        let params = DSAParams { g: 5, ..DSAParams::example() };
+        assert_eq!(keygen(&params, 3).unwrap_err(), DSAParamError::GeneratorWrongOrder);
+    }
 }
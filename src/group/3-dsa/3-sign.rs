@@ -1,22 +1,32 @@
 //! src/group/3-dsa/3-sign.rs
-use super::params::DSAParams;
+use thiserror::Error;
+use super::params::{DSAParamError, DSAParams};
 use super::key::DSAKeyPair;
 use super::super::rsa::gcd::extended_gcd;
 use super::super::ecc::field::{mod_pow, mod_inverse};
 
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum DSASignError {
+    #[error(transparent)]
+    Params(#[from] DSAParamError),
+    #[error("nonce k is outside [1, q) or gives r = 0 or s = 0, retry with another")]
+    BadNonce,
+}
+
 /// Sign message m using private key x and random k
-pub fn sign(params: &DSAParams, key: &DSAKeyPair, m: i64, k: i64) -> Option<(i64, i64)> {
+pub fn sign(params: &DSAParams, key: &DSAKeyPair, m: i64, k: i64) -> Result<(i64, i64), DSASignError> {
+    params.validate()?;
     if k <= 0 || k >= params.q {
-        return None;
+        return Err(DSASignError::BadNonce);
     }
     let r = mod_pow(params.g, k, params.p) % params.q;
-    if r == 0 { return None; }
+    if r == 0 { return Err(DSASignError::BadNonce); }
 
-    let k_inv = mod_inverse(k, params.q)?;
+    let k_inv = mod_inverse(k, params.q).ok_or(DSASignError::BadNonce)?;
     let s = (k_inv * (m + key.private * r)).rem_euclid(params.q);
-    if s == 0 { return None; }
+    if s == 0 { return Err(DSASignError::BadNonce); }
 
-    Some((r, s))
+    Ok((r, s))
 }
 
 #[cfg(test)]
@@ -28,8 +38,11 @@ mod tests {
     #[test]
     fn test_sign() {
         let params = DSAParams::example();
-        let keypair = keygen(&params, 3);
+        let keypair = keygen(&params, 3).unwrap();
         let sig = sign(&params, &keypair, 7, 2).unwrap();
         assert!(sig.0 > 0 && sig.1 > 0);
+        assert_eq!(sign(&params, &keypair, 7, 11), Err(DSASignError::BadNonce));
+        let bad = DSAParams { q: 7, ..params };
+        assert_eq!(sign(&bad, &keypair, 7, 2), Err(DSASignError::Params(DSAParamError::QDoesNotDivide)));
     }
 }
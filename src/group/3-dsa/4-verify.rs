@@ -27,7 +27,7 @@ mod tests {
     #[test]
     fn test_verify() {
         let params = DSAParams::example();
-        let keypair = keygen(&params, 3);
+        let keypair = keygen(&params, 3).unwrap();
         let sig = sign(&params, &keypair, 7, 2).unwrap();
         assert!(verify(&params, keypair.public, 7, sig.0, sig.1));
     }
@@ -7,7 +7,7 @@ use super::verify::verify;
 pub fn demo() {
     let params = DSAParams::example();
     let private = 3; // private key x
-    let keypair = keygen(&params, private);
+    let keypair = keygen(&params, private).unwrap();
     println!("DSA Keypair: {:?}", keypair);
 
     let m = 7; // message
@@ -0,0 +1,509 @@
+//! src/group/3-dsa/6-fips186.rs
+//! DSA at real sizes (FIPS 186-4) on `BigUint`, SHA-256 throughout.
+//! - p and q: A.1.1.2, primes derived by hashing a random domain parameter
+//!   seed. The seed and the counter of the p search are kept with the
+//!   parameters, and A.1.1.3 replays the search to check them. (A.1.1.2 is
+//!   the probable-prime method; the provable Shawe–Taylor one is A.1.2.)
+//! - g: A.2.3 canonical generation, g = SHA-256(seed ‖ "ggen" ‖ index ‖
+//!   count)^((p-1)/q) mod p, so anyone holding the seed can rule out a
+//!   planted generator; A.2.4 recomputes it.
+//! - (L, N) = (2048, 256) or (3072, 256), seedlen = N.
+//! - Signatures: k from RFC 6979 §3.2 (HMAC-SHA-256 on x and H(m)), so a
+//!   broken RNG cannot leak x through related nonces.
+//!
+//! Primality is Baillie–PSW (group::utils::primes), no weaker than the
+//! Miller–Rabin rounds of FIPS 186-4 Table C.1. The toy i64 version in
+//! super::{params, key, sign, verify} stays as the walkthrough.
+
+use hmac::{Hmac, Mac};
+use num_bigint::{BigInt, BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use crate::group::utils::montgomery::modpow;
+use crate::group::utils::number_theory::mod_inverse;
+use crate::group::utils::primes::is_prime;
+
+/// Supported (L, N) pairs
+pub const SIZES: [(usize, usize); 2] = [(2048, 256), (3072, 256)];
+
+/// SHA-256 output length in bits
+const OUTLEN: usize = 256;
+
+/// "ggen", the A.2.3 domain separator
+const GGEN: [u8; 4] = [0x67, 0x67, 0x65, 0x6e];
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Fips186Error {
+    #[error("unsupported (L, N) = ({0}, {1})")]
+    UnsupportedSize(usize, usize),
+    #[error("domain parameter seed has {got} bits, need at least {min}")]
+    SeedTooShort { got: usize, min: usize },
+    #[error("invalid domain parameters: {0}")]
+    InvalidParams(&'static str),
+    #[error("private key must be in [1, q)")]
+    InvalidPrivateKey,
+    #[error("public key must be in [2, p - 2] with y^q = 1 mod p")]
+    InvalidPublicKey,
+    #[error("invalid signature")]
+    InvalidSignature,
+}
+
+/// What A.1.1.3 and A.2.4 need to re-derive p, q and g
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainSeed {
+    pub seed: Vec<u8>, // domain_parameter_seed
+    pub counter: u32,  // iterations of the p search
+    pub index: u8,     // generator index
+}
+
+/// Domain parameters; every constructor validates them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DsaDomain {
+    p: BigUint,
+    q: BigUint,
+    g: BigUint,
+    seed: Option<DomainSeed>,
+}
+
+fn check_size(l: usize, n: usize) -> Result<(), Fips186Error> {
+    if SIZES.contains(&(l, n)) { Ok(()) } else { Err(Fips186Error::UnsupportedSize(l, n)) }
+}
+
+fn prime(x: &BigUint) -> bool {
+    is_prime(&BigInt::from(x.clone()))
+}
+
+fn hash_int(data: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(&Sha256::digest(data))
+}
+
+/// A.1.1.2 steps 6–7: q = 2^(N-1) + U + 1 - (U mod 2), U = Hash(seed) mod 2^(N-1)
+fn q_from_seed(seed: &[u8], n: usize) -> BigUint {
+    let top = BigUint::one() << (n - 1);
+    let u = hash_int(seed) % &top;
+    let odd = if u.bit(0) { BigUint::zero() } else { BigUint::one() };
+    top + u + odd
+}
+
+/// A.1.1.2 step 11: candidates p for counter = 0, 1, ..., max_counter; the
+/// first prime and its counter
+fn search_p(seed: &[u8], q: &BigUint, l: usize, max_counter: u32) -> Option<(BigUint, u32)> {
+    let seedlen = seed.len() * 8;
+    let modulus = BigUint::one() << seedlen;
+    let s = BigUint::from_bytes_be(seed);
+    let n = l.div_ceil(OUTLEN) - 1;
+    let b = l - 1 - n * OUTLEN;
+    let low = BigUint::one() << (l - 1);
+    let two_q = q << 1usize;
+    let mut offset = 1usize;
+    for counter in 0..=max_counter {
+        // W = V_0 + V_1·2^outlen + ... + (V_n mod 2^b)·2^(n·outlen)
+        let mut w = BigUint::zero();
+        for j in 0..=n {
+            let bytes = ((&s + offset + j) % &modulus).to_bytes_be();
+            let mut block = vec![0u8; seed.len() - bytes.len()];
+            block.extend_from_slice(&bytes);
+            let mut v = hash_int(&block);
+            if j == n {
+                v %= BigUint::one() << b;
+            }
+            w += v << (j * OUTLEN);
+        }
+        let x = w + &low;
+        // p = X - (c - 1) ≡ 1 (mod 2q)
+        let c = &x % &two_q;
+        let p = x + 1u32 - c;
+        if p >= low && prime(&p) {
+            return Some((p, counter));
+        }
+        offset += n + 1;
+    }
+    None
+}
+
+/// A.2.3: g = Hash(seed ‖ "ggen" ‖ index ‖ count)^((p-1)/q) mod p for the
+/// first count giving g >= 2
+fn canonical_generator(p: &BigUint, q: &BigUint, seed: &[u8], index: u8) -> Option<BigUint> {
+    let e = (p - 1u32) / q;
+    (1..=u16::MAX).find_map(|count| {
+        let mut u = seed.to_vec();
+        u.extend_from_slice(&GGEN);
+        u.push(index);
+        u.extend_from_slice(&count.to_be_bytes());
+        let g = modpow(&hash_int(&u), &e, p);
+        (g >= BigUint::from(2u32)).then_some(g)
+    })
+}
+
+impl DsaDomain {
+    /// Fresh parameters with generator index 1
+    pub fn generate(l: usize, n: usize) -> Result<Self, Fips186Error> {
+        Self::generate_with_rng(l, n, 1, &mut OsRng)
+    }
+
+    /// A.1.1.2 then A.2.3 with a seed of N bits from `rng`
+    pub fn generate_with_rng(l: usize, n: usize, index: u8, rng: &mut dyn RngCore) -> Result<Self, Fips186Error> {
+        check_size(l, n)?;
+        let mut seed = vec![0u8; n / 8];
+        loop {
+            rng.fill_bytes(&mut seed);
+            let q = q_from_seed(&seed, n);
+            if !prime(&q) {
+                continue;
+            }
+            let Some((p, counter)) = search_p(&seed, &q, l, 4 * l as u32 - 1) else {
+                continue;
+            };
+            let g = canonical_generator(&p, &q, &seed, index).expect("some count gives g >= 2");
+            return Ok(DsaDomain { p, q, g, seed: Some(DomainSeed { seed, counter, index }) });
+        }
+    }
+
+    /// A.1.1.3 and A.2.4: re-derive q, p and g from the seed and compare
+    pub fn from_seed(p: BigUint, q: BigUint, g: BigUint, seed: DomainSeed) -> Result<Self, Fips186Error> {
+        let (l, n) = (p.bits() as usize, q.bits() as usize);
+        check_size(l, n)?;
+        if seed.counter > 4 * l as u32 - 1 {
+            return Err(Fips186Error::InvalidParams("counter exceeds 4L - 1"));
+        }
+        if seed.seed.len() * 8 < n {
+            return Err(Fips186Error::SeedTooShort { got: seed.seed.len() * 8, min: n });
+        }
+        if q_from_seed(&seed.seed, n) != q || !prime(&q) {
+            return Err(Fips186Error::InvalidParams("q does not match the seed"));
+        }
+        // the replay must find its first prime exactly at `counter`
+        if search_p(&seed.seed, &q, l, seed.counter) != Some((p.clone(), seed.counter)) {
+            return Err(Fips186Error::InvalidParams("p does not match the seed and counter"));
+        }
+        let domain = Self::from_pqg(p, q, g)?;
+        if canonical_generator(&domain.p, &domain.q, &seed.seed, seed.index).as_ref() != Some(&domain.g) {
+            return Err(Fips186Error::InvalidParams("g does not match the seed and index"));
+        }
+        Ok(DsaDomain { seed: Some(seed), ..domain })
+    }
+
+    /// Parameters without a seed: only the checks that need none (p and q
+    /// prime of the right sizes, q | p - 1, and A.2.2 for g)
+    pub fn from_pqg(p: BigUint, q: BigUint, g: BigUint) -> Result<Self, Fips186Error> {
+        check_size(p.bits() as usize, q.bits() as usize)?;
+        if !prime(&q) || !prime(&p) {
+            return Err(Fips186Error::InvalidParams("p and q must be prime"));
+        }
+        if !((&p - 1u32) % &q).is_zero() {
+            return Err(Fips186Error::InvalidParams("q does not divide p - 1"));
+        }
+        if g < BigUint::from(2u32) || g >= p || !modpow(&g, &q, &p).is_one() {
+            return Err(Fips186Error::InvalidParams("g must be in [2, p) with g^q = 1 mod p"));
+        }
+        Ok(DsaDomain { p, q, g, seed: None })
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    pub fn q(&self) -> &BigUint {
+        &self.q
+    }
+
+    pub fn g(&self) -> &BigUint {
+        &self.g
+    }
+
+    /// Seed, counter and index, when the parameters were generated or
+    /// verified from them
+    pub fn seed(&self) -> Option<&DomainSeed> {
+        self.seed.as_ref()
+    }
+
+    /// (L, N)
+    pub fn size(&self) -> (usize, usize) {
+        (self.p.bits() as usize, self.q.bits() as usize)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DsaPublicKey {
+    pub domain: DsaDomain,
+    pub y: BigUint,
+}
+
+impl DsaPublicKey {
+    /// Checks 2 <= y <= p - 2 and y^q = 1 mod p
+    pub fn new(domain: DsaDomain, y: BigUint) -> Result<Self, Fips186Error> {
+        let (p, q) = (&domain.p, &domain.q);
+        if y < BigUint::from(2u32) || y > p - 2u32 || !modpow(&y, q, p).is_one() {
+            return Err(Fips186Error::InvalidPublicKey);
+        }
+        Ok(DsaPublicKey { domain, y })
+    }
+}
+
+pub struct DsaPrivateKey {
+    pub(crate) public: DsaPublicKey,
+    pub(crate) x: BigUint,
+}
+
+impl Drop for DsaPrivateKey {
+    fn drop(&mut self) {
+        // best-effort, as for the RSA key
+        self.x.set_zero();
+    }
+}
+
+impl std::fmt::Debug for DsaPrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DsaPrivateKey").field("public", &self.public).finish_non_exhaustive()
+    }
+}
+
+impl DsaPrivateKey {
+    /// x uniform in [1, q)
+    pub fn generate(domain: &DsaDomain) -> Self {
+        Self::generate_with_rng(domain, &mut OsRng)
+    }
+
+    pub fn generate_with_rng(domain: &DsaDomain, rng: &mut dyn RngCore) -> Self {
+        let x = rng.gen_biguint_range(&BigUint::one(), &domain.q);
+        Self::from_x(domain.clone(), x).expect("x is in [1, q)")
+    }
+
+    pub fn from_x(domain: DsaDomain, x: BigUint) -> Result<Self, Fips186Error> {
+        if x.is_zero() || x >= domain.q {
+            return Err(Fips186Error::InvalidPrivateKey);
+        }
+        let y = modpow(&domain.g, &x, &domain.p);
+        Ok(DsaPrivateKey { public: DsaPublicKey { domain, y }, x })
+    }
+
+    pub fn public_key(&self) -> &DsaPublicKey {
+        &self.public
+    }
+}
+
+/// RFC 6979 §2.3.2: leftmost qlen bits of b as an integer
+fn bits2int(b: &[u8], qlen: usize) -> BigUint {
+    let x = BigUint::from_bytes_be(b);
+    let blen = b.len() * 8;
+    if blen > qlen { x >> (blen - qlen) } else { x }
+}
+
+/// RFC 6979 §2.3.3: x as rlen / 8 big-endian bytes
+fn int2octets(x: &BigUint, rlen: usize) -> Vec<u8> {
+    let bytes = x.to_bytes_be();
+    let mut out = vec![0u8; rlen - bytes.len()];
+    out.extend_from_slice(&bytes);
+    out
+}
+
+fn hmac(key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC takes any key length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// RFC 6979 §3.2 HMAC-DRBG producing the candidate nonces k in [1, q)
+struct Rfc6979 {
+    k: Vec<u8>,
+    v: Vec<u8>,
+    q: BigUint,
+    first: bool,
+}
+
+impl Rfc6979 {
+    fn new(q: &BigUint, x: &BigUint, h1: &[u8]) -> Self {
+        let qlen = q.bits() as usize;
+        let rlen = qlen.div_ceil(8);
+        let x = int2octets(x, rlen);
+        // bits2octets: reduce once mod q
+        let z1 = bits2int(h1, qlen);
+        let z2 = if &z1 >= q { z1 - q } else { z1 };
+        let h = int2octets(&z2, rlen);
+        let mut k = vec![0u8; 32];
+        let mut v = vec![1u8; 32];
+        k = hmac(&k, &[&v, &[0x00], &x, &h]);
+        v = hmac(&k, &[&v]);
+        k = hmac(&k, &[&v, &[0x01], &x, &h]);
+        v = hmac(&k, &[&v]);
+        Rfc6979 { k, v, q: q.clone(), first: true }
+    }
+
+    fn next_k(&mut self) -> BigUint {
+        let qlen = self.q.bits() as usize;
+        loop {
+            if !self.first {
+                // step h.3: previous candidate rejected
+                self.k = hmac(&self.k, &[&self.v, &[0x00]]);
+                self.v = hmac(&self.k, &[&self.v]);
+            }
+            self.first = false;
+            let mut t = Vec::new();
+            while t.len() * 8 < qlen {
+                self.v = hmac(&self.k, &[&self.v]);
+                t.extend_from_slice(&self.v);
+            }
+            let k = bits2int(&t, qlen);
+            if !k.is_zero() && k < self.q {
+                return k;
+            }
+        }
+    }
+}
+
+/// z: the leftmost min(N, outlen) bits of SHA-256(msg)
+fn digest_int(msg: &[u8], q: &BigUint) -> (Vec<u8>, BigUint) {
+    let h1 = Sha256::digest(msg).to_vec();
+    let z = bits2int(&h1, q.bits() as usize);
+    (h1, z)
+}
+
+/// FIPS 186-4 §4.6 with the RFC 6979 nonce: (r, s), both in [1, q)
+pub fn sign(sk: &DsaPrivateKey, msg: &[u8]) -> (BigUint, BigUint) {
+    let domain = &sk.public.domain;
+    let (p, q, g) = (&domain.p, &domain.q, &domain.g);
+    let (h1, z) = digest_int(msg, q);
+    let mut nonces = Rfc6979::new(q, &sk.x, &h1);
+    loop {
+        let k = nonces.next_k();
+        let r = modpow(g, &k, p) % q;
+        if r.is_zero() {
+            continue;
+        }
+        let k_inv = mod_inverse(&k, q).expect("q is prime");
+        let s = k_inv * ((&z + &sk.x * &r) % q) % q;
+        if !s.is_zero() {
+            return (r, s);
+        }
+    }
+}
+
+/// FIPS 186-4 §4.7
+pub fn verify(pk: &DsaPublicKey, msg: &[u8], r: &BigUint, s: &BigUint) -> Result<(), Fips186Error> {
+    let (p, q, g) = (&pk.domain.p, &pk.domain.q, &pk.domain.g);
+    if r.is_zero() || r >= q || s.is_zero() || s >= q {
+        return Err(Fips186Error::InvalidSignature);
+    }
+    let (_, z) = digest_int(msg, q);
+    let w = mod_inverse(s, q).expect("q is prime");
+    let u1 = z * &w % q;
+    let u2 = r * &w % q;
+    let v = modpow(g, &u1, p) * modpow(&pk.y, &u2, p) % p % q;
+    if &v == r { Ok(()) } else { Err(Fips186Error::InvalidSignature) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const DSA2048: &str = include_str!("fixtures/dsa2048-256-sha256.txt");
+    const DSA3072: &str = include_str!("fixtures/dsa3072-256-sha256.txt");
+    const RFC6979_A22: &str = include_str!("fixtures/rfc6979-a22-dsa2048.txt");
+
+    /// `name = value` lines, # comments
+    fn fields(text: &str) -> HashMap<&str, &str> {
+        text.lines()
+            .filter(|l| !l.starts_with('#'))
+            .filter_map(|l| l.split_once('='))
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .collect()
+    }
+
+    fn hex(f: &HashMap<&str, &str>, name: &str) -> BigUint {
+        BigUint::parse_bytes(f[name].as_bytes(), 16).unwrap()
+    }
+
+    fn fixture(text: &str) -> (BigUint, BigUint, BigUint, DomainSeed) {
+        let f = fields(text);
+        let seed = (0..f["seed"].len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&f["seed"][i..i + 2], 16).unwrap())
+            .collect();
+        let seed = DomainSeed { seed, counter: f["counter"].parse().unwrap(), index: f["index"].parse().unwrap() };
+        (hex(&f, "p"), hex(&f, "q"), hex(&f, "g"), seed)
+    }
+
+    #[test]
+    fn openssl_parameters_verify() {
+        for (text, size) in [(DSA2048, (2048, 256)), (DSA3072, (3072, 256))] {
+            let (p, q, g, seed) = fixture(text);
+            let domain = DsaDomain::from_seed(p.clone(), q.clone(), g.clone(), seed.clone()).unwrap();
+            assert_eq!(domain.size(), size);
+            assert_eq!(domain.seed(), Some(&seed));
+
+            // any change to the seed record is caught
+            let bad = |s: DomainSeed| DsaDomain::from_seed(p.clone(), q.clone(), g.clone(), s);
+            let mut flipped = seed.seed.clone();
+            flipped[0] ^= 1;
+            assert!(matches!(bad(DomainSeed { seed: flipped, ..seed.clone() }), Err(Fips186Error::InvalidParams(_))));
+            assert!(matches!(bad(DomainSeed { counter: seed.counter + 1, ..seed.clone() }), Err(Fips186Error::InvalidParams(_))));
+            assert!(matches!(bad(DomainSeed { index: 2, ..seed.clone() }), Err(Fips186Error::InvalidParams(_))));
+            assert_eq!(
+                bad(DomainSeed { seed: seed.seed[..16].to_vec(), ..seed.clone() }),
+                Err(Fips186Error::SeedTooShort { got: 128, min: 256 })
+            );
+        }
+    }
+
+    #[test]
+    fn rfc6979_vectors() {
+        let f = fields(RFC6979_A22);
+        let domain = DsaDomain::from_pqg(hex(&f, "p"), hex(&f, "q"), hex(&f, "g")).unwrap();
+        let sk = DsaPrivateKey::from_x(domain.clone(), hex(&f, "x")).unwrap();
+        assert_eq!(sk.public_key().y, hex(&f, "y"));
+        assert!(DsaPublicKey::new(domain.clone(), hex(&f, "y")).is_ok());
+        for (msg, name) in [(&b"sample"[..], "sample"), (&b"test"[..], "test")] {
+            let (r, s) = sign(&sk, msg);
+            assert_eq!(r, hex(&f, &format!("r_{name}")));
+            assert_eq!(s, hex(&f, &format!("s_{name}")));
+            assert_eq!(verify(sk.public_key(), msg, &r, &s), Ok(()));
+            assert_eq!(verify(sk.public_key(), b"other", &r, &s), Err(Fips186Error::InvalidSignature));
+            assert_eq!(verify(sk.public_key(), msg, &(&r + domain.q()), &s), Err(Fips186Error::InvalidSignature));
+        }
+        assert_eq!(DsaPrivateKey::from_x(domain.clone(), domain.q().clone()).unwrap_err(), Fips186Error::InvalidPrivateKey);
+        assert_eq!(DsaPublicKey::new(domain, BigUint::one()), Err(Fips186Error::InvalidPublicKey));
+    }
+
+    #[test]
+    fn sizes_and_generated_keys() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let mut rng = StdRng::seed_from_u64(50);
+        assert_eq!(DsaDomain::generate_with_rng(1024, 160, 1, &mut rng), Err(Fips186Error::UnsupportedSize(1024, 160)));
+        let (p, q, g, _) = fixture(DSA3072);
+        assert!(DsaDomain::from_pqg(p.clone(), q.clone(), &g + 1u32).is_err());
+        let domain = DsaDomain::from_pqg(p, q, g).unwrap();
+        assert_eq!(domain.seed(), None);
+        let sk = DsaPrivateKey::generate_with_rng(&domain, &mut rng);
+        let (r, s) = sign(&sk, b"hello");
+        assert_eq!(sign(&sk, b"hello"), (r.clone(), s.clone()));
+        assert_eq!(verify(sk.public_key(), b"hello", &r, &s), Ok(()));
+    }
+
+    // A 2048-bit p search takes over a minute in a debug build:
+    // cargo test --release -- --ignored generated_domain
+    #[test]
+    #[ignore]
+    fn generated_domain_verifies_from_seed() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let mut rng = StdRng::seed_from_u64(186);
+        let domain = DsaDomain::generate_with_rng(2048, 256, 1, &mut rng).unwrap();
+        assert_eq!(domain.size(), (2048, 256));
+        let seed = domain.seed().expect("generated domains keep their seed").clone();
+        assert_eq!(seed.index, 1);
+        let (p, q, g) = (domain.p().clone(), domain.q().clone(), domain.g().clone());
+        assert_eq!(DsaDomain::from_seed(p.clone(), q.clone(), g.clone(), seed.clone()), Ok(domain));
+        // another counter or index does not reproduce p or g
+        let bumped = DomainSeed { counter: seed.counter + 1, ..seed.clone() };
+        assert!(DsaDomain::from_seed(p.clone(), q.clone(), g.clone(), bumped).is_err());
+        let other_index = DomainSeed { index: 2, ..seed };
+        assert!(DsaDomain::from_seed(p, q, g, other_index).is_err());
+    }
+}
@@ -12,3 +12,6 @@ pub mod verify;
 
 #[path ="5-dsa.rs"]
 pub mod dsa; // dsa.rs
+
+#[path ="6-fips186.rs"]
+pub mod fips186;
@@ -1,5 +1,7 @@
 //! src/group/4-ecdsa/1-params.rs
 use super::super::ecc::curve::Curve;
+use super::super::ecc::counting::{prime_order_generator, prime_point_order};
+use super::super::ecc::validate::{validate_toy_curve, CurveParamError};
 use super::super::ecc::point::Point;
 
 /// ECDSA domain parameters
@@ -11,26 +13,58 @@ pub struct ECDSAParams {
 }
 
 impl ECDSAParams {
+    /// Parameters with n computed as the order of G; None when the curve is
+    /// singular, G is not on it, or that order is not prime
+    pub fn new(curve: Curve, g: Point) -> Option<Self> {
+        let n = prime_point_order(g, &curve)?;
+        Some(ECDSAParams { curve, G: g, n })
+    }
+
+    /// Curve, generator and order checks; see ecc::validate
+    pub fn validate(&self) -> Result<(), CurveParamError> {
+        validate_toy_curve(&self.curve, self.G, self.n)
+    }
+
     /// Example parameters for testing (toy curve, do NOT use in production)
     pub fn example() -> Self {
-        let curve = Curve { a: 2, b: 3, p: 97 };
-        let G = Point { x: 3, y: 6, infinity: false };
-        let n = 5; // small order for testing
-        ECDSAParams { curve, G, n }
+        // 89 points, a prime: cofactor 1 and G generates the whole group
+        let curve = Curve { a: 1, b: 4, p: 97 };
+        let sub = prime_order_generator(&curve).expect("curve is nonsingular");
+        Self::new(curve, sub.generator).expect("toy parameters are valid")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::group::ecc::point::point_add;
 
     #[test]
     fn test_example_params() {
         let params = ECDSAParams::example();
-        assert_eq!(params.curve.a, 2);
-        assert_eq!(params.curve.b, 3);
+        assert_eq!(params.curve.a, 1);
+        assert_eq!(params.curve.b, 4);
         assert_eq!(params.curve.p, 97);
-        assert_eq!(params.G.x, 3);
-        assert_eq!(params.n, 5);
+        assert_eq!((params.G.x, params.G.y), (0, 2));
+        assert_eq!(params.n, 89);
+        assert_eq!(params.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_new_rejects_bad_generators() {
+        let curve = Curve { a: 2, b: 3, p: 97 };
+        // off the curve
+        assert!(ECDSAParams::new(curve, Point { x: 3, y: 5, infinity: false }).is_none());
+        assert!(ECDSAParams::new(curve, Point { x: 0, y: 0, infinity: true }).is_none());
+        // on y² = x³ + 2x + 3 (100 points): (3, 6) and 2·(3, 6) have order 5,
+        // (0, 10) has order 50
+        let g = Point { x: 3, y: 6, infinity: false };
+        assert_eq!(ECDSAParams::new(curve, g).map(|p| p.n), Some(5));
+        let two_g = point_add(g, g, &curve);
+        assert_eq!(ECDSAParams::new(curve, two_g).map(|p| p.n), Some(5));
+        assert!(ECDSAParams::new(curve, Point { x: 0, y: 10, infinity: false }).is_none());
+        // fields edited after construction
+        let bad = ECDSAParams { n: 7, ..ECDSAParams::example() };
+        assert_eq!(bad.validate(), Err(CurveParamError::WrongOrder));
     }
 }
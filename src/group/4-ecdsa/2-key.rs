@@ -2,6 +2,7 @@
 use super::params::ECDSAParams;
 use super::super::ecc::scalar::scalar_mul;
 use super::super::ecc::point::Point;
+use super::super::ecc::validate::CurveParamError;
 
 /// ECDSA keypair
 #[derive(Debug)]
@@ -10,10 +11,11 @@ pub struct ECDSAKeyPair {
     pub public: Point, // public key Q = d*G
 }
 
-/// Generate ECDSA keypair
-pub fn keygen(params: &ECDSAParams, d: i64) -> ECDSAKeyPair {
+/// Generate ECDSA keypair; refuses parameters that fail `ECDSAParams::validate`
+pub fn keygen(params: &ECDSAParams, d: i64) -> Result<ECDSAKeyPair, CurveParamError> {
+    params.validate()?;
     let Q = scalar_mul(d, params.G, &params.curve);
-    ECDSAKeyPair { private: d, public: Q }
+    Ok(ECDSAKeyPair { private: d, public: Q })
 }
 
 #[cfg(test)]
@@ -25,8 +27,14 @@ mod tests {
     fn test_keygen() {
         let params = ECDSAParams::example();
         let d = 3;
-        let keypair = keygen(&params, d);
+        let keypair = keygen(&params, d).unwrap();
         assert_eq!(keypair.private, 3);
         assert!(!keypair.public.infinity);
     }
+
+    #[test]
+    fn test_keygen_rejects_bad_params() {
+        let params = ECDSAParams { n: 25, ..ECDSAParams::example() };
+        assert_eq!(keygen(&params, 3).unwrap_err(), CurveParamError::OrderNotPrime);
+    }
 }
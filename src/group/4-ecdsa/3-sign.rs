@@ -1,24 +1,35 @@
 //! src/group/4-ecdsa/3-sign.rs
+use thiserror::Error;
 use super::params::ECDSAParams;
 use super::key::ECDSAKeyPair;
 use super::super::ecc::scalar::scalar_mul;
 use super::super::ecc::field::mod_inverse;
+use super::super::ecc::validate::CurveParamError;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ECDSASignError {
+    #[error(transparent)]
+    Params(#[from] CurveParamError),
+    #[error("nonce k is outside [1, n) or gives r = 0 or s = 0, retry with another")]
+    BadNonce,
+}
 
 /// Sign a message `m` using private key d and nonce k
-pub fn sign(params: &ECDSAParams, key: &ECDSAKeyPair, m: i64, k: i64) -> Option<(i64, i64)> {
+pub fn sign(params: &ECDSAParams, key: &ECDSAKeyPair, m: i64, k: i64) -> Result<(i64, i64), ECDSASignError> {
+    params.validate()?;
     if k <= 0 || k >= params.n {
-        return None;
+        return Err(ECDSASignError::BadNonce);
     }
 
     let R = scalar_mul(k, params.G, &params.curve);
     let r = R.x.rem_euclid(params.n);
-    if r == 0 { return None; }
+    if r == 0 { return Err(ECDSASignError::BadNonce); }
 
-    let k_inv = mod_inverse(k, params.n)?;
+    let k_inv = mod_inverse(k, params.n).ok_or(ECDSASignError::BadNonce)?;
     let s = (k_inv * (m + key.private * r)).rem_euclid(params.n);
-    if s == 0 { return None; }
+    if s == 0 { return Err(ECDSASignError::BadNonce); }
 
-    Some((r, s))
+    Ok((r, s))
 }
 
 #[cfg(test)]
@@ -30,8 +41,24 @@ mod tests {
     #[test]
     fn test_sign() {
         let params = ECDSAParams::example();
-        let keypair = keygen(&params, 3);
+        let keypair = keygen(&params, 3).unwrap();
+        // 2G = (91, 48): r = 91 mod 89 = 2
         let sig = sign(&params, &keypair, 7, 2).unwrap();
-        assert!(sig.0 > 0 && sig.1 > 0);
+        assert_eq!(sig.0, 2);
+        assert!(sig.1 > 0 && sig.1 < params.n);
+        // the only nonces refused are r = 0 (R.x ≡ 0 mod n) or s = 0
+        let refused: Vec<i64> = (1..params.n).filter(|&k| sign(&params, &keypair, 7, k).is_err()).collect();
+        assert_eq!(refused, vec![1, 36, 37, 52, 53, 88]);
+    }
+
+    #[test]
+    fn test_sign_rejects_bad_params() {
+        let params = ECDSAParams::example();
+        let keypair = keygen(&params, 3).unwrap();
+        assert_eq!(sign(&params, &keypair, 7, params.n), Err(ECDSASignError::BadNonce));
+        // G = (0, 2), so k = 1 gives r = 0
+        assert_eq!(sign(&params, &keypair, 7, 1), Err(ECDSASignError::BadNonce));
+        let bad = ECDSAParams { n: 7, ..params };
+        assert_eq!(sign(&bad, &keypair, 7, 2), Err(ECDSASignError::Params(CurveParamError::WrongOrder)));
     }
 }
@@ -32,8 +32,15 @@ mod tests {
     #[test]
     fn test_verify() {
         let params = ECDSAParams::example();
-        let keypair = keygen(&params, 3);
+        let keypair = keygen(&params, 3).unwrap();
         let sig = sign(&params, &keypair, 7, 2).unwrap();
         assert!(verify(&params, keypair.public, 7, sig.0, sig.1));
+        assert!(!verify(&params, keypair.public, 8, sig.0, sig.1));
+        // every nonce sign accepts gives a valid signature
+        for k in 1..params.n {
+            if let Ok((r, s)) = sign(&params, &keypair, 7, k) {
+                assert!(verify(&params, keypair.public, 7, r, s), "k = {k}");
+            }
+        }
     }
 }
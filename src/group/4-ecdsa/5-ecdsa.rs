@@ -7,11 +7,11 @@ use super::verify::verify;
 pub fn demo() {
     let params = ECDSAParams::example();
     let d = 3;
-    let keypair = keygen(&params, d);
+    let keypair = keygen(&params, d).unwrap();
     println!("ECDSA Keypair: {:?}", keypair);
 
     let m = 7; // message
-    let k = 2; // random nonce
+    let k = 2; // random nonce; sign refuses k with r = 0 or s = 0 (k = 1 here)
     let signature = sign(&params, &keypair, m, k).unwrap();
     println!("Message {} signed: {:?}", m, signature);
 
@@ -1,5 +1,8 @@
 //! src/group/5-eddsa/1-params.rs
 use super::super::ecc::field::mod_inverse;
+use super::super::ecc::counting::{prime_order_generator, prime_point_order};
+use super::super::ecc::curve::Curve;
+use super::super::ecc::validate::{validate_toy_curve, CurveParamError};
 use super::super::ecc::point::Point;
 
 /// Edwards curve parameters for EdDSA
@@ -13,21 +16,38 @@ pub struct EdDSAParams {
 }
 
 impl EdDSAParams {
+    /// The curve keygen, sign and verify do arithmetic on:
+    /// y^2 = x^3 + a*x + d mod p
+    pub fn curve(&self) -> Curve {
+        Curve { a: self.a, b: self.d, p: self.p }
+    }
+
+    /// Parameters with n computed as the order of G on `curve()`; None when
+    /// that curve is singular, G is not on it, or the order is not prime
+    pub fn new(p: i64, a: i64, d: i64, g: Point) -> Option<Self> {
+        let n = prime_point_order(g, &Curve { a, b: d, p })?;
+        Some(EdDSAParams { p, a, d, G: g, n })
+    }
+
+    /// Curve, generator and order checks on `curve()`; see ecc::validate
+    pub fn validate(&self) -> Result<(), CurveParamError> {
+        validate_toy_curve(&self.curve(), self.G, self.n)
+    }
+
     /// Example small curve parameters for testing
     pub fn example() -> Self {
-        EdDSAParams {
-            p: 97,      // small prime for testing
-            a: 1,       // twisted Edwards curve a
-            d: 2,       // twisted Edwards curve d
-            G: Point { x: 3, y: 6, infinity: false },
-            n: 5,       // small order for testing
-        }
+        // 104 = 8 * 13 points; G generates the subgroup of order 13
+        let (p, a, d) = (97, 1, 2);
+        let sub = prime_order_generator(&Curve { a, b: d, p }).expect("curve is nonsingular");
+        Self::new(p, a, d, sub.generator).expect("toy parameters are valid")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::group::ecc::curve::is_on_curve;
+    use crate::group::ecc::scalar::scalar_mul;
 
     #[test]
     fn test_example_params() {
@@ -35,7 +55,20 @@ mod tests {
         assert_eq!(params.p, 97);
         assert_eq!(params.a, 1);
         assert_eq!(params.d, 2);
-        assert_eq!(params.G.x, 3);
-        assert_eq!(params.n, 5);
+        assert_eq!(params.n, 13);
+        assert!(is_on_curve(params.G.x, params.G.y, &params.curve()));
+        assert!(scalar_mul(params.n, params.G, &params.curve()).infinity);
+        assert_eq!(params.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_new_rejects_bad_generators() {
+        // the old example generator is not on the curve
+        assert!(EdDSAParams::new(97, 1, 2, Point { x: 3, y: 6, infinity: false }).is_none());
+        // (4, 19) generates all 104 points
+        assert!(EdDSAParams::new(97, 1, 2, Point { x: 4, y: 19, infinity: false }).is_none());
+        // the pre-validation example: G off the curve, n = 5
+        let old = EdDSAParams { p: 97, a: 1, d: 2, G: Point { x: 3, y: 6, infinity: false }, n: 5 };
+        assert_eq!(old.validate(), Err(CurveParamError::GeneratorNotOnCurve));
     }
 }
@@ -3,6 +3,7 @@ use super::params::EdDSAParams;
 use super::super::ecc::scalar::scalar_mul;
 use super::super::ecc::curve::Curve; // import Curve
 use super::super::ecc::point::Point;
+use super::super::ecc::validate::CurveParamError;
 
 /// EdDSA keypair
 #[derive(Debug)]
@@ -11,12 +12,13 @@ pub struct EdDSAKeyPair {
     pub public: Point, // public key A = d*G
 }
 
-/// Generate EdDSA keypair
-pub fn keygen(params: &EdDSAParams, d: i64) -> EdDSAKeyPair {
+/// Generate EdDSA keypair; refuses parameters that fail `EdDSAParams::validate`
+pub fn keygen(params: &EdDSAParams, d: i64) -> Result<EdDSAKeyPair, CurveParamError> {
+    params.validate()?;
     // create a Curve struct just for scalar_mul
     let curve = Curve { a: params.a, b: params.d, p: params.p }; 
     let A = scalar_mul(d, params.G, &curve);
-    EdDSAKeyPair { private: d, public: A }
+    Ok(EdDSAKeyPair { private: d, public: A })
 }
 
 #[cfg(test)]
@@ -28,8 +30,14 @@ mod tests {
     fn test_keygen() {
         let params = EdDSAParams::example();
         let d = 3;
-        let keypair = keygen(&params, d);
+        let keypair = keygen(&params, d).unwrap();
         assert_eq!(keypair.private, 3);
         assert!(!keypair.public.infinity);
     }
+
+    #[test]
+    fn test_keygen_rejects_bad_params() {
+        let params = EdDSAParams { G: Point { x: 3, y: 6, infinity: false }, ..EdDSAParams::example() };
+        assert_eq!(keygen(&params, 3).unwrap_err(), CurveParamError::GeneratorNotOnCurve);
+    }
 }
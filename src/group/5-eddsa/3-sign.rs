@@ -2,33 +2,45 @@
 use super::params::EdDSAParams;
 use super::key::EdDSAKeyPair;
 use super::super::ecc::scalar::scalar_mul;
-use super::super::ecc::point::point_add;
+use super::super::ecc::point::Point;
+use super::super::ecc::validate::CurveParamError;
 
 /// Simple deterministic "hash" function for testing
-fn hash_message(m: i64) -> i64 {
-    m % 97 // replace with real hash in production
+fn hash_message(parts: &[i64]) -> i64 {
+    parts.iter().fold(17, |h, &x| (h * 31 + x).rem_euclid(97)) // replace with real hash in production
 }
 
-/// Sign a message m
-pub fn sign(params: &EdDSAParams, key: &EdDSAKeyPair, m: i64) -> (i64, i64) {
-    let r = hash_message(m); // deterministic nonce
-    let curve = super::super::ecc::curve::Curve { a: params.a, b: params.d, p: params.p };
-    let R = scalar_mul(r, params.G, &curve);
-    let s = (r + key.private * R.x).rem_euclid(params.n); // simplified
-    (R.x, s)
+/// Challenge e = H(R.x, A, m) mod n, shared by sign and verify
+pub fn challenge(params: &EdDSAParams, r: i64, public: Point, m: i64) -> i64 {
+    hash_message(&[r, public.x, public.y, m]).rem_euclid(params.n)
+}
+
+/// Sign a message m; refuses parameters that fail `EdDSAParams::validate`
+/// R = k·G with k = H(d, m) in [1, n), s = k + e·d mod n; the signature is
+/// (R.x, s), and s·G - e·A = k·G = R
+pub fn sign(params: &EdDSAParams, key: &EdDSAKeyPair, m: i64) -> Result<(i64, i64), CurveParamError> {
+    params.validate()?;
+    let k = 1 + hash_message(&[key.private, m]).rem_euclid(params.n - 1); // deterministic nonce
+    let R = scalar_mul(k, params.G, &params.curve());
+    let e = challenge(params, R.x, key.public, m);
+    let s = (k + e * key.private).rem_euclid(params.n);
+    Ok((R.x, s))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use super::super::params::EdDSAParams;
-    use super::super::key::{keygen, EdDSAKeyPair};
+    use super::super::key::keygen;
 
     #[test]
     fn test_sign() {
         let params = EdDSAParams::example();
-        let keypair = keygen(&params, 3);
-        let sig = sign(&params, &keypair, 7);
-        assert!(sig.0 > 0 && sig.1 > 0);
+        let keypair = keygen(&params, 3).unwrap();
+        let sig = sign(&params, &keypair, 7).unwrap();
+        assert!(sig.0 >= 0 && sig.0 < params.p);
+        assert!(sig.1 >= 0 && sig.1 < params.n);
+        // deterministic: same key and message, same signature
+        assert_eq!(sign(&params, &keypair, 7).unwrap(), sig);
     }
 }
@@ -1,17 +1,22 @@
 //! src/group/5-eddsa/4-verify.rs
 use super::params::EdDSAParams;
+use super::sign::challenge;
 use super::super::ecc::scalar::scalar_mul;
 use super::super::ecc::point::point_add;
-use super::super::ecc::curve::Curve;
 use super::super::ecc::point::Point;
 
-/// Verify EdDSA signature (R_x, s)
+/// Verify EdDSA signature (R_x, s): s·G - e·A must be a point with x = R_x,
+/// where -e·A is computed as (n - e)·A
 pub fn verify(params: &EdDSAParams, public: Point, m: i64, r: i64, s: i64) -> bool {
-    let curve = Curve { a: params.a, b: params.d, p: params.p };
+    if s < 0 || s >= params.n {
+        return false;
+    }
+    let curve = params.curve();
+    let e = challenge(params, r, public, m);
     let R1 = scalar_mul(s, params.G, &curve);
-    let R2 = scalar_mul(r, public, &curve);
+    let R2 = scalar_mul(params.n - e, public, &curve);
     let R_check = point_add(R1, R2, &curve);
-    R_check.x.rem_euclid(params.n) == r
+    !R_check.infinity && R_check.x == r
 }
 
 #[cfg(test)]
@@ -24,8 +29,13 @@ mod tests {
     #[test]
     fn test_verify() {
         let params = EdDSAParams::example();
-        let keypair = keygen(&params, 3);
-        let (r, s) = sign(&params, &keypair, 7);
+        let keypair = keygen(&params, 3).unwrap();
+        let (r, s) = sign(&params, &keypair, 7).unwrap();
         assert!(verify(&params, keypair.public, 7, r, s));
+        // another message, a changed s, or another key
+        assert!(!verify(&params, keypair.public, 8, r, s));
+        assert!(!verify(&params, keypair.public, 7, r, (s + 1) % params.n));
+        let other = keygen(&params, 5).unwrap();
+        assert!(!verify(&params, other.public, 7, r, s));
     }
 }
@@ -7,11 +7,11 @@ use super::verify::verify;
 pub fn demo() {
     let params = EdDSAParams::example();
     let d = 3;
-    let keypair = keygen(&params, d);
+    let keypair = keygen(&params, d).unwrap();
     println!("EdDSA Keypair: {:?}", keypair);
 
     let m = 7;
-    let (r, s) = sign(&params, &keypair, m);
+    let (r, s) = sign(&params, &keypair, m).unwrap();
     println!("Message {} signed: r={}, s={}", m, r, s);
 
     let valid = verify(&params, keypair.public, m, r, s);
@@ -1,6 +1,10 @@
 // src/group/secp256k1/1-params.rs
-use num_bigint::BigUint;
+use num_bigint::{BigInt, BigUint};
 use num_traits::{FromPrimitive, One, Zero};
+use std::sync::OnceLock;
+use crate::group::ecc::validate::{check_attacks, CurveParamError, MOV_BOUND};
+use crate::group::utils::primes::is_prime;
+use super::point::{point_neg, scalar_mul, AffinePoint};
 
 /// secp256k1 parameters (constants)
 pub struct Secp256k1 {
@@ -10,9 +14,12 @@ pub struct Secp256k1 {
     pub gx: BigUint,
     pub gy: BigUint,
     pub n: BigUint,
+    pub h: BigUint, // cofactor #E / n
 }
 
 impl Secp256k1 {
+    /// The standard constants, checked by `validate` on the first call only;
+    /// signing and the protocols built on it trust a curve from here
     pub fn new() -> Self {
         let p = BigUint::parse_bytes(b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F", 16).unwrap();
         let a = BigUint::zero(); // a = 0
@@ -20,7 +27,68 @@ impl Secp256k1 {
         let gx = BigUint::parse_bytes(b"79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798", 16).unwrap();
         let gy = BigUint::parse_bytes(b"483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8", 16).unwrap();
         let n = BigUint::parse_bytes(b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141", 16).unwrap();
+        let h = BigUint::one(); // prime-order curve
 
-        Secp256k1 { p, a, b, gx, gy, n }
+        let curve = Secp256k1 { p, a, b, gx, gy, n, h };
+        static VALIDATED: OnceLock<Result<(), CurveParamError>> = OnceLock::new();
+        if let Err(e) = VALIDATED.get_or_init(|| curve.validate()) {
+            panic!("secp256k1 constants fail validation: {e}");
+        }
+        curve
+    }
+
+    /// SEC 1 §3.1.1.2.1 checks, see ecc::validate; for parameters edited
+    /// after `new`
+    pub fn validate(&self) -> Result<(), CurveParamError> {
+        let (p, n) = (&self.p, &self.n);
+        if !is_prime(&BigInt::from(p.clone())) {
+            return Err(CurveParamError::FieldNotPrime);
+        }
+        let disc = (4u32 * self.a.modpow(&BigUint::from(3u32), p) + 27u32 * (&self.b * &self.b)) % p;
+        if disc.is_zero() {
+            return Err(CurveParamError::Singular);
+        }
+        let g = AffinePoint::generator(self);
+        if !g.is_on_curve(self) {
+            return Err(CurveParamError::GeneratorNotOnCurve);
+        }
+        if !is_prime(&BigInt::from(n.clone())) {
+            return Err(CurveParamError::OrderNotPrime);
+        }
+        // scalar_mul reduces k mod n, so check (n - 1)·G = -G instead of n·G = O
+        if scalar_mul(&(n - 1u32), &g, self) != point_neg(&g, self) {
+            return Err(CurveParamError::WrongOrder);
+        }
+        // h = ⌊(√p + 1)² / n⌋ = ⌊(p + 1 + ⌊2√p⌋) / n⌋, unique when n > 4√p
+        if n * n <= 16u32 * p {
+            return Err(CurveParamError::SubgroupTooSmall);
+        }
+        if (p + 1u32 + (4u32 * p).sqrt()) / n != self.h {
+            return Err(CurveParamError::CofactorMismatch);
+        }
+        check_attacks(&BigInt::from(p.clone()), &BigInt::from(n.clone()), MOV_BOUND)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secp256k1_validates() {
+        assert_eq!(Secp256k1::new().validate(), Ok(()));
+
+        let mut bad = Secp256k1::new();
+        bad.h = BigUint::from(2u32);
+        assert_eq!(bad.validate(), Err(CurveParamError::CofactorMismatch));
+        let mut bad = Secp256k1::new();
+        bad.b = BigUint::from(8u32);
+        assert_eq!(bad.validate(), Err(CurveParamError::GeneratorNotOnCurve));
+        let mut bad = Secp256k1::new();
+        bad.n += 2u32;
+        assert!(bad.validate().is_err());
+        let mut bad = Secp256k1::new();
+        bad.p -= 2u32;
+        assert_eq!(bad.validate(), Err(CurveParamError::FieldNotPrime));
     }
 }
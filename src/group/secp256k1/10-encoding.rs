@@ -0,0 +1,329 @@
+// src/group/secp256k1/10-encoding.rs
+// -----------------------------------------------------------------------------
+// Signed and fixed-point plaintext encoding for Paillier
+// -----------------------------------------------------------------------------
+// Paillier plaintexts live in Z_n. Signed integers are mapped with the usual
+// centered representation
+//
+//     m ∈ [-n/2, n/2)  ↦  m mod n           x ∈ [0, n)  ↦  x - n if x >= n/2
+//
+// and fixed-point decimals are integers with an implicit scale 10^decimals.
+//
+// Homomorphic operations cannot see the plaintext, so wrap-around can only be
+// caught by bookkeeping: every `EncryptedNumber` carries a public bound on
+// |value| that grows with each operation. An operation whose result bound
+// would reach n/2 fails with `EncodingError::Overflow` instead of silently
+// producing a wrong value. Bounds are conservative, e.g. x - x keeps the
+// bound 2|x|.
+// -----------------------------------------------------------------------------
+
+use std::cmp::Ordering;
+use std::fmt;
+use num_bigint::BigInt;
+use num_traits::{One, Signed};
+use thiserror::Error;
+use super::pailliar::{mod_positive, Ciphertext, PaillierError, PrivateKey, PublicKey};
+
+#[derive(Error, Debug)]
+pub enum EncodingError {
+    #[error("value does not fit in [-n/2, n/2)")]
+    Overflow,
+    #[error("not a finite number")]
+    NotFinite,
+    #[error("malformed decimal: {0}")]
+    Malformed(String),
+    #[error("expected an integer, found {0} decimal places")]
+    NotAnInteger(u32),
+    #[error(transparent)]
+    Paillier(#[from] PaillierError),
+}
+
+/// value / 10^decimals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedPoint {
+    pub value: BigInt,
+    pub decimals: u32,
+}
+
+impl FixedPoint {
+    pub fn new<V: Into<BigInt>>(value: V, decimals: u32) -> Self {
+        FixedPoint { value: value.into(), decimals }
+    }
+
+    /// Parse "-12.050"; the number of digits after the point sets `decimals`
+    pub fn parse(s: &str) -> Result<Self, EncodingError> {
+        let malformed = || EncodingError::Malformed(s.to_string());
+        let (neg, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+        if int_part.is_empty() || !int_part.bytes().chain(frac_part.bytes()).all(|b| b.is_ascii_digit()) {
+            return Err(malformed());
+        }
+        if digits.contains('.') && frac_part.is_empty() {
+            return Err(malformed());
+        }
+        let mag = BigInt::parse_bytes(format!("{int_part}{frac_part}").as_bytes(), 10).ok_or_else(malformed)?;
+        Ok(FixedPoint { value: if neg { -mag } else { mag }, decimals: frac_part.len() as u32 })
+    }
+
+    /// Round x to `decimals` places
+    pub fn from_f64(x: f64, decimals: u32) -> Result<Self, EncodingError> {
+        if !x.is_finite() {
+            return Err(EncodingError::NotFinite);
+        }
+        // formatting keeps the exact decimal expansion of the rounded value
+        Self::parse(&format!("{:.*}", decimals as usize, x))
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().expect("Display output parses as f64")
+    }
+
+    /// Same value with more decimal places
+    pub fn rescale(&self, decimals: u32) -> Self {
+        assert!(decimals >= self.decimals, "rescale only adds decimal places");
+        FixedPoint { value: &self.value * pow10(decimals - self.decimals), decimals }
+    }
+}
+
+impl fmt::Display for FixedPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.value.is_negative() { "-" } else { "" };
+        let digits = self.value.abs().to_string();
+        if self.decimals == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+        let d = self.decimals as usize;
+        let digits = format!("{digits:0>width$}", width = d + 1);
+        let (int_part, frac_part) = digits.split_at(digits.len() - d);
+        write!(f, "{sign}{int_part}.{frac_part}")
+    }
+}
+
+/// A ciphertext together with its scale and a public bound on |value|
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedNumber {
+    pub ct: Ciphertext,
+    pub decimals: u32,
+    bound: BigInt,
+}
+
+impl EncryptedNumber {
+    /// Wrap a ciphertext received from elsewhere; `bound` must hold for its
+    /// plaintext, otherwise overflow detection is meaningless
+    pub fn with_bound(ct: Ciphertext, decimals: u32, bound: BigInt) -> Self {
+        EncryptedNumber { ct, decimals, bound: bound.abs() }
+    }
+
+    pub fn bound(&self) -> &BigInt {
+        &self.bound
+    }
+}
+
+/// Signed / fixed-point view of a Paillier public key
+pub struct Encoder<'a> {
+    pk: &'a PublicKey,
+    half: BigInt, // ceil(n/2): n is odd, so valid values are |m| < half
+}
+
+impl<'a> Encoder<'a> {
+    pub fn new(pk: &'a PublicKey) -> Self {
+        let half = (&pk.n + BigInt::one()) >> 1;
+        Encoder { pk, half }
+    }
+
+    /// m ∈ [-n/2, n/2) to a plaintext in [0, n)
+    pub fn encode(&self, m: &BigInt) -> Result<BigInt, EncodingError> {
+        self.check(m)?;
+        Ok(mod_positive(m.clone(), &self.pk.n))
+    }
+
+    /// Plaintext in [0, n) to its centered representative
+    pub fn decode(&self, x: &BigInt) -> BigInt {
+        let x = mod_positive(x.clone(), &self.pk.n);
+        if x >= self.half { x - &self.pk.n } else { x }
+    }
+
+    pub fn encrypt(&self, m: &BigInt) -> Result<EncryptedNumber, EncodingError> {
+        self.encrypt_fixed(&FixedPoint::new(m.clone(), 0))
+    }
+
+    pub fn encrypt_fixed(&self, x: &FixedPoint) -> Result<EncryptedNumber, EncodingError> {
+        let ct = self.pk.encrypt(self.encode(&x.value)?)?;
+        Ok(EncryptedNumber { ct, decimals: x.decimals, bound: x.value.abs() })
+    }
+
+    pub fn decrypt(&self, sk: &PrivateKey, c: &EncryptedNumber) -> Result<FixedPoint, EncodingError> {
+        let x = sk.decrypt(self.pk, &c.ct)?;
+        Ok(FixedPoint::new(self.decode(&x), c.decimals))
+    }
+
+    /// Decrypt a value with no decimal places
+    pub fn decrypt_int(&self, sk: &PrivateKey, c: &EncryptedNumber) -> Result<BigInt, EncodingError> {
+        if c.decimals != 0 {
+            return Err(EncodingError::NotAnInteger(c.decimals));
+        }
+        Ok(self.decrypt(sk, c)?.value)
+    }
+
+    pub fn add(&self, a: &EncryptedNumber, b: &EncryptedNumber) -> Result<EncryptedNumber, EncodingError> {
+        let (a, b) = self.align(a, b)?;
+        let bound = self.checked(&a.bound + &b.bound)?;
+        Ok(EncryptedNumber { ct: self.pk.add(&a.ct, &b.ct), decimals: a.decimals, bound })
+    }
+
+    pub fn sub(&self, a: &EncryptedNumber, b: &EncryptedNumber) -> Result<EncryptedNumber, EncodingError> {
+        let (a, b) = self.align(a, b)?;
+        let bound = self.checked(&a.bound + &b.bound)?;
+        Ok(EncryptedNumber { ct: self.pk.sub(&a.ct, &b.ct)?, decimals: a.decimals, bound })
+    }
+
+    pub fn neg(&self, a: &EncryptedNumber) -> Result<EncryptedNumber, EncodingError> {
+        let bound = self.checked(a.bound.clone())?;
+        Ok(EncryptedNumber { ct: self.pk.neg(&a.ct)?, decimals: a.decimals, bound })
+    }
+
+    /// Add a public signed plaintext
+    pub fn add_plain(&self, a: &EncryptedNumber, x: &FixedPoint) -> Result<EncryptedNumber, EncodingError> {
+        let decimals = a.decimals.max(x.decimals);
+        let a = self.upscale(a, decimals)?;
+        let x = x.rescale(decimals);
+        let bound = self.checked(&a.bound + x.value.abs())?;
+        let ct = self.pk.add_plain(&a.ct, self.encode(&x.value)?)?;
+        Ok(EncryptedNumber { ct, decimals, bound })
+    }
+
+    /// Multiply by a public signed integer
+    pub fn mul_int(&self, a: &EncryptedNumber, k: &BigInt) -> Result<EncryptedNumber, EncodingError> {
+        self.mul_fixed(a, &FixedPoint::new(k.clone(), 0))
+    }
+
+    /// Multiply by a public fixed-point scalar; decimal places add up
+    pub fn mul_fixed(&self, a: &EncryptedNumber, k: &FixedPoint) -> Result<EncryptedNumber, EncodingError> {
+        let bound = self.checked(&a.bound * k.value.abs())?;
+        Ok(EncryptedNumber {
+            ct: self.pk.mul_scalar(&a.ct, k.value.clone()),
+            decimals: a.decimals + k.decimals,
+            bound,
+        })
+    }
+
+    // bring both operands to the larger number of decimal places
+    fn align(&self, a: &EncryptedNumber, b: &EncryptedNumber) -> Result<(EncryptedNumber, EncryptedNumber), EncodingError> {
+        match a.decimals.cmp(&b.decimals) {
+            Ordering::Equal => Ok((a.clone(), b.clone())),
+            Ordering::Less => Ok((self.upscale(a, b.decimals)?, b.clone())),
+            Ordering::Greater => Ok((a.clone(), self.upscale(b, a.decimals)?)),
+        }
+    }
+
+    fn upscale(&self, a: &EncryptedNumber, decimals: u32) -> Result<EncryptedNumber, EncodingError> {
+        if a.decimals == decimals {
+            return Ok(a.clone());
+        }
+        let mut scaled = self.mul_int(a, &pow10(decimals - a.decimals))?;
+        scaled.decimals = decimals;
+        Ok(scaled)
+    }
+
+    fn check(&self, m: &BigInt) -> Result<(), EncodingError> {
+        if m.abs() >= self.half {
+            return Err(EncodingError::Overflow);
+        }
+        Ok(())
+    }
+
+    // the value range is symmetric, so a bound is safe iff it is encodable
+    fn checked(&self, bound: BigInt) -> Result<BigInt, EncodingError> {
+        if bound >= self.half {
+            return Err(EncodingError::Overflow);
+        }
+        Ok(bound)
+    }
+}
+
+fn pow10(e: u32) -> BigInt {
+    num_traits::pow(BigInt::from(10), e as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::pailliar::Keypair;
+
+    #[test]
+    fn signed_roundtrip_and_range() {
+        let kp = Keypair::generate(512);
+        let enc = Encoder::new(&kp.pk);
+        let half = (&kp.pk.n + 1) >> 1usize;
+        for m in [BigInt::from(0), BigInt::from(-1), BigInt::from(12345), 1 - &half, &half - 1] {
+            let c = enc.encrypt(&m).unwrap();
+            assert_eq!(enc.decrypt_int(&kp.sk, &c).unwrap(), m);
+        }
+        assert!(matches!(enc.encode(&half), Err(EncodingError::Overflow)));
+        assert!(matches!(enc.encode(&-&half), Err(EncodingError::Overflow)));
+    }
+
+    #[test]
+    fn signed_arithmetic() {
+        let kp = Keypair::generate(512);
+        let enc = Encoder::new(&kp.pk);
+        let a = enc.encrypt(&BigInt::from(-700)).unwrap();
+        let b = enc.encrypt(&BigInt::from(250)).unwrap();
+        let dec = |c: &EncryptedNumber| enc.decrypt_int(&kp.sk, c).unwrap();
+        assert_eq!(dec(&enc.add(&a, &b).unwrap()), BigInt::from(-450));
+        assert_eq!(dec(&enc.sub(&b, &a).unwrap()), BigInt::from(950));
+        assert_eq!(dec(&enc.neg(&a).unwrap()), BigInt::from(700));
+        assert_eq!(dec(&enc.mul_int(&b, &BigInt::from(-3)).unwrap()), BigInt::from(-750));
+        assert_eq!(dec(&enc.add_plain(&a, &FixedPoint::new(-5, 0)).unwrap()), BigInt::from(-705));
+        assert_eq!(enc.add(&a, &b).unwrap().bound(), &BigInt::from(950));
+    }
+
+    #[test]
+    fn fixed_point_arithmetic() {
+        let kp = Keypair::generate(512);
+        let enc = Encoder::new(&kp.pk);
+        let a = enc.encrypt_fixed(&FixedPoint::parse("-12.5").unwrap()).unwrap();
+        let b = enc.encrypt_fixed(&FixedPoint::parse("0.125").unwrap()).unwrap();
+        let dec = |c: &EncryptedNumber| enc.decrypt(&kp.sk, c).unwrap().to_string();
+        assert_eq!(dec(&enc.add(&a, &b).unwrap()), "-12.375");
+        assert_eq!(dec(&enc.sub(&b, &a).unwrap()), "12.625");
+        assert_eq!(dec(&enc.mul_fixed(&a, &FixedPoint::parse("1.5").unwrap()).unwrap()), "-18.75");
+        assert_eq!(dec(&enc.add_plain(&b, &FixedPoint::parse("2.5").unwrap()).unwrap()), "2.625");
+        assert!(matches!(enc.decrypt_int(&kp.sk, &a), Err(EncodingError::NotAnInteger(1))));
+        assert_eq!(enc.decrypt(&kp.sk, &a).unwrap().to_f64(), -12.5);
+    }
+
+    #[test]
+    fn overflow_is_detected() {
+        let kp = Keypair::generate(512);
+        let enc = Encoder::new(&kp.pk);
+        let big = (&kp.pk.n >> 2usize) + 1; // just over n/4
+        let a = enc.encrypt(&big).unwrap();
+        // |2·big| >= n/2 cannot be represented
+        assert!(matches!(enc.add(&a, &a), Err(EncodingError::Overflow)));
+        assert!(matches!(enc.sub(&a, &enc.neg(&a).unwrap()), Err(EncodingError::Overflow)));
+        assert!(matches!(enc.mul_int(&a, &BigInt::from(3)), Err(EncodingError::Overflow)));
+        assert!(matches!(enc.upscale(&a, 2), Err(EncodingError::Overflow)));
+
+        // the raw Paillier API would have wrapped silently
+        let wrapped = kp.pk.mul_scalar(&a.ct, 3);
+        assert_ne!(enc.decode(&kp.sk.decrypt(&kp.pk, &wrapped).unwrap()), &big * 3);
+    }
+
+    #[test]
+    fn fixed_point_parsing() {
+        assert_eq!(FixedPoint::parse("3.14").unwrap(), FixedPoint::new(314, 2));
+        assert_eq!(FixedPoint::parse("-0.05").unwrap(), FixedPoint::new(-5, 2));
+        assert_eq!(FixedPoint::parse("42").unwrap(), FixedPoint::new(42, 0));
+        for bad in ["", "-", "1.", ".5", "1.2.3", "1e5", "+1"] {
+            assert!(FixedPoint::parse(bad).is_err(), "{bad}");
+        }
+        assert_eq!(FixedPoint::new(-5, 2).to_string(), "-0.05");
+        assert_eq!(FixedPoint::from_f64(2.675, 1).unwrap(), FixedPoint::new(27, 1));
+        assert!(matches!(FixedPoint::from_f64(f64::NAN, 2), Err(EncodingError::NotFinite)));
+        assert_eq!(FixedPoint::new(5, 1).rescale(3), FixedPoint::new(500, 3));
+    }
+}
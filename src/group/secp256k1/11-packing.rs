@@ -0,0 +1,232 @@
+// src/group/secp256k1/11-packing.rs
+// -----------------------------------------------------------------------------
+// Plaintext packing: several small unsigned values in one Paillier plaintext
+// -----------------------------------------------------------------------------
+// A plaintext m < n is split into k slots of w = value_bits + headroom_bits
+// bits each:
+//
+//     m = v_0 + v_1·2^w + v_2·2^(2w) + ... + v_(k-1)·2^((k-1)w)
+//
+// Adding ciphertexts adds slot-wise and multiplying by a public scalar
+// scales every slot, as long as no slot carries into its neighbour. Fresh
+// values use value_bits; the headroom absorbs growth from homomorphic
+// operations. Each `PackedCiphertext` tracks a public bound on its slots and
+// operations that could carry fail with `PackingError::Overflow`.
+//
+// Only non-negative values are supported: a negative slot would borrow from
+// the slot above it.
+// -----------------------------------------------------------------------------
+
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
+use thiserror::Error;
+use super::pailliar::{Ciphertext, PaillierError, PrivateKey, PublicKey};
+
+#[derive(Error, Debug)]
+pub enum PackingError {
+    #[error("layout holds {max} slots, got {got} values")]
+    TooManyValues { max: usize, got: usize },
+    #[error("value does not fit in {0} bits")]
+    ValueTooLarge(u32),
+    #[error("slot would overflow its {0}-bit width")]
+    Overflow(u32),
+    #[error("slot width must be positive and fit in the modulus")]
+    InvalidLayout,
+    #[error("ciphertexts were packed with different layouts")]
+    LayoutMismatch,
+    #[error(transparent)]
+    Paillier(#[from] PaillierError),
+}
+
+/// Slot geometry for one public key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackingLayout {
+    pub value_bits: u32,
+    pub headroom_bits: u32,
+    pub slots: usize,
+}
+
+impl PackingLayout {
+    /// As many slots as fit below n; value_bits + headroom_bits per slot
+    pub fn new(pk: &PublicKey, value_bits: u32, headroom_bits: u32) -> Result<Self, PackingError> {
+        let width = value_bits as u64 + headroom_bits as u64;
+        // m must stay below n, so only use n.bits() - 1 bits
+        let usable = pk.n.bits() - 1;
+        if value_bits == 0 || width > usable {
+            return Err(PackingError::InvalidLayout);
+        }
+        Ok(PackingLayout { value_bits, headroom_bits, slots: (usable / width) as usize })
+    }
+
+    pub fn slot_bits(&self) -> u32 {
+        self.value_bits + self.headroom_bits
+    }
+
+    /// Place values in consecutive slots (missing slots are zero)
+    pub fn pack(&self, values: &[BigInt]) -> Result<BigInt, PackingError> {
+        if values.len() > self.slots {
+            return Err(PackingError::TooManyValues { max: self.slots, got: values.len() });
+        }
+        let mut m = BigInt::zero();
+        for v in values.iter().rev() {
+            if v.sign() == Sign::Minus || v.bits() > self.value_bits as u64 {
+                return Err(PackingError::ValueTooLarge(self.value_bits));
+            }
+            m = (m << self.slot_bits()) + v;
+        }
+        Ok(m)
+    }
+
+    /// Split a plaintext into `count` slots
+    pub fn unpack(&self, m: &BigInt, count: usize) -> Vec<BigInt> {
+        let mask = (BigInt::one() << self.slot_bits()) - 1;
+        let mut m = m.clone();
+        (0..count.min(self.slots))
+            .map(|_| {
+                let v = &m & &mask;
+                m >>= self.slot_bits();
+                v
+            })
+            .collect()
+    }
+}
+
+/// Ciphertext of packed slots with a public bound on every slot value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedCiphertext {
+    pub ct: Ciphertext,
+    pub len: usize,
+    layout: PackingLayout,
+    bound: BigInt,
+}
+
+impl PackedCiphertext {
+    pub fn bound(&self) -> &BigInt {
+        &self.bound
+    }
+}
+
+/// Packed encryption under one key
+pub struct Packer<'a> {
+    pk: &'a PublicKey,
+    layout: PackingLayout,
+}
+
+impl<'a> Packer<'a> {
+    pub fn new(pk: &'a PublicKey, layout: PackingLayout) -> Self {
+        Packer { pk, layout }
+    }
+
+    pub fn layout(&self) -> &PackingLayout {
+        &self.layout
+    }
+
+    pub fn encrypt(&self, values: &[BigInt]) -> Result<PackedCiphertext, PackingError> {
+        let m = self.layout.pack(values)?;
+        let bound = values.iter().max().cloned().unwrap_or_default();
+        Ok(PackedCiphertext { ct: self.pk.encrypt(m)?, len: values.len(), layout: self.layout, bound })
+    }
+
+    /// Convenience for the common case of 64-bit inputs
+    pub fn encrypt_u64(&self, values: &[u64]) -> Result<PackedCiphertext, PackingError> {
+        let values: Vec<BigInt> = values.iter().map(|&v| BigInt::from(v)).collect();
+        self.encrypt(&values)
+    }
+
+    pub fn decrypt(&self, sk: &PrivateKey, c: &PackedCiphertext) -> Result<Vec<BigInt>, PackingError> {
+        self.check_layout(c)?;
+        let m = sk.decrypt(self.pk, &c.ct)?;
+        Ok(self.layout.unpack(&m, c.len))
+    }
+
+    /// Slot-wise a + b
+    pub fn add(&self, a: &PackedCiphertext, b: &PackedCiphertext) -> Result<PackedCiphertext, PackingError> {
+        self.check_layout(a)?;
+        self.check_layout(b)?;
+        let bound = self.checked(&a.bound + &b.bound)?;
+        Ok(PackedCiphertext { ct: self.pk.add(&a.ct, &b.ct), len: a.len.max(b.len), layout: self.layout, bound })
+    }
+
+    /// Slot-wise k·a for a public k >= 0
+    pub fn mul_scalar(&self, a: &PackedCiphertext, k: u64) -> Result<PackedCiphertext, PackingError> {
+        self.check_layout(a)?;
+        let bound = self.checked(&a.bound * k)?;
+        Ok(PackedCiphertext { ct: self.pk.mul_scalar(&a.ct, k), len: a.len, layout: self.layout, bound })
+    }
+
+    fn check_layout(&self, c: &PackedCiphertext) -> Result<(), PackingError> {
+        if c.layout != self.layout {
+            return Err(PackingError::LayoutMismatch);
+        }
+        Ok(())
+    }
+
+    fn checked(&self, bound: BigInt) -> Result<BigInt, PackingError> {
+        if bound.bits() > self.layout.slot_bits() as u64 {
+            return Err(PackingError::Overflow(self.layout.slot_bits()));
+        }
+        Ok(bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::pailliar::Keypair;
+
+    #[test]
+    fn pack_unpack_roundtrip() {
+        let kp = Keypair::generate(1024);
+        let layout = PackingLayout::new(&kp.pk, 64, 16).unwrap();
+        assert_eq!(layout.slots, (kp.pk.n.bits() as usize - 1) / 80);
+        let packer = Packer::new(&kp.pk, layout);
+        let values: Vec<u64> = (0..layout.slots as u64).map(|i| u64::MAX - i * 1_000_003).collect();
+        let c = packer.encrypt_u64(&values).unwrap();
+        let out = packer.decrypt(&kp.sk, &c).unwrap();
+        assert_eq!(out, values.iter().map(|&v| BigInt::from(v)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn slotwise_homomorphic_ops() {
+        let kp = Keypair::generate(1024);
+        let layout = PackingLayout::new(&kp.pk, 64, 16).unwrap();
+        let packer = Packer::new(&kp.pk, layout);
+        let a = [u64::MAX, 1, 2, 3];
+        let b = [u64::MAX, 10, 20];
+        let ca = packer.encrypt_u64(&a).unwrap();
+        let cb = packer.encrypt_u64(&b).unwrap();
+        let sum = packer.add(&ca, &cb).unwrap();
+        let scaled = packer.mul_scalar(&sum, 1000).unwrap();
+        let out = packer.decrypt(&kp.sk, &scaled).unwrap();
+        let expected: Vec<BigInt> = [
+            (BigInt::from(u64::MAX) * 2) * 1000,
+            BigInt::from(11_000),
+            BigInt::from(22_000),
+            BigInt::from(3_000),
+        ].into();
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn overflow_and_bad_inputs_are_rejected() {
+        let kp = Keypair::generate(1024);
+        let layout = PackingLayout::new(&kp.pk, 64, 4).unwrap();
+        let packer = Packer::new(&kp.pk, layout);
+        let c = packer.encrypt_u64(&[u64::MAX, 5]).unwrap();
+        // 16·(2^64 - 1) still fits in 68 bits, 17× does not
+        assert!(packer.mul_scalar(&c, 16).is_ok());
+        assert!(matches!(packer.mul_scalar(&c, 17), Err(PackingError::Overflow(68))));
+        let mut acc = c.clone();
+        for _ in 0..15 { acc = packer.add(&acc, &c).unwrap(); }
+        assert!(matches!(packer.add(&acc, &c), Err(PackingError::Overflow(68))));
+
+        let too_many = vec![1u64; layout.slots + 1];
+        assert!(matches!(packer.encrypt_u64(&too_many), Err(PackingError::TooManyValues { .. })));
+        assert!(matches!(layout.pack(&[BigInt::from(-1)]), Err(PackingError::ValueTooLarge(64))));
+        assert!(matches!(layout.pack(&[BigInt::one() << 64usize]), Err(PackingError::ValueTooLarge(64))));
+        assert!(matches!(PackingLayout::new(&kp.pk, 1000, 100), Err(PackingError::InvalidLayout)));
+
+        let other = Packer::new(&kp.pk, PackingLayout::new(&kp.pk, 32, 4).unwrap());
+        assert!(matches!(other.add(&c, &c), Err(PackingError::LayoutMismatch)));
+    }
+}
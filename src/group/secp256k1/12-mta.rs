@@ -0,0 +1,275 @@
+// src/group/secp256k1/12-mta.rs
+// -----------------------------------------------------------------------------
+// Multiplicative-to-additive share conversion (MtA / MtAwc, GG18 §3)
+// -----------------------------------------------------------------------------
+// Alice holds a, Bob holds b (both mod q, the secp256k1 group order). At the
+// end Alice holds α and Bob holds β with α + β = a·b (mod q).
+//
+//   1. Alice → Bob:  C = Enc_A(a)  with Π^enc (a is small), checked against
+//                    Bob's ring-Pedersen parameters
+//   2. Bob → Alice:  D = b·C + Enc_A(β')  for a random β' ∈ [0, 2^ℓ'),
+//                    keeps β = -β' mod q
+//   3. Alice:        α = Dec_A(D) mod q
+//
+// β' is much larger than a·b < q², so D reveals nothing about b beyond what
+// Alice learns anyway. For the result to be exact mod q, a·b + β' must not
+// wrap around N_A, which `check_modulus` enforces.
+//
+// MtAwc ("with check") additionally binds b to a public point B = b·G: Bob
+// attaches Y = Enc_B(β') under his own key and a Π^aff-g proof, which Alice
+// verifies against B and her ring-Pedersen parameters before decrypting.
+// -----------------------------------------------------------------------------
+
+use num_bigint::{BigInt, RandBigInt, Sign};
+use num_integer::Integer;
+use rand::rngs::OsRng;
+use thiserror::Error;
+use super::pailliar::{Ciphertext, Keypair, PaillierError, PublicKey};
+use super::paillier_proofs::{curve_order, encrypt_signed_fresh, AffGProof, AffGStatement, AffGWitness, EncProof, RangeParams};
+use super::params::Secp256k1;
+use super::point::{scalar_mul_base, AffinePoint};
+use super::ring_pedersen::RingPedersenParams;
+
+#[derive(Error, Debug)]
+pub enum MtaError {
+    #[error("Paillier modulus has {bits} bits, need more than {min}")]
+    ModulusTooSmall { bits: u64, min: u64 },
+    #[error("Alice's range proof did not verify")]
+    InvalidEncProof,
+    #[error("Bob's affine-operation proof did not verify")]
+    InvalidAffineProof,
+    #[error("MtAwc response is missing its check")]
+    MissingCheck,
+    #[error(transparent)]
+    Paillier(#[from] PaillierError),
+}
+
+/// Alice's first message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MtaRequest {
+    pub c: Ciphertext,
+    pub proof: EncProof,
+}
+
+/// Bob's reply; `check` is present in MtAwc
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MtaResponse {
+    pub d: Ciphertext,
+    pub check: Option<MtaCheck>,
+}
+
+/// Y = Enc_B(β') and the Π^aff-g proof tying D, Y and B = b·G together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MtaCheck {
+    pub y_ct: Ciphertext,
+    pub proof: AffGProof,
+}
+
+/// Alice's side of one conversion
+pub struct MtaAlice<'a> {
+    kp: &'a Keypair,
+    request: MtaRequest,
+}
+
+impl<'a> MtaAlice<'a> {
+    /// Encrypt a and prove it is in range for Bob (`bob_rp` are his parameters)
+    pub fn new(kp: &'a Keypair, a: &BigInt, bob_rp: &RingPedersenParams, params: &RangeParams) -> Result<Self, MtaError> {
+        check_modulus(&kp.pk, params)?;
+        let a = a.mod_floor(&curve_order());
+        let (c, rho) = encrypt_signed_fresh(&kp.pk, &a);
+        let proof = EncProof::prove(&kp.pk, &c, &a, &rho, bob_rp, params);
+        Ok(MtaAlice { kp, request: MtaRequest { c, proof } })
+    }
+
+    pub fn request(&self) -> &MtaRequest {
+        &self.request
+    }
+
+    /// Plain MtA: α = Dec(D) mod q
+    pub fn finish(&self, resp: &MtaResponse) -> Result<BigInt, MtaError> {
+        let m = self.kp.sk.decrypt(&self.kp.pk, &resp.d)?;
+        Ok(m.mod_floor(&curve_order()))
+    }
+
+    /// MtAwc: verify Bob's proof against B = b·G before decrypting
+    pub fn finish_checked(
+        &self,
+        resp: &MtaResponse,
+        bob_pk: &PublicKey,
+        b_point: &AffinePoint,
+        own_rp: &RingPedersenParams,
+        params: &RangeParams,
+    ) -> Result<BigInt, MtaError> {
+        let check = resp.check.as_ref().ok_or(MtaError::MissingCheck)?;
+        let st = AffGStatement {
+            pk0: &self.kp.pk,
+            pk1: bob_pk,
+            c: &self.request.c,
+            d: &resp.d,
+            y_ct: &check.y_ct,
+            x_point: b_point,
+        };
+        if !check.proof.verify(&st, own_rp, params) {
+            return Err(MtaError::InvalidAffineProof);
+        }
+        self.finish(resp)
+    }
+}
+
+/// Bob's side of plain MtA; returns his reply and β
+pub fn mta_respond(
+    alice_pk: &PublicKey,
+    req: &MtaRequest,
+    b: &BigInt,
+    own_rp: &RingPedersenParams,
+    params: &RangeParams,
+) -> Result<(MtaResponse, BigInt), MtaError> {
+    let (d, _, _, beta) = respond(alice_pk, req, b, own_rp, params)?;
+    Ok((MtaResponse { d, check: None }, beta))
+}
+
+/// Bob's side of MtAwc: also proves that D used the b behind B = b·G.
+/// `alice_rp` are Alice's ring-Pedersen parameters, `own_rp` Bob's.
+pub fn mta_respond_checked(
+    alice_pk: &PublicKey,
+    bob_pk: &PublicKey,
+    req: &MtaRequest,
+    b: &BigInt,
+    own_rp: &RingPedersenParams,
+    alice_rp: &RingPedersenParams,
+    params: &RangeParams,
+) -> Result<(MtaResponse, BigInt), MtaError> {
+    let (d, rho, beta_prime, beta) = respond(alice_pk, req, b, own_rp, params)?;
+    let b = b.mod_floor(&curve_order());
+    let (y_ct, rho_y) = encrypt_signed_fresh(bob_pk, &beta_prime);
+    let b_point = scalar_mul_base(&b.to_biguint().expect("reduced mod q"), &Secp256k1::new());
+    let st = AffGStatement { pk0: alice_pk, pk1: bob_pk, c: &req.c, d: &d, y_ct: &y_ct, x_point: &b_point };
+    let wit = AffGWitness { x: &b, y: &beta_prime, rho: &rho, rho_y: &rho_y };
+    let proof = AffGProof::prove(&st, &wit, alice_rp, params);
+    Ok((MtaResponse { d, check: Some(MtaCheck { y_ct, proof }) }, beta))
+}
+
+// D = b·C + Enc(β'; ρ); returns (D, ρ, β', β)
+fn respond(
+    alice_pk: &PublicKey,
+    req: &MtaRequest,
+    b: &BigInt,
+    own_rp: &RingPedersenParams,
+    params: &RangeParams,
+) -> Result<(Ciphertext, BigInt, BigInt, BigInt), MtaError> {
+    check_modulus(alice_pk, params)?;
+    if !req.proof.verify(alice_pk, &req.c, own_rp, params) {
+        return Err(MtaError::InvalidEncProof);
+    }
+    let q = curve_order();
+    let b = b.mod_floor(&q);
+    let beta_prime = BigInt::from_biguint(Sign::Plus, OsRng.gen_biguint(params.ell_prime));
+    let (enc_beta, rho) = encrypt_signed_fresh(alice_pk, &beta_prime);
+    let d = alice_pk.add(&alice_pk.mul_scalar(&req.c, b), &enc_beta);
+    let beta = (-&beta_prime).mod_floor(&q);
+    Ok((d, rho, beta_prime, beta))
+}
+
+// a·b + β' < 2^(2ℓ) + 2^ℓ' must stay below N
+fn check_modulus(pk: &PublicKey, params: &RangeParams) -> Result<(), MtaError> {
+    let min = (2 * params.ell).max(params.ell_prime) + 1;
+    let bits = pk.n.bits();
+    if bits <= min {
+        return Err(MtaError::ModulusTooSmall { bits, min });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::pailliar::RandomnessPool;
+    use super::super::ring_pedersen::test_params;
+
+    // ℓ' = 2ℓ + 128 keeps β' statistically hiding a·b and fits 1024-bit keys
+    const PARAMS: RangeParams = RangeParams { ell: 256, ell_prime: 640, eps: 512 };
+
+    fn random_scalar() -> BigInt {
+        OsRng.gen_bigint_range(&BigInt::from(0), &curve_order())
+    }
+
+    #[test]
+    fn mta_reconstructs_product() {
+        let alice = Keypair::generate(1024);
+        let (bob_rp, _) = test_params(1);
+        let q = curve_order();
+        for _ in 0..32 {
+            let (a, b) = (random_scalar(), random_scalar());
+            let session = MtaAlice::new(&alice, &a, &bob_rp, &PARAMS).unwrap();
+            let (resp, beta) = mta_respond(&alice.pk, session.request(), &b, &bob_rp, &PARAMS).unwrap();
+            assert!(resp.check.is_none());
+            let alpha = session.finish(&resp).unwrap();
+            assert_eq!((alpha + beta).mod_floor(&q), (&a * &b).mod_floor(&q));
+        }
+        // edge values and inputs outside [0, q)
+        for (a, b) in [(0.into(), 5.into()), (&q - 1, &q - 1), (-BigInt::from(3), &q + 7)] {
+            let session = MtaAlice::new(&alice, &a, &bob_rp, &PARAMS).unwrap();
+            let (resp, beta) = mta_respond(&alice.pk, session.request(), &b, &bob_rp, &PARAMS).unwrap();
+            let alpha = session.finish(&resp).unwrap();
+            assert_eq!((alpha + beta).mod_floor(&q), (&a * &b).mod_floor(&q));
+        }
+    }
+
+    #[test]
+    fn encryptions_draw_from_attached_pool() {
+        let mut alice = Keypair::generate(1024);
+        let pool = RandomnessPool::with_entries(&alice.pk, 4);
+        alice.pk.attach_pool(pool).unwrap();
+        let (bob_rp, _) = test_params(1);
+        let q = curve_order();
+        let (a, b) = (random_scalar(), random_scalar());
+        // Alice's Enc(a) and Bob's Enc(β') under her key each take one entry
+        let session = MtaAlice::new(&alice, &a, &bob_rp, &PARAMS).unwrap();
+        let (resp, beta) = mta_respond(&alice.pk, session.request(), &b, &bob_rp, &PARAMS).unwrap();
+        assert_eq!(alice.pk.pooled(), 2);
+        let alpha = session.finish(&resp).unwrap();
+        assert_eq!((alpha + beta).mod_floor(&q), (&a * &b).mod_floor(&q));
+    }
+
+    #[test]
+    fn mtawc_checks_public_point() {
+        let (alice, bob) = (Keypair::generate(1024), Keypair::generate(1024));
+        let (alice_rp, _) = test_params(0);
+        let (bob_rp, _) = test_params(1);
+        let curve = Secp256k1::new();
+        let q = curve_order();
+        for _ in 0..4 {
+            let (a, b) = (random_scalar(), random_scalar());
+            let b_point = scalar_mul_base(&b.to_biguint().unwrap(), &curve);
+            let session = MtaAlice::new(&alice, &a, &bob_rp, &PARAMS).unwrap();
+            let (resp, beta) = mta_respond_checked(&alice.pk, &bob.pk, session.request(), &b, &bob_rp, &alice_rp, &PARAMS).unwrap();
+            let alpha = session.finish_checked(&resp, &bob.pk, &b_point, &alice_rp, &PARAMS).unwrap();
+            assert_eq!((alpha + beta).mod_floor(&q), (&a * &b).mod_floor(&q));
+
+            // B for a different b, or a response stripped of its check
+            let wrong = scalar_mul_base(&(&b + 1u32).to_biguint().unwrap(), &curve);
+            assert!(matches!(session.finish_checked(&resp, &bob.pk, &wrong, &alice_rp, &PARAMS), Err(MtaError::InvalidAffineProof)));
+            let plain = MtaResponse { d: resp.d.clone(), check: None };
+            assert!(matches!(session.finish_checked(&plain, &bob.pk, &b_point, &alice_rp, &PARAMS), Err(MtaError::MissingCheck)));
+        }
+    }
+
+    #[test]
+    fn rejects_bad_requests_and_small_moduli() {
+        let alice = Keypair::generate(1024);
+        let (bob_rp, _) = test_params(1);
+        let session = MtaAlice::new(&alice, &random_scalar(), &bob_rp, &PARAMS).unwrap();
+
+        // request proven for someone else's parameters
+        let (other_rp, _) = test_params(2);
+        assert!(matches!(mta_respond(&alice.pk, session.request(), &BigInt::from(9), &other_rp, &PARAMS), Err(MtaError::InvalidEncProof)));
+        // ciphertext swapped under the proof
+        let mut req = session.request().clone();
+        req.c = alice.pk.encrypt(BigInt::from(1u64) << 300usize).unwrap();
+        assert!(matches!(mta_respond(&alice.pk, &req, &BigInt::from(9), &bob_rp, &PARAMS), Err(MtaError::InvalidEncProof)));
+
+        // default ℓ' = 1280 needs a 2048-bit key
+        let err = MtaAlice::new(&alice, &BigInt::from(1), &bob_rp, &RangeParams::default()).err().unwrap();
+        assert!(matches!(err, MtaError::ModulusTooSmall { min: 1281, .. }));
+    }
+}
@@ -0,0 +1,116 @@
+// src/group/secp256k1/13-ecdsa.rs
+// -----------------------------------------------------------------------------
+// Single-party ECDSA over secp256k1 with SHA-256 (SEC 1 §4.1)
+// -----------------------------------------------------------------------------
+// The reference the two-party protocols are checked against: a signature
+// produced jointly must verify here exactly like one from a single key.
+//
+//   sign:    k random, R = k·G, r = R.x mod n, s = k^{-1}(h + r·x) mod n
+//   verify:  u1 = h·s^{-1}, u2 = r·s^{-1}, accept iff (u1·G + u2·Q).x ≡ r
+//
+// h is the SHA-256 digest read as a big-endian integer (256 bits, so no
+// truncation is needed for this curve). `sign` outputs low-s signatures;
+// `verify` accepts either s, as SEC 1 does. The domain parameters are
+// validated once, in `Secp256k1::new`, not per signature.
+// -----------------------------------------------------------------------------
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use super::params::Secp256k1;
+use super::point::{point_add, scalar_mul, scalar_mul_base, AffinePoint};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+impl Signature {
+    /// Replace s by n - s when s > n/2
+    pub fn normalize_s(&self, curve: &Secp256k1) -> Self {
+        let s = if self.s > (&curve.n >> 1usize) { &curve.n - &self.s } else { self.s.clone() };
+        Signature { r: self.r.clone(), s }
+    }
+}
+
+/// SHA-256(msg) as an integer
+pub fn message_digest(msg: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(&Sha256::digest(msg))
+}
+
+/// Uniform scalar in [1, n)
+pub fn random_scalar(curve: &Secp256k1) -> BigUint {
+    OsRng.gen_biguint_range(&BigUint::one(), &curve.n)
+}
+
+/// x^{-1} mod n (n is prime)
+pub fn scalar_inv(x: &BigUint, curve: &Secp256k1) -> BigUint {
+    x.modpow(&(&curve.n - 2u32), &curve.n)
+}
+
+pub fn sign(x: &BigUint, msg: &[u8], curve: &Secp256k1) -> Signature {
+    let n = &curve.n;
+    let h = message_digest(msg) % n;
+    loop {
+        let k = random_scalar(curve);
+        let r = scalar_mul_base(&k, curve).x % n;
+        if r.is_zero() {
+            continue;
+        }
+        let s = scalar_inv(&k, curve) * ((&h + &r * x) % n) % n;
+        if !s.is_zero() {
+            return Signature { r, s }.normalize_s(curve);
+        }
+    }
+}
+
+pub fn verify(q: &AffinePoint, msg: &[u8], sig: &Signature, curve: &Secp256k1) -> bool {
+    let n = &curve.n;
+    if sig.r.is_zero() || &sig.r >= n || sig.s.is_zero() || &sig.s >= n {
+        return false;
+    }
+    if q.infinity || !q.is_on_curve(curve) {
+        return false;
+    }
+    let h = message_digest(msg) % n;
+    let w = scalar_inv(&sig.s, curve);
+    let u1 = h * &w % n;
+    let u2 = &sig.r * &w % n;
+    let point = point_add(&scalar_mul_base(&u1, curve), &scalar_mul(&u2, q, curve), curve);
+    !point.infinity && point.x % n == sig.r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_verify_roundtrip() {
+        let curve = Secp256k1::new();
+        let x = random_scalar(&curve);
+        let q = scalar_mul_base(&x, &curve);
+        let sig = sign(&x, b"hello", &curve);
+        assert!(sig.s <= &curve.n >> 1usize);
+        assert!(verify(&q, b"hello", &sig, &curve));
+
+        // high-s form verifies too
+        let high = Signature { r: sig.r.clone(), s: &curve.n - &sig.s };
+        assert!(verify(&q, b"hello", &high, &curve));
+        assert_eq!(high.normalize_s(&curve), sig);
+
+        // wrong message, key, or out-of-range components
+        assert!(!verify(&q, b"hellp", &sig, &curve));
+        assert!(!verify(&AffinePoint::generator(&curve), b"hello", &sig, &curve));
+        assert!(!verify(&q, b"hello", &Signature { r: sig.r.clone(), s: BigUint::zero() }, &curve));
+        assert!(!verify(&q, b"hello", &Signature { r: &sig.r + &curve.n, s: sig.s.clone() }, &curve));
+    }
+
+    #[test]
+    fn digest_is_sha256() {
+        // SHA-256("abc")
+        let expected = BigUint::parse_bytes(b"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad", 16).unwrap();
+        assert_eq!(message_digest(b"abc"), expected);
+    }
+}
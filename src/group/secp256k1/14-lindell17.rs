@@ -0,0 +1,495 @@
+// src/group/secp256k1/14-lindell17.rs
+// -----------------------------------------------------------------------------
+// Two-party ECDSA (Lindell, "Fast Secure Two-Party ECDSA Signing", CRYPTO'17)
+// -----------------------------------------------------------------------------
+// The signing key is x = x1·x2 mod q with Q = x·G. P1 keeps x1 and a Paillier
+// key, P2 keeps x2 and c_key = Enc_1(x1).
+//
+// Key generation
+//   1. P1 → P2:  commitment to (Q1 = x1·G, π1)          π = Schnorr proof of dlog
+//   2. P2 → P1:  Q2 = x2·G, π2, P2's ring-Pedersen parameters with Π^prm
+//   3. P1 → P2:  opening of (Q1, π1); N with Π^mod; c_key = Enc(x1) with Π^log*
+//                against P2's ring-Pedersen parameters
+//   Both set Q = x1·Q2 = x2·Q1.
+//
+// Signing a message m (h = SHA-256(m))
+//   1. P1 → P2:  commitment to (R1 = k1·G, π1)
+//   2. P2 → P1:  R2 = k2·G, π2
+//   3. P1 → P2:  opening of (R1, π1)
+//   4. P2 → P1:  R = k2·R1, r = R.x mod q, ρ ← Z_{q²},
+//                c3 = Enc(ρ·q + k2^{-1}·h mod q) ⊕ c_key^(k2^{-1}·r·x2 mod q)
+//   5. P1:       R = k1·R2, s = k1^{-1}·Dec(c3) mod q, normalised to low-s;
+//                P1 verifies (r, s) under Q before releasing it
+//
+// Dec(c3) = ρq + t1 + x1·t2 must not wrap mod N. Π^log* only bounds x1 by
+// 2^(ℓ+ε), so N needs more than max(3ℓ, 2ℓ+ε) + 2 bits; `check_paillier`
+// enforces that together with the configured minimum.
+//
+// The final signature is plain ECDSA and is checked with `ecdsa::verify`.
+// -----------------------------------------------------------------------------
+
+use num_bigint::{BigInt, BigUint, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::Zero;
+use rand::rngs::OsRng;
+use thiserror::Error;
+use crate::group::utils::transcript::Transcript;
+use super::ecdsa::{message_digest, random_scalar, scalar_inv, verify, Signature};
+use super::modulus_proofs::{ModProof, ModulusProofError};
+use super::pailliar::{Ciphertext, Keypair, PaillierError, PublicKey};
+use super::paillier_proofs::{encrypt_signed_fresh, LogStarProof, RangeParams};
+use super::params::Secp256k1;
+use super::point::{point_add, scalar_mul, scalar_mul_base, AffinePoint};
+use super::ring_pedersen::{PrmProof, RingPedersenError, RingPedersenParams, RingPedersenSecret};
+
+#[derive(Error, Debug)]
+pub enum Lindell17Error {
+    #[error("commitment opening does not match")]
+    InvalidCommitment,
+    #[error("discrete-log proof did not verify")]
+    InvalidDlogProof,
+    #[error("Paillier modulus has {bits} bits, need at least {min}")]
+    ModulusTooSmall { bits: u64, min: u64 },
+    #[error("Paillier modulus proof did not verify")]
+    InvalidModulusProof,
+    #[error("c_key proof did not verify")]
+    InvalidKeyProof,
+    #[error("r = 0, retry with fresh nonces")]
+    ZeroR,
+    #[error("signature does not verify under the joint key")]
+    InvalidSignature,
+    #[error(transparent)]
+    RingPedersen(#[from] RingPedersenError),
+    #[error(transparent)]
+    ModulusProof(#[from] ModulusProofError),
+    #[error(transparent)]
+    Paillier(#[from] PaillierError),
+}
+
+/// Proof bounds and the minimum modulus sizes each party accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lindell17Params {
+    pub range: RangeParams,
+    pub min_paillier_bits: u64,
+    pub min_ring_pedersen_bits: u64,
+}
+
+impl Default for Lindell17Params {
+    fn default() -> Self {
+        Lindell17Params { range: RangeParams::default(), min_paillier_bits: 2048, min_ring_pedersen_bits: 2048 }
+    }
+}
+
+// -----------------------------
+// Schnorr proof and commitments
+// -----------------------------
+
+/// Non-interactive proof of knowledge of x with X = x·G
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DlogProof {
+    pub t: AffinePoint,
+    pub z: BigUint,
+}
+
+impl DlogProof {
+    pub fn prove(x: &BigUint, x_point: &AffinePoint, context: &[u8], curve: &Secp256k1) -> Self {
+        let a = random_scalar(curve);
+        let t = scalar_mul_base(&a, curve);
+        let e = dlog_challenge(x_point, &t, context, curve);
+        DlogProof { z: (a + e * x) % &curve.n, t }
+    }
+
+    /// z·G = T + e·X
+    pub fn verify(&self, x_point: &AffinePoint, context: &[u8], curve: &Secp256k1) -> bool {
+        if x_point.infinity || !x_point.is_on_curve(curve) || !self.t.is_on_curve(curve) || self.z >= curve.n {
+            return false;
+        }
+        let e = dlog_challenge(x_point, &self.t, context, curve);
+        scalar_mul_base(&self.z, curve) == point_add(&self.t, &scalar_mul(&e, x_point, curve), curve)
+    }
+}
+
+fn dlog_challenge(x_point: &AffinePoint, t: &AffinePoint, context: &[u8], curve: &Secp256k1) -> BigUint {
+    let mut tr = Transcript::new(b"lindell17/dlog");
+    tr.append_bytes(b"ctx", context);
+    tr.append_bytes(b"X", &x_point.to_compressed());
+    tr.append_bytes(b"T", &t.to_compressed());
+    reduce_mod_q(tr.challenge_mod(b"e", &BigInt::from_biguint(Sign::Plus, curve.n.clone())))
+}
+
+/// Opening of a hash commitment to a point and its proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decommitment {
+    pub point: AffinePoint,
+    pub proof: DlogProof,
+    pub salt: BigInt,
+}
+
+impl Decommitment {
+    fn new(point: AffinePoint, proof: DlogProof) -> Self {
+        Decommitment { point, proof, salt: BigInt::from_biguint(Sign::Plus, OsRng.gen_biguint(256)) }
+    }
+
+    pub fn commitment(&self) -> BigInt {
+        let mut tr = Transcript::new(b"lindell17/commit");
+        tr.append_bytes(b"P", &self.point.to_compressed());
+        tr.append_bytes(b"T", &self.proof.t.to_compressed());
+        tr.append_int(b"z", &BigInt::from_biguint(Sign::Plus, self.proof.z.clone()));
+        tr.append_int(b"salt", &self.salt);
+        tr.challenge_bits(b"com", 256)
+    }
+
+    // the opening matches `commitment` and carries a valid proof for `context`
+    fn check(&self, commitment: &BigInt, context: &[u8], curve: &Secp256k1) -> Result<(), Lindell17Error> {
+        if &self.commitment() != commitment {
+            return Err(Lindell17Error::InvalidCommitment);
+        }
+        if !self.proof.verify(&self.point, context, curve) {
+            return Err(Lindell17Error::InvalidDlogProof);
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------
+// Key generation
+// -----------------------------
+
+pub struct KeyGenMsg1 {
+    pub commitment: BigInt,
+}
+
+pub struct KeyGenMsg2 {
+    pub q2: AffinePoint,
+    pub proof: DlogProof,
+    pub rp: RingPedersenParams,
+    pub prm: PrmProof,
+}
+
+pub struct KeyGenMsg3 {
+    pub opening: Decommitment,
+    pub pk: PublicKey,
+    pub mod_proof: ModProof,
+    pub c_key: Ciphertext,
+    pub key_proof: LogStarProof,
+}
+
+pub struct Party1KeyGen {
+    x1: BigUint,
+    opening: Decommitment,
+    params: Lindell17Params,
+}
+
+pub struct Party2KeyGen {
+    x2: BigUint,
+    rp: RingPedersenParams,
+    commitment: BigInt,
+    params: Lindell17Params,
+}
+
+/// P1's long-term share
+pub struct Party1Share {
+    x1: BigUint,
+    pub public_key: AffinePoint,
+    pub paillier: Keypair,
+}
+
+/// P2's long-term share
+pub struct Party2Share {
+    x2: BigUint,
+    pub public_key: AffinePoint,
+    pub pk: PublicKey,
+    pub c_key: Ciphertext,
+}
+
+impl Party1KeyGen {
+    pub fn start(params: Lindell17Params) -> (Self, KeyGenMsg1) {
+        let curve = Secp256k1::new();
+        let x1 = random_scalar(&curve);
+        let q1 = scalar_mul_base(&x1, &curve);
+        let proof = DlogProof::prove(&x1, &q1, b"keygen/p1", &curve);
+        let opening = Decommitment::new(q1, proof);
+        let msg = KeyGenMsg1 { commitment: opening.commitment() };
+        (Party1KeyGen { x1, opening, params }, msg)
+    }
+
+    /// Check P2's share and parameters, then encrypt x1 under `paillier`
+    /// (a Paillier-Blum key, see `Keypair::generate_blum`)
+    pub fn finish(self, msg: &KeyGenMsg2, paillier: Keypair) -> Result<(Party1Share, KeyGenMsg3), Lindell17Error> {
+        let curve = Secp256k1::new();
+        if !msg.proof.verify(&msg.q2, b"keygen/p2", &curve) {
+            return Err(Lindell17Error::InvalidDlogProof);
+        }
+        msg.rp.verify_peer(&msg.prm, self.params.min_ring_pedersen_bits)?;
+        check_paillier(&paillier.pk, &self.params)?;
+
+        let mod_proof = ModProof::prove(&paillier.pk, &paillier.sk)?;
+        let x1 = BigInt::from_biguint(Sign::Plus, self.x1.clone());
+        let (c_key, rho) = encrypt_signed_fresh(&paillier.pk, &x1);
+        let key_proof = LogStarProof::prove(&paillier.pk, &c_key, &self.opening.point, &x1, &rho, &msg.rp, &self.params.range);
+
+        let public_key = scalar_mul(&self.x1, &msg.q2, &curve);
+        let out = KeyGenMsg3 { opening: self.opening, pk: paillier.pk.clone(), mod_proof, c_key, key_proof };
+        Ok((Party1Share { x1: self.x1, public_key, paillier }, out))
+    }
+}
+
+impl Party2KeyGen {
+    /// `rp`/`secret` are P2's ring-Pedersen parameters (slow to generate, so
+    /// they are created once and reused across sessions)
+    pub fn start(
+        msg: &KeyGenMsg1,
+        rp: &RingPedersenParams,
+        secret: &RingPedersenSecret,
+        params: Lindell17Params,
+    ) -> (Self, KeyGenMsg2) {
+        let curve = Secp256k1::new();
+        let x2 = random_scalar(&curve);
+        let q2 = scalar_mul_base(&x2, &curve);
+        let proof = DlogProof::prove(&x2, &q2, b"keygen/p2", &curve);
+        let out = KeyGenMsg2 { q2, proof, rp: rp.clone(), prm: rp.prove(secret) };
+        (Party2KeyGen { x2, rp: rp.clone(), commitment: msg.commitment.clone(), params }, out)
+    }
+
+    pub fn finish(self, msg: &KeyGenMsg3) -> Result<Party2Share, Lindell17Error> {
+        let curve = Secp256k1::new();
+        msg.opening.check(&self.commitment, b"keygen/p1", &curve)?;
+        check_paillier(&msg.pk, &self.params)?;
+        if !msg.mod_proof.verify(&msg.pk) {
+            return Err(Lindell17Error::InvalidModulusProof);
+        }
+        if !msg.key_proof.verify(&msg.pk, &msg.c_key, &msg.opening.point, &self.rp, &self.params.range) {
+            return Err(Lindell17Error::InvalidKeyProof);
+        }
+        let public_key = scalar_mul(&self.x2, &msg.opening.point, &curve);
+        Ok(Party2Share { x2: self.x2, public_key, pk: msg.pk.clone(), c_key: msg.c_key.clone() })
+    }
+}
+
+// N must hold ρq + t1 + x1·t2 with ρ < q², t1, t2 < q and |x1| <= 2^(ℓ+ε)
+fn check_paillier(pk: &PublicKey, params: &Lindell17Params) -> Result<(), Lindell17Error> {
+    let r = &params.range;
+    let min = params.min_paillier_bits.max((3 * r.ell).max(2 * r.ell + r.eps) + 3);
+    if pk.n.bits() < min {
+        return Err(Lindell17Error::ModulusTooSmall { bits: pk.n.bits(), min });
+    }
+    Ok(())
+}
+
+// -----------------------------
+// Signing
+// -----------------------------
+
+pub struct SignMsg1 {
+    pub commitment: BigInt,
+}
+
+pub struct SignMsg2 {
+    pub r2: AffinePoint,
+    pub proof: DlogProof,
+}
+
+pub struct SignMsg3 {
+    pub opening: Decommitment,
+}
+
+pub struct SignMsg4 {
+    pub c3: Ciphertext,
+}
+
+pub struct Party1Sign<'a> {
+    share: &'a Party1Share,
+    k1: BigUint,
+    opening: Decommitment,
+    msg: Vec<u8>,
+}
+
+pub struct Party2Sign<'a> {
+    share: &'a Party2Share,
+    k2: BigUint,
+    commitment: BigInt,
+    msg: Vec<u8>,
+}
+
+impl<'a> Party1Sign<'a> {
+    pub fn start(share: &'a Party1Share, msg: &[u8]) -> (Self, SignMsg1) {
+        let curve = Secp256k1::new();
+        let k1 = random_scalar(&curve);
+        let r1 = scalar_mul_base(&k1, &curve);
+        let proof = DlogProof::prove(&k1, &r1, b"sign/p1", &curve);
+        let opening = Decommitment::new(r1, proof);
+        let out = SignMsg1 { commitment: opening.commitment() };
+        (Party1Sign { share, k1, opening, msg: msg.to_vec() }, out)
+    }
+
+    pub fn reveal(&self, msg: &SignMsg2) -> Result<SignMsg3, Lindell17Error> {
+        let curve = Secp256k1::new();
+        if !msg.proof.verify(&msg.r2, b"sign/p2", &curve) {
+            return Err(Lindell17Error::InvalidDlogProof);
+        }
+        Ok(SignMsg3 { opening: self.opening.clone() })
+    }
+
+    /// Decrypt P2's partial s, finish and verify the signature
+    pub fn finish(&self, msg2: &SignMsg2, msg4: &SignMsg4) -> Result<Signature, Lindell17Error> {
+        let curve = Secp256k1::new();
+        let n = &curve.n;
+        let r = scalar_mul(&self.k1, &msg2.r2, &curve).x % n;
+        if r.is_zero() {
+            return Err(Lindell17Error::ZeroR);
+        }
+        let kp = &self.share.paillier;
+        let s_prime = reduce_mod_q(kp.sk.decrypt(&kp.pk, &msg4.c3)?);
+        let s = scalar_inv(&self.k1, &curve) * s_prime % n;
+        let sig = Signature { r, s }.normalize_s(&curve);
+        if !verify(&self.share.public_key, &self.msg, &sig, &curve) {
+            return Err(Lindell17Error::InvalidSignature);
+        }
+        Ok(sig)
+    }
+}
+
+impl<'a> Party2Sign<'a> {
+    pub fn start(share: &'a Party2Share, msg: &[u8], msg1: &SignMsg1) -> (Self, SignMsg2) {
+        let curve = Secp256k1::new();
+        let k2 = random_scalar(&curve);
+        let r2 = scalar_mul_base(&k2, &curve);
+        let proof = DlogProof::prove(&k2, &r2, b"sign/p2", &curve);
+        let session = Party2Sign { share, k2, commitment: msg1.commitment.clone(), msg: msg.to_vec() };
+        (session, SignMsg2 { r2, proof })
+    }
+
+    /// c3 = Enc(ρq + k2^{-1}h) ⊕ c_key^(k2^{-1} r x2)
+    pub fn partial_sign(&self, msg: &SignMsg3) -> Result<SignMsg4, Lindell17Error> {
+        let curve = Secp256k1::new();
+        let n = &curve.n;
+        msg.opening.check(&self.commitment, b"sign/p1", &curve)?;
+        let r = scalar_mul(&self.k2, &msg.opening.point, &curve).x % n;
+        if r.is_zero() {
+            return Err(Lindell17Error::ZeroR);
+        }
+        let pk = &self.share.pk;
+        let k2_inv = scalar_inv(&self.k2, &curve);
+        let h = message_digest(&self.msg) % n;
+        let t1 = &k2_inv * h % n;
+        let t2 = k2_inv * r * &self.share.x2 % n;
+        let rho = OsRng.gen_biguint_below(&(n * n));
+        let c1 = pk.encrypt(BigInt::from_biguint(Sign::Plus, rho * n + t1))?;
+        let c2 = pk.mul_scalar(&self.share.c_key, BigInt::from_biguint(Sign::Plus, t2));
+        Ok(SignMsg4 { c3: pk.add(&c1, &c2) })
+    }
+}
+
+fn reduce_mod_q(x: BigInt) -> BigUint {
+    x.mod_floor(&BigInt::from_biguint(Sign::Plus, Secp256k1::new().n)).to_biguint().expect("reduced")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::pailliar::RandomnessPool;
+    use super::super::ring_pedersen::test_params;
+
+    fn test_config() -> Lindell17Params {
+        // the fixture ring-Pedersen moduli are 1024 bits
+        Lindell17Params { min_ring_pedersen_bits: 1024, ..Default::default() }
+    }
+
+    fn keygen() -> (Party1Share, Party2Share) {
+        let (rp, secret) = test_params(1);
+        let (p1, m1) = Party1KeyGen::start(test_config());
+        let (p2, m2) = Party2KeyGen::start(&m1, &rp, &secret, test_config());
+        let (share1, m3) = p1.finish(&m2, Keypair::generate_blum(2048)).unwrap();
+        let share2 = p2.finish(&m3).unwrap();
+        (share1, share2)
+    }
+
+    fn sign(share1: &Party1Share, share2: &Party2Share, msg: &[u8]) -> Result<Signature, Lindell17Error> {
+        let (p1, m1) = Party1Sign::start(share1, msg);
+        let (p2, m2) = Party2Sign::start(share2, msg, &m1);
+        let m3 = p1.reveal(&m2)?;
+        let m4 = p2.partial_sign(&m3)?;
+        p1.finish(&m2, &m4)
+    }
+
+    #[test]
+    fn keygen_and_sign_verify_as_plain_ecdsa() {
+        let curve = Secp256k1::new();
+        let (share1, mut share2) = keygen();
+        assert_eq!(share1.public_key, share2.public_key);
+        // P2 presigns: each partial_sign encryption takes one r^n
+        let pool = RandomnessPool::with_entries(&share2.pk, 3);
+        share2.pk.attach_pool(pool).unwrap();
+        let x = &share1.x1 * &share2.x2 % &curve.n;
+        assert_eq!(share1.public_key, scalar_mul_base(&x, &curve));
+
+        for i in 0..4u8 {
+            let msg = [b"lindell17 message ".as_slice(), &[i]].concat();
+            let sig = sign(&share1, &share2, &msg).unwrap();
+            assert!(verify(&share1.public_key, &msg, &sig, &curve));
+            assert!(!verify(&share1.public_key, b"other message", &sig, &curve));
+            assert!(sig.s <= &curve.n >> 1usize);
+            assert_eq!(share2.pk.pooled(), 2usize.saturating_sub(i as usize));
+        }
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let curve = Secp256k1::new();
+        let (rp, secret) = test_params(1);
+        let config = test_config();
+
+        // opening that does not match the commitment, and a c_key for the wrong x1
+        let (p1, m1) = Party1KeyGen::start(config);
+        let (p2, m2) = Party2KeyGen::start(&m1, &rp, &secret, config);
+        let (share1, mut m3) = p1.finish(&m2, Keypair::generate_blum(2048)).unwrap();
+        let good = m3.c_key.clone();
+        m3.c_key = m3.pk.add(&good, &m3.pk.encrypt(1u32).unwrap());
+        let (p2_again, _) = Party2KeyGen::start(&m1, &rp, &secret, config);
+        assert!(matches!(p2_again.finish(&m3), Err(Lindell17Error::InvalidKeyProof)));
+        m3.c_key = good;
+        m3.opening.salt += 1;
+        assert!(matches!(p2.finish(&m3), Err(Lindell17Error::InvalidCommitment)));
+
+        // P2's ring-Pedersen proof for other parameters, and a Paillier key below the minimum
+        let (p1, m1) = Party1KeyGen::start(config);
+        let (_, mut m2) = Party2KeyGen::start(&m1, &rp, &secret, config);
+        let (other_rp, other_secret) = test_params(2);
+        m2.prm = other_rp.prove(&other_secret);
+        assert!(matches!(p1.finish(&m2, Keypair::generate_blum(1024)), Err(Lindell17Error::RingPedersen(_))));
+        let (p1, m1) = Party1KeyGen::start(config);
+        let (_, m2) = Party2KeyGen::start(&m1, &rp, &secret, config);
+        assert!(matches!(p1.finish(&m2, Keypair::generate_blum(1024)), Err(Lindell17Error::ModulusTooSmall { min: 2048, .. })));
+
+        // signing: bad R2 proof, bad R1 opening, and a c3 that does not match
+        let share2 = Party2Share {
+            x2: random_scalar(&curve),
+            public_key: share1.public_key.clone(),
+            pk: share1.paillier.pk.clone(),
+            c_key: share1.paillier.pk.encrypt(BigInt::from_biguint(Sign::Plus, share1.x1.clone())).unwrap(),
+        };
+        let (s1, m1) = Party1Sign::start(&share1, b"msg");
+        let (s2, mut m2) = Party2Sign::start(&share2, b"msg", &m1);
+        let good_r2 = m2.r2.clone();
+        m2.r2 = AffinePoint::generator(&curve);
+        assert!(matches!(s1.reveal(&m2), Err(Lindell17Error::InvalidDlogProof)));
+        m2.r2 = good_r2;
+        let mut m3 = s1.reveal(&m2).unwrap();
+        m3.opening.point = AffinePoint::generator(&curve);
+        assert!(matches!(s2.partial_sign(&m3), Err(Lindell17Error::InvalidCommitment)));
+        // x2 does not match the joint key, so P1 refuses to release the signature
+        let m4 = s2.partial_sign(&s1.reveal(&m2).unwrap()).unwrap();
+        assert!(matches!(s1.finish(&m2, &m4), Err(Lindell17Error::InvalidSignature)));
+    }
+
+    #[test]
+    fn dlog_proof_is_bound_to_context() {
+        let curve = Secp256k1::new();
+        let x = random_scalar(&curve);
+        let xp = scalar_mul_base(&x, &curve);
+        let proof = DlogProof::prove(&x, &xp, b"a", &curve);
+        assert!(proof.verify(&xp, b"a", &curve));
+        assert!(!proof.verify(&xp, b"b", &curve));
+        assert!(!proof.verify(&AffinePoint::generator(&curve), b"a", &curve));
+    }
+}
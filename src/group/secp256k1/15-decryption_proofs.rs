@@ -0,0 +1,247 @@
+// src/group/secp256k1/15-decryption_proofs.rs
+// -----------------------------------------------------------------------------
+// Verifiable Paillier decryption
+// -----------------------------------------------------------------------------
+// c decrypts to m exactly when u = c·g^(-m) mod N² is an N-th residue, i.e.
+// u = r^N for some r. The key holder recovers r = u^(N^{-1} mod φ(N)) mod N
+// and proves knowledge of it with a Guillou–Quisquater style Σ-protocol:
+//
+//     a = s^N mod N²               (s random unit mod N)
+//     e = H(N, c, m, a)            e ∈ [0, 2^DEC_CHALLENGE_BITS)
+//     z = s·r^e mod N
+//     check: z^N = a·u^e mod N²
+//
+// Special soundness needs e below the smallest prime factor of N, which holds
+// for any real Paillier key.
+//
+// `batch_verify` checks many proofs under one key with the small-exponent
+// test: for random weights w_i it compares (Π z_i^w_i)^N with
+// Π a_i^w_i · u_i^(e_i·w_i), so the N-th power (the expensive part) is taken
+// once instead of per proof. The guarantee is weaker than `verify` on each
+// proof: Z_{N²}^* has elements of small order, and a proof that is off by a
+// factor t of order d (z_i^N = t·a_i·u_i^e_i) passes whenever d | w_i, with
+// probability 1/d. A prover who knows r can publish N² − a and hash that into
+// e, giving t = −1: `verify` rejects, the batch accepts half the time, and
+// two such proofs can cancel. So a passing batch shows every proof holds up
+// to small-order factors (any larger deviation survives with probability
+// about 2^-BATCH_WEIGHT_BITS); call `verify` per proof where each proof must
+// be exactly valid.
+// -----------------------------------------------------------------------------
+
+use num_bigint::{BigInt, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::One;
+use rand::rngs::OsRng;
+use thiserror::Error;
+use crate::group::utils::codec::{CodecError, Reader, Writer};
+use crate::group::utils::montgomery::modpow_bigint;
+use crate::group::utils::transcript::Transcript;
+use super::pailliar::{mod_positive, modinv, Ciphertext, PaillierError, PrivateKey, PublicKey};
+
+/// Challenge length; soundness error 2^-DEC_CHALLENGE_BITS
+pub const DEC_CHALLENGE_BITS: u64 = 128;
+
+/// Random weight length in `batch_verify`
+pub const BATCH_WEIGHT_BITS: u64 = 64;
+
+#[derive(Error, Debug)]
+pub enum DecryptionProofError {
+    #[error("ciphertext is not a unit mod n^2")]
+    InvalidCiphertext,
+    #[error(transparent)]
+    Paillier(#[from] PaillierError),
+}
+
+/// Proof that a ciphertext decrypts to a stated plaintext
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecryptionProof {
+    pub a: BigInt,
+    pub z: BigInt,
+}
+
+impl DecryptionProof {
+    /// Decrypt c and prove the result
+    pub fn prove(pk: &PublicKey, sk: &PrivateKey, c: &Ciphertext) -> Result<(BigInt, Self), DecryptionProofError> {
+        if !is_unit(&c.0, &pk.n2) {
+            return Err(DecryptionProofError::InvalidCiphertext);
+        }
+        let m = sk.decrypt(pk, c)?;
+        let u = residue(pk, c, &m).ok_or(DecryptionProofError::InvalidCiphertext)?;
+
+        let (p, q) = sk.factors();
+        let phi = (p - 1) * (q - 1);
+        let n_inv = modinv(&pk.n, &phi).ok_or(PaillierError::NoInverse)?;
+        let r = modpow_bigint(&u, &n_inv, &pk.n);
+
+        let s = loop {
+            let s = OsRng.gen_bigint_range(&BigInt::one(), &pk.n);
+            if s.gcd(&pk.n).is_one() { break s; }
+        };
+        let a = modpow_bigint(&s, &pk.n, &pk.n2);
+        let e = challenge(pk, c, &m, &a);
+        let z = mod_positive(s * modpow_bigint(&r, &e, &pk.n), &pk.n);
+        Ok((m, DecryptionProof { a, z }))
+    }
+
+    pub fn verify(&self, pk: &PublicKey, c: &Ciphertext, m: &BigInt) -> bool {
+        let Some(u) = self.statement(pk, c, m) else { return false; };
+        let e = challenge(pk, c, m, &self.a);
+        let lhs = modpow_bigint(&self.z, &pk.n, &pk.n2);
+        lhs == mod_positive(&self.a * modpow_bigint(&u, &e, &pk.n2), &pk.n2)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+        w.put_int(&self.a);
+        w.put_int(&self.z);
+        w.finish()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        let mut r = Reader::new(bytes);
+        let proof = DecryptionProof { a: r.get_int()?, z: r.get_int()? };
+        r.finish()?;
+        Ok(proof)
+    }
+
+    // range checks shared by `verify` and `batch_verify`; returns u = c·g^(-m)
+    fn statement(&self, pk: &PublicKey, c: &Ciphertext, m: &BigInt) -> Option<BigInt> {
+        if m.sign() == Sign::Minus || m >= &pk.n || !is_unit(&self.a, &pk.n2) || !is_unit(&self.z, &pk.n) {
+            return None;
+        }
+        if !is_unit(&c.0, &pk.n2) {
+            return None;
+        }
+        residue(pk, c, m)
+    }
+}
+
+/// Check many (ciphertext, plaintext, proof) triples under one key at once,
+/// up to small-order factors (see the module docs)
+pub fn batch_verify(pk: &PublicKey, items: &[(&Ciphertext, &BigInt, &DecryptionProof)]) -> bool {
+    let mut z_acc = BigInt::one();
+    let mut rhs = BigInt::one();
+    for (c, m, proof) in items {
+        let Some(u) = proof.statement(pk, c, m) else { return false; };
+        let e = challenge(pk, c, m, &proof.a);
+        let w = BigInt::from_biguint(Sign::Plus, OsRng.gen_biguint(BATCH_WEIGHT_BITS));
+        z_acc = mod_positive(z_acc * modpow_bigint(&proof.z, &w, &pk.n2), &pk.n2);
+        let term = modpow_bigint(&proof.a, &w, &pk.n2) * modpow_bigint(&u, &(e * &w), &pk.n2);
+        rhs = mod_positive(rhs * term, &pk.n2);
+    }
+    modpow_bigint(&z_acc, &pk.n, &pk.n2) == rhs
+}
+
+// c·g^(-m) mod N²
+fn residue(pk: &PublicKey, c: &Ciphertext, m: &BigInt) -> Option<BigInt> {
+    let gm = modpow_bigint(&pk.g, m, &pk.n2);
+    Some(mod_positive(&c.0 * modinv(&gm, &pk.n2)?, &pk.n2))
+}
+
+fn challenge(pk: &PublicKey, c: &Ciphertext, m: &BigInt, a: &BigInt) -> BigInt {
+    let mut tr = Transcript::new(b"paillier/decryption");
+    tr.append_int(b"N", &pk.n);
+    tr.append_int(b"g", &pk.g);
+    tr.append_int(b"c", &c.0);
+    tr.append_int(b"m", m);
+    tr.append_int(b"a", a);
+    tr.challenge_bits(b"e", DEC_CHALLENGE_BITS)
+}
+
+fn is_unit(x: &BigInt, m: &BigInt) -> bool {
+    x.sign() == Sign::Plus && x < m && x.gcd(m).is_one()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::pailliar::Keypair;
+
+    #[test]
+    fn proves_correct_decryption() {
+        let kp = Keypair::generate(1024);
+        let m = BigInt::from(123_456_789u64) << 300usize;
+        let c = kp.pk.encrypt(m.clone()).unwrap();
+        let (dec, proof) = DecryptionProof::prove(&kp.pk, &kp.sk, &c).unwrap();
+        assert_eq!(dec, m);
+        assert!(proof.verify(&kp.pk, &c, &m));
+        assert_eq!(DecryptionProof::from_bytes(&proof.to_bytes()).unwrap(), proof);
+
+        // wrong plaintext, ciphertext or key
+        assert!(!proof.verify(&kp.pk, &c, &(&m + 1)));
+        assert!(!proof.verify(&kp.pk, &kp.pk.rerandomize(&c).unwrap(), &m));
+        assert!(!proof.verify(&Keypair::generate(1024).pk, &c, &m));
+        assert!(!proof.verify(&kp.pk, &c, &(&m + &kp.pk.n)));
+        let mut bad = proof.clone();
+        bad.z = mod_positive(&bad.z * 2, &kp.pk.n);
+        assert!(!bad.verify(&kp.pk, &c, &m));
+
+        // homomorphically derived ciphertexts prove the same way
+        let sum = kp.pk.add(&c, &kp.pk.encrypt(5u32).unwrap());
+        let (dec, proof) = DecryptionProof::prove(&kp.pk, &kp.sk, &sum).unwrap();
+        assert_eq!(dec, &m + 5);
+        assert!(proof.verify(&kp.pk, &sum, &dec));
+
+        assert!(matches!(
+            DecryptionProof::prove(&kp.pk, &kp.sk, &Ciphertext(kp.pk.n.clone())),
+            Err(DecryptionProofError::InvalidCiphertext)
+        ));
+    }
+
+    #[test]
+    fn batch_verification() {
+        let kp = Keypair::generate(1024);
+        let cts: Vec<Ciphertext> = (0..16u32).map(|i| kp.pk.encrypt(i * 1000).unwrap()).collect();
+        let proved: Vec<(BigInt, DecryptionProof)> =
+            cts.iter().map(|c| DecryptionProof::prove(&kp.pk, &kp.sk, c).unwrap()).collect();
+        let items: Vec<_> = cts.iter().zip(&proved).map(|(c, (m, p))| (c, m, p)).collect();
+        assert!(batch_verify(&kp.pk, &items));
+        assert!(batch_verify(&kp.pk, &[]));
+
+        // one wrong plaintext or one tampered proof spoils the batch
+        let wrong = BigInt::from(1);
+        let mut bad = items.clone();
+        bad[7].1 = &wrong;
+        assert!(!batch_verify(&kp.pk, &bad));
+        let mut tampered = proved[3].1.clone();
+        tampered.a = mod_positive(&tampered.a * 4, &kp.pk.n2);
+        let mut bad = items.clone();
+        bad[3].2 = &tampered;
+        assert!(!batch_verify(&kp.pk, &bad));
+    }
+
+    // A prover holding r publishes a' = N² - a with e = H(.., a') and
+    // z = s·r^e: then z^N = -a'·u^e, off by exactly -1
+    fn negated_proof(kp: &Keypair, c: &Ciphertext, m: &BigInt) -> DecryptionProof {
+        let u = residue(&kp.pk, c, m).unwrap();
+        let (p, q) = kp.sk.factors();
+        let n_inv = modinv(&kp.pk.n, &((p - 1) * (q - 1))).unwrap();
+        let r = modpow_bigint(&u, &n_inv, &kp.pk.n);
+        let s = BigInt::from(3);
+        let a = &kp.pk.n2 - modpow_bigint(&s, &kp.pk.n, &kp.pk.n2);
+        let e = challenge(&kp.pk, c, m, &a);
+        DecryptionProof { z: mod_positive(s * modpow_bigint(&r, &e, &kp.pk.n), &kp.pk.n), a }
+    }
+
+    #[test]
+    fn batch_misses_order_two_factors() {
+        let kp = Keypair::generate(1024);
+        let m = BigInt::from(42);
+        let c = kp.pk.encrypt(m.clone()).unwrap();
+
+        // `verify` rejects, but the batch only sees (-1)^w, so it accepts
+        // whenever the weight is even
+        let negated = negated_proof(&kp, &c, &m);
+        assert!(!negated.verify(&kp.pk, &c, &m));
+        let runs: Vec<bool> = (0..40).map(|_| batch_verify(&kp.pk, &[(&c, &m, &negated)])).collect();
+        assert!(runs.contains(&true) && runs.contains(&false));
+
+        // two such proofs cancel whenever both weights have the same parity
+        let m2 = BigInt::from(7);
+        let c2 = kp.pk.encrypt(m2.clone()).unwrap();
+        let negated2 = negated_proof(&kp, &c2, &m2);
+        assert!(!negated2.verify(&kp.pk, &c2, &m2));
+        let items = [(&c, &m, &negated), (&c2, &m2, &negated2)];
+        assert!((0..40).any(|_| batch_verify(&kp.pk, &items)));
+    }
+}
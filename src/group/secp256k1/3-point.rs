@@ -1,5 +1,6 @@
 // src/group/secp256k1/3-point.rs
 use num_bigint::BigUint;
+use num_integer::Integer;
 use num_traits::{Zero, One};
 use crate::group::secp256k1::params::Secp256k1;
 use crate::group::secp256k1::field::FieldElement;
@@ -12,6 +13,70 @@ pub struct AffinePoint {
     pub infinity: bool,
 }
 
+impl AffinePoint {
+    /// Point at infinity
+    pub fn identity() -> Self {
+        AffinePoint { x: BigUint::zero(), y: BigUint::zero(), infinity: true }
+    }
+
+    /// Base point G
+    pub fn generator(curve: &Secp256k1) -> Self {
+        AffinePoint { x: curve.gx.clone(), y: curve.gy.clone(), infinity: false }
+    }
+
+    /// y^2 = x^3 + a*x + b (mod p)
+    pub fn is_on_curve(&self, curve: &Secp256k1) -> bool {
+        if self.infinity {
+            return true;
+        }
+        let p = &curve.p;
+        if &self.x >= p || &self.y >= p {
+            return false;
+        }
+        let lhs = (&self.y * &self.y) % p;
+        let rhs = (&self.x * &self.x * &self.x + &curve.a * &self.x + &curve.b) % p;
+        lhs == rhs
+    }
+
+    /// SEC1 compressed encoding: 0x02/0x03 || x (33 bytes), or 0x00 for infinity
+    pub fn to_compressed(&self) -> Vec<u8> {
+        if self.infinity {
+            return vec![0x00];
+        }
+        let mut out = Vec::with_capacity(33);
+        out.push(if self.y.is_odd() { 0x03 } else { 0x02 });
+        let x = self.x.to_bytes_be();
+        out.extend(std::iter::repeat_n(0u8, 32 - x.len()));
+        out.extend_from_slice(&x);
+        out
+    }
+
+    /// Parse a SEC1 compressed point; None if it is not on the curve
+    pub fn from_compressed(bytes: &[u8], curve: &Secp256k1) -> Option<Self> {
+        if bytes == [0x00] {
+            return Some(Self::identity());
+        }
+        if bytes.len() != 33 || (bytes[0] != 0x02 && bytes[0] != 0x03) {
+            return None;
+        }
+        let p = &curve.p;
+        let x = BigUint::from_bytes_be(&bytes[1..]);
+        if &x >= p {
+            return None;
+        }
+        // p ≡ 3 (mod 4): sqrt(v) = v^((p+1)/4)
+        let rhs = (&x * &x * &x + &curve.a * &x + &curve.b) % p;
+        let mut y = rhs.modpow(&((p + BigUint::one()) >> 2), p);
+        if (&y * &y) % p != rhs {
+            return None;
+        }
+        if y.is_odd() != (bytes[0] == 0x03) {
+            y = (p - &y) % p;
+        }
+        Some(AffinePoint { x, y, infinity: false })
+    }
+}
+
 /// Jacobian projective point (X:Y:Z) corresponds to (X/Z^2, Y/Z^3) in affine
 #[derive(Debug, Clone)]
 pub struct JacobianPoint {
@@ -163,6 +228,39 @@ pub fn jacobian_add(P: &JacobianPoint, Q: &JacobianPoint, curve: &Secp256k1) ->
     }
 }
 
+/// Affine addition via Jacobian coordinates
+pub fn point_add(a: &AffinePoint, b: &AffinePoint, curve: &Secp256k1) -> AffinePoint {
+    let sum = jacobian_add(&JacobianPoint::from_affine(a), &JacobianPoint::from_affine(b), curve);
+    sum.to_affine(&curve.p)
+}
+
+/// -P = (x, -y)
+pub fn point_neg(a: &AffinePoint, curve: &Secp256k1) -> AffinePoint {
+    if a.infinity {
+        return a.clone();
+    }
+    AffinePoint { x: a.x.clone(), y: (&curve.p - &a.y) % &curve.p, infinity: false }
+}
+
+/// k·P by left-to-right double-and-add in Jacobian coordinates (not constant time)
+pub fn scalar_mul(k: &BigUint, a: &AffinePoint, curve: &Secp256k1) -> AffinePoint {
+    let k = k % &curve.n;
+    let base = JacobianPoint::from_affine(a);
+    let mut acc = JacobianPoint::from_affine(&AffinePoint::identity());
+    for i in (0..k.bits()).rev() {
+        acc = jacobian_double(&acc, curve);
+        if k.bit(i) {
+            acc = jacobian_add(&acc, &base, curve);
+        }
+    }
+    acc.to_affine(&curve.p)
+}
+
+/// k·G
+pub fn scalar_mul_base(k: &BigUint, curve: &Secp256k1) -> AffinePoint {
+    scalar_mul(k, &AffinePoint::generator(curve), curve)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +275,41 @@ mod tests {
         let q_aff = Q.to_affine(&secp.p);
         assert!(!q_aff.infinity);
     }
+
+    #[test]
+    fn scalar_mul_matches_repeated_addition() {
+        let secp = Secp256k1::new();
+        let g = AffinePoint::generator(&secp);
+        let mut acc = AffinePoint::identity();
+        for k in 1u32..=5 {
+            acc = point_add(&acc, &g, &secp);
+            assert_eq!(scalar_mul_base(&BigUint::from(k), &secp), acc);
+            assert!(acc.is_on_curve(&secp));
+        }
+        // n·G = O and (n-1)·G = -G
+        assert!(scalar_mul_base(&secp.n, &secp).infinity);
+        assert_eq!(scalar_mul_base(&(&secp.n - 1u32), &secp), point_neg(&g, &secp));
+        // 2G, well-known x coordinate
+        let two_g = scalar_mul_base(&BigUint::from(2u32), &secp);
+        let x = BigUint::parse_bytes(b"C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5", 16).unwrap();
+        assert_eq!(two_g.x, x);
+    }
+
+    #[test]
+    fn compressed_roundtrip() {
+        let secp = Secp256k1::new();
+        for k in [1u32, 2, 3, 12345] {
+            let pt = scalar_mul_base(&BigUint::from(k), &secp);
+            let bytes = pt.to_compressed();
+            assert_eq!(bytes.len(), 33);
+            assert_eq!(AffinePoint::from_compressed(&bytes, &secp), Some(pt));
+        }
+        assert!(AffinePoint::from_compressed(&[0x00], &secp).unwrap().infinity);
+        // x = 5 has no point on secp256k1 (5^3 + 7 = 132 is a non-residue)
+        let mut bad = vec![0x02];
+        bad.extend_from_slice(&[0u8; 31]);
+        bad.push(5);
+        assert!(AffinePoint::from_compressed(&bad, &secp).is_none());
+        assert!(AffinePoint::from_compressed(&[0x04; 33], &secp).is_none());
+    }
 }
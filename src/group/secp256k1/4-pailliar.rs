@@ -13,8 +13,13 @@
 // - 2048+ bit modulus (configurable)
 // - KeyGen with probable primes
 // - Encrypt/Decrypt with g = n + 1
+// - CRT decryption mod p^2 and q^2 (default), λ/μ path kept for cross-checks
 // - Homomorphic addition and scalar multiplication
 // - Ciphertext re-randomization
+// - Shared Montgomery-form sliding-window exponentiation (group::utils)
+// - Binomial shortcut g^m = 1 + m*n mod n^2 and an offline pool of r^n
+//   values (`RandomnessPool`) that `encrypt` / `rerandomize` draw from once
+//   attached to the public key
 // - Zeroizes private key on drop (best-effort)
 // - Unit tests for correctness
 //
@@ -22,8 +27,9 @@
 // - Paillier is IND‑CPA (randomized). It is malleable; for CCA security, wrap
 //   in a KEM+DEM or a CCA transform (e.g., Fujisaki–Okamoto style) or use
 //   higher-level protocols with proofs. Not provided here.
-// - CRT decryption optimization can be added; the classic λ, μ path is used
-//   for clarity and fewer moving parts.
+// - Decryption works mod p^2 and q^2 with precomputed hp, hq and recombines
+//   with CRT (roughly 3-4x faster than one exponentiation mod n^2). The
+//   classic λ, μ path is kept as `decrypt_lambda` for clarity and testing.
 //
 // Cargo.toml (add):
 // [dependencies]
@@ -41,21 +47,24 @@ use num_integer::Integer;
 use num_traits::{One, Zero};
 use rand::rngs::OsRng;
 use rand::RngCore;
+use std::sync::Mutex;
 use thiserror::Error;
+use crate::group::utils::montgomery::modpow_bigint;
+use crate::group::utils::primes::{gen_prime, random_blum_prime_with_rng, BailliePsw};
 
 // -----------------------------
 // Utilities
 // -----------------------------
-fn mod_positive(x: BigInt, m: &BigInt) -> BigInt {
+pub(crate) fn mod_positive(x: BigInt, m: &BigInt) -> BigInt {
     let r = x % m;
     if r.sign() == Sign::Minus { r + m } else { r }
 }
 
-fn lcm(a: &BigInt, b: &BigInt) -> BigInt {
+pub(crate) fn lcm(a: &BigInt, b: &BigInt) -> BigInt {
     (a * b) / a.gcd(b)
 }
 
-fn modinv(a: &BigInt, m: &BigInt) -> Option<BigInt> {
+pub(crate) fn modinv(a: &BigInt, m: &BigInt) -> Option<BigInt> {
     // Extended Euclid
     let (mut t, mut new_t) = (BigInt::zero(), BigInt::one());
     let (mut r, mut new_r) = (m.clone(), mod_positive(a.clone(), m));
@@ -71,84 +80,60 @@ fn modinv(a: &BigInt, m: &BigInt) -> Option<BigInt> {
     Some(t)
 }
 
-fn modexp(mut base: BigInt, mut exp: BigInt, modu: &BigInt) -> BigInt {
-    // Fast exponentiation (square-and-multiply)
-    let mut result = BigInt::one();
-    base = mod_positive(base, modu);
-    while exp > BigInt::zero() {
-        if (&exp & BigInt::one()) == BigInt::one() {
-            result = mod_positive(result * &base, modu);
-        }
-        base = mod_positive(&base * &base, modu);
-        exp >>= 1;
-    }
-    result
-}
-
-// Miller–Rabin probable-prime test
-fn is_probable_prime(n: &BigInt, rounds: usize) -> bool {
-    if *n < BigInt::from(2u32) { return false; }
-    // small primes
-    for p in [2u32,3,5,7,11,13,17,19,23,29,31,37].iter() {
-        let bp = BigInt::from(*p);
-        if n == &bp { return true; }
-        if (n % &bp).is_zero() { return false; }
-    }
-    // write n-1 = d * 2^s
-    let one = BigInt::one();
-    let two = BigInt::from(2u32);
-    let n_minus_1 = n - &one;
-    let mut d = n_minus_1.clone();
-    let mut s = 0u32;
-    while (&d & &one).is_zero() { d >>= 1; s += 1; }
-
-    let mut rng = OsRng;
-    'outer: for _ in 0..rounds {
-        let a = rng.gen_bigint_range(&two, &(n_minus_1));
-        let mut x = modexp(a, d.clone(), n);
-        if x == one || x == n_minus_1 { continue 'outer; }
-        for _ in 1..s {
-            x = mod_positive(&x * &x, n);
-            if x == n_minus_1 { continue 'outer; }
-        }
-        return false;
-    }
-    true
+fn modexp(base: BigInt, exp: BigInt, modu: &BigInt) -> BigInt {
+    // Sliding-window exponentiation in Montgomery form (odd moduli)
+    modpow_bigint(&base, &exp, modu)
 }
 
-fn random_prime(bits: usize) -> BigInt {
-    assert!(bits >= 256, "Use >= 256-bit primes (recommended 1024+)\n");
-    let mut rng = OsRng;
-    loop {
-        let mut p = rng.gen_bigint(bits as u64);
-        // force top and bottom bits: ensure exact bit length and odd
-        let one = BigInt::one();
-        let two = BigInt::from(2u32);
-        p.set_bit(bits as u64 - 1, true);
-        if (&p & &one).is_zero() { p = p + &one; }
-        if is_probable_prime(&p, 64) { return p; }
-    }
+// hp = L_p(g^(p-1) mod p^2)^{-1} mod p, where L_p(u) = (u - 1) / p
+fn crt_h(g: &BigInt, p: &BigInt, p2: &BigInt) -> Option<BigInt> {
+    let u = modexp(g.clone(), p - BigInt::one(), p2);
+    let l = (u - BigInt::one()) / p;
+    modinv(&l, p)
 }
 
 // -----------------------------
 // Public/Private keys and API
 // -----------------------------
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug)]
 pub struct PublicKey {
     pub n: BigInt,
     pub n2: BigInt, // n^2 for speed
     pub g: BigInt,  // typically g = n + 1
+    // (r, r^n) values for this instance only, see `attach_pool`
+    pool: Option<Mutex<RandomnessPool>>,
+}
+
+// The pool holds the encryptor's secret randomness: a clone (say, the copy
+// sent to a peer) gets none, and it plays no part in key equality.
+impl Clone for PublicKey {
+    fn clone(&self) -> Self {
+        PublicKey { n: self.n.clone(), n2: self.n2.clone(), g: self.g.clone(), pool: None }
+    }
+}
+
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.g == other.g
+    }
 }
 
+impl Eq for PublicKey {}
+
 #[derive(Debug)]
 pub struct PrivateKey {
     // λ = lcm(p-1, q-1)
     lambda: BigInt,
     // μ = (L(g^λ mod n^2))^{-1} mod n ; with g=n+1 => μ = λ^{-1} mod n
     mu: BigInt,
-    // Optional: keep p, q for potential CRT optimizations
+    // p, q and the CRT material derived from them
     p: BigInt,
     q: BigInt,
+    p2: BigInt,     // p^2
+    q2: BigInt,     // q^2
+    hp: BigInt,     // L_p(g^(p-1) mod p^2)^{-1} mod p
+    hq: BigInt,     // L_q(g^(q-1) mod q^2)^{-1} mod q
+    q_inv_p: BigInt, // q^{-1} mod p
 }
 
 impl Drop for PrivateKey {
@@ -161,6 +146,11 @@ impl Drop for PrivateKey {
         self.mu = BigInt::zero();
         self.p = BigInt::zero();
         self.q = BigInt::zero();
+        self.p2 = BigInt::zero();
+        self.q2 = BigInt::zero();
+        self.hp = BigInt::zero();
+        self.hq = BigInt::zero();
+        self.q_inv_p = BigInt::zero();
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -176,6 +166,8 @@ pub enum PaillierError {
     RNotInvertible,
     #[error("internal error: modular inverse not found")]
     NoInverse,
+    #[error("randomness pool was filled for a different public key")]
+    PoolKeyMismatch,
 }
 
 pub struct Keypair {
@@ -185,12 +177,41 @@ pub struct Keypair {
 
 impl Keypair {
     pub fn generate(bits_n: usize) -> Self {
-        assert!(bits_n % 2 == 0, "bits_n should be even (p and q similar size)");
-        let half = bits_n / 2;
+        Self::generate_with_rng(bits_n, &mut OsRng)
+    }
+
+    /// Same as `generate` with a caller-supplied RNG (e.g. seeded for tests)
+    pub fn generate_with_rng(bits_n: usize, rng: &mut dyn RngCore) -> Self {
+        let half = Self::half_bits(bits_n);
         // Generate distinct primes p, q
-        let mut p = random_prime(half);
-        let mut q = random_prime(half);
-        while p == q { q = random_prime(half); }
+        let p = gen_prime(half, rng, &BailliePsw);
+        let mut q = gen_prime(half, rng, &BailliePsw);
+        while p == q { q = gen_prime(half, rng, &BailliePsw); }
+        Self::from_primes(p, q)
+    }
+
+    /// Keypair whose modulus is a Paillier-Blum integer (p ≡ q ≡ 3 mod 4),
+    /// as required by the modulus proof in `modulus_proofs`
+    pub fn generate_blum(bits_n: usize) -> Self {
+        Self::generate_blum_with_rng(bits_n, &mut OsRng)
+    }
+
+    pub fn generate_blum_with_rng(bits_n: usize, rng: &mut dyn RngCore) -> Self {
+        let half = Self::half_bits(bits_n);
+        let p = random_blum_prime_with_rng(half, rng);
+        let mut q = random_blum_prime_with_rng(half, rng);
+        while p == q { q = random_blum_prime_with_rng(half, rng); }
+        Self::from_primes(p, q)
+    }
+
+    fn half_bits(bits_n: usize) -> usize {
+        assert!(bits_n.is_multiple_of(2), "bits_n should be even (p and q similar size)");
+        assert!(bits_n >= 512, "Use >= 256-bit primes (recommended 1024+)");
+        bits_n / 2
+    }
+
+    /// Build a keypair from two distinct primes of similar size
+    pub fn from_primes(p: BigInt, q: BigInt) -> Self {
         let n = &p * &q;
         let n2 = &n * &n;
         let g = &n + BigInt::one(); // standard choice
@@ -200,21 +221,46 @@ impl Keypair {
         // With g = n + 1, L(g^λ mod n^2) = λ (mod n)
         let mu = modinv(&lambda, &n).expect("lambda invertible mod n for primes p,q");
 
-        let pk = PublicKey { n: n.clone(), n2, g };
-        let sk = PrivateKey { lambda, mu, p, q };
+        let pk = PublicKey { n: n.clone(), n2, g, pool: None };
+        let sk = PrivateKey::from_primes(&pk, lambda, mu, p, q);
         Keypair { pk, sk }
     }
 }
 
 impl PublicKey {
-    /// Encrypt m \in [0, n)
+    /// Encrypt m \in [0, n). r^n comes from the attached pool while it has
+    /// entries, and is computed online otherwise.
     pub fn encrypt<M: Into<BigInt>>(&self, m: M) -> Result<Ciphertext, PaillierError> {
         let m = m.into();
         if m.sign() == Sign::Minus || m >= self.n {
             return Err(PaillierError::MessageOutOfRange);
         }
+        let (_, r_to_n) = self.randomness();
+        Ok(self.encrypt_with_r_to_n(&m, &r_to_n))
+    }
+
+    /// Let `encrypt`, `rerandomize` and `randomness` on this instance draw
+    /// from `pool`, replacing any pool attached before. Clones do not share it.
+    pub fn attach_pool(&mut self, pool: RandomnessPool) -> Result<(), PaillierError> {
+        if pool.n != self.n { return Err(PaillierError::PoolKeyMismatch); }
+        self.pool = Some(Mutex::new(pool));
+        Ok(())
+    }
+
+    /// Entries left in the attached pool (0 when there is none)
+    pub fn pooled(&self) -> usize {
+        self.pool.as_ref().map_or(0, |p| p.lock().expect("pool lock").len())
+    }
+
+    /// A fresh unit r and r^n mod n^2, from the attached pool when it has
+    /// entries. For protocols that need r itself, e.g. as a proof witness.
+    pub fn randomness(&self) -> (BigInt, BigInt) {
+        if let Some(entry) = self.pool.as_ref().and_then(|p| p.lock().expect("pool lock").entries.pop()) {
+            return entry;
+        }
         let r = self.sample_zn_star();
-        self.encrypt_with_r(m, r)
+        let r_to_n = self.r_to_n(r.clone());
+        (r, r_to_n)
     }
 
     /// Encrypt with externally provided randomness r \in Z*_n. Useful for tests or protocols.
@@ -224,15 +270,30 @@ impl PublicKey {
         }
         // require gcd(r, n) = 1
         if r.gcd(&self.n) != BigInt::one() { return Err(PaillierError::RNotInvertible); }
-        let c1 = modexp(self.g.clone(), m, &self.n2); // g^m mod n^2
-        let c2 = modexp(r.mod_floor(&self.n).pow(1), self.n.clone(), &self.n2); // r^n mod n^2
-        Ok(Ciphertext(mod_positive(c1 * c2, &self.n2)))
+        let r_to_n = self.r_to_n(r);
+        Ok(self.encrypt_with_r_to_n(&m, &r_to_n))
+    }
+
+    /// Encrypt m \in [0, n) drawing r^n mod n^2 from a precomputed pool.
+    /// Falls back to computing r^n online when the pool is empty.
+    pub fn encrypt_with_pool<M: Into<BigInt>>(&self, m: M, pool: &mut RandomnessPool) -> Result<Ciphertext, PaillierError> {
+        let m = m.into();
+        if m.sign() == Sign::Minus || m >= self.n {
+            return Err(PaillierError::MessageOutOfRange);
+        }
+        let r_to_n = self.draw_r_to_n(pool)?;
+        Ok(self.encrypt_with_r_to_n(&m, &r_to_n))
     }
 
+    /// Multiply in a fresh s^n, from the attached pool when it has entries
     pub fn rerandomize(&self, c: &Ciphertext) -> Result<Ciphertext, PaillierError> {
-        // multiply by s^n for fresh random s \in Z*_n
-        let s = self.sample_zn_star();
-        let s_to_n = modexp(s, self.n.clone(), &self.n2);
+        let (_, s_to_n) = self.randomness();
+        Ok(Ciphertext(mod_positive(&c.0 * s_to_n, &self.n2)))
+    }
+
+    /// Rerandomize drawing s^n mod n^2 from a precomputed pool.
+    pub fn rerandomize_with_pool(&self, c: &Ciphertext, pool: &mut RandomnessPool) -> Result<Ciphertext, PaillierError> {
+        let s_to_n = self.draw_r_to_n(pool)?;
         Ok(Ciphertext(mod_positive(&c.0 * s_to_n, &self.n2)))
     }
 
@@ -243,15 +304,55 @@ impl PublicKey {
     pub fn add_plain<M: Into<BigInt>>(&self, c: &Ciphertext, m: M) -> Result<Ciphertext, PaillierError> {
         let m = m.into();
         if m.sign() == Sign::Minus || m >= self.n { return Err(PaillierError::MessageOutOfRange); }
-        let g_to_m = modexp(self.g.clone(), m, &self.n2);
+        let g_to_m = self.g_pow(&m);
         Ok(Ciphertext(mod_positive(&c.0 * g_to_m, &self.n2)))
     }
 
+    /// Enc(k*m) for any integer k; the plaintext product wraps mod n
+    /// (see `encoding` for signed values with overflow tracking)
     pub fn mul_scalar<S: Into<BigInt>>(&self, c: &Ciphertext, k: S) -> Ciphertext {
-        let k = k.into();
+        // c^(k mod n) decrypts to k*m mod n as well, so negative k needs no inverse
+        let k = mod_positive(k.into(), &self.n);
         Ciphertext(modexp(c.0.clone(), k, &self.n2))
     }
 
+    /// Enc(-m) = c^{-1} mod n^2
+    pub fn neg(&self, c: &Ciphertext) -> Result<Ciphertext, PaillierError> {
+        modinv(&c.0, &self.n2).map(Ciphertext).ok_or(PaillierError::InvalidCiphertext)
+    }
+
+    /// Enc(m1 - m2) = c1 * c2^{-1} mod n^2
+    pub fn sub(&self, c1: &Ciphertext, c2: &Ciphertext) -> Result<Ciphertext, PaillierError> {
+        let neg = self.neg(c2)?;
+        Ok(self.add(c1, &neg))
+    }
+
+    // g^m mod n^2; with g = n + 1 the binomial theorem gives g^m = 1 + m*n mod n^2
+    fn g_pow(&self, m: &BigInt) -> BigInt {
+        if self.g == &self.n + BigInt::one() {
+            mod_positive(BigInt::one() + m * &self.n, &self.n2)
+        } else {
+            modexp(self.g.clone(), m.clone(), &self.n2)
+        }
+    }
+
+    // r^n mod n^2, the expensive half of an encryption
+    fn r_to_n(&self, r: BigInt) -> BigInt {
+        modexp(r, self.n.clone(), &self.n2)
+    }
+
+    fn encrypt_with_r_to_n(&self, m: &BigInt, r_to_n: &BigInt) -> Ciphertext {
+        Ciphertext(mod_positive(self.g_pow(m) * r_to_n, &self.n2))
+    }
+
+    fn draw_r_to_n(&self, pool: &mut RandomnessPool) -> Result<BigInt, PaillierError> {
+        if pool.n != self.n { return Err(PaillierError::PoolKeyMismatch); }
+        Ok(match pool.entries.pop() {
+            Some((_, r_to_n)) => r_to_n,
+            None => self.r_to_n(self.sample_zn_star()),
+        })
+    }
+
     fn sample_zn_star(&self) -> BigInt {
         // uniform r in Z*_n via rejection sampling
         let mut rng = OsRng;
@@ -262,9 +363,99 @@ impl PublicKey {
     }
 }
 
+/// Offline pool of precomputed (r, r^n mod n^2) pairs for one public key.
+/// Fill it while idle and `PublicKey::attach_pool` it; `encrypt`,
+/// `rerandomize` and everything built on `randomness` (MtA, Lindell'17
+/// signing) then only pay for a multiplication mod n^2. A pool can also be
+/// held separately and passed to the `_with_pool` variants. Every entry is
+/// used at most once.
+pub struct RandomnessPool {
+    n: BigInt,
+    entries: Vec<(BigInt, BigInt)>,
+}
+
+impl std::fmt::Debug for RandomnessPool {
+    // entry count only: the values are secret randomness
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RandomnessPool").field("entries", &self.entries.len()).finish()
+    }
+}
+
+impl RandomnessPool {
+    /// Empty pool bound to `pk`
+    pub fn new(pk: &PublicKey) -> Self {
+        RandomnessPool { n: pk.n.clone(), entries: Vec::new() }
+    }
+
+    /// Pool bound to `pk`, pre-filled with `count` entries
+    pub fn with_entries(pk: &PublicKey, count: usize) -> Self {
+        let mut pool = Self::new(pk);
+        pool.fill(pk, count);
+        pool
+    }
+
+    /// Precompute `count` more pairs (r, r^n mod n^2) for fresh random r \in Z*_n
+    pub fn fill(&mut self, pk: &PublicKey, count: usize) {
+        assert_eq!(pk.n, self.n, "pool belongs to a different public key");
+        self.entries.reserve(count);
+        for _ in 0..count {
+            let r = pk.sample_zn_star();
+            let r_to_n = pk.r_to_n(r.clone());
+            self.entries.push((r, r_to_n));
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Drop for RandomnessPool {
+    fn drop(&mut self) {
+        // unused r^n values would let their holder strip randomness from
+        // future ciphertexts; clear them best-effort like PrivateKey
+        for (r, r_to_n) in self.entries.iter_mut() {
+            *r = BigInt::zero();
+            *r_to_n = BigInt::zero();
+        }
+    }
+}
+
 impl PrivateKey {
-    /// Decrypt c -> m in [0, n)
+    fn from_primes(pk: &PublicKey, lambda: BigInt, mu: BigInt, p: BigInt, q: BigInt) -> Self {
+        let p2 = &p * &p;
+        let q2 = &q * &q;
+        let hp = crt_h(&pk.g, &p, &p2).expect("hp invertible mod p for g = n + 1");
+        let hq = crt_h(&pk.g, &q, &q2).expect("hq invertible mod q for g = n + 1");
+        let q_inv_p = modinv(&q, &p).expect("q invertible mod p for distinct primes");
+        PrivateKey { lambda, mu, p, q, p2, q2, hp, hq, q_inv_p }
+    }
+
+    /// The secret primes (p, q), for protocols that extend this key
+    pub(crate) fn factors(&self) -> (&BigInt, &BigInt) {
+        (&self.p, &self.q)
+    }
+
+    /// Decrypt c -> m in [0, n) using CRT over p^2 and q^2
     pub fn decrypt(&self, pk: &PublicKey, c: &Ciphertext) -> Result<BigInt, PaillierError> {
+        // m_p = L_p(c^(p-1) mod p^2) * hp mod p
+        // m_q = L_q(c^(q-1) mod q^2) * hq mod q
+        // m   = m_q + q * ((m_p - m_q) * q^{-1} mod p)
+        let one = BigInt::one();
+        let up = modexp(c.0.clone(), &self.p - &one, &self.p2);
+        let uq = modexp(c.0.clone(), &self.q - &one, &self.q2);
+        let mp = mod_positive(((up - &one) / &self.p) * &self.hp, &self.p);
+        let mq = mod_positive(((uq - &one) / &self.q) * &self.hq, &self.q);
+        let h = mod_positive((mp - &mq) * &self.q_inv_p, &self.p);
+        Ok(mod_positive(mq + h * &self.q, &pk.n))
+    }
+
+    /// Decrypt c -> m in [0, n) with the classic λ, μ formula (no CRT)
+    pub fn decrypt_lambda(&self, pk: &PublicKey, c: &Ciphertext) -> Result<BigInt, PaillierError> {
         // Classic decryption with λ and μ
         // m = L(c^λ mod n^2) * μ mod n, where L(u) = (u - 1) / n
         let u = modexp(c.0.clone(), self.lambda.clone(), &pk.n2);
@@ -320,6 +511,16 @@ mod tests {
         assert_eq!(m, d);
     }
 
+    #[test]
+    fn seeded_blum_keygen_is_reproducible() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let a = Keypair::generate_blum_with_rng(512, &mut StdRng::seed_from_u64(36));
+        let b = Keypair::generate_blum_with_rng(512, &mut StdRng::seed_from_u64(36));
+        assert_eq!(a.pk, b.pk);
+        assert_eq!(a.pk.n.mod_floor(&BigInt::from(4)), BigInt::one());
+    }
+
     #[test]
     fn homomorphic_add() {
         let kp = Keypair::generate(1536);
@@ -345,6 +546,20 @@ mod tests {
         assert_eq!(d2, m * k);
     }
 
+    #[test]
+    fn neg_sub_and_negative_scalar() {
+        let kp = Keypair::generate(1024);
+        let pk = kp.pk; let sk = kp.sk;
+        let c1 = pk.encrypt(1000u32).unwrap();
+        let c2 = pk.encrypt(58u32).unwrap();
+        assert_eq!(sk.decrypt(&pk, &pk.sub(&c1, &c2).unwrap()).unwrap(), BigInt::from(942));
+        // negatives wrap to n - |m|
+        assert_eq!(sk.decrypt(&pk, &pk.sub(&c2, &c1).unwrap()).unwrap(), &pk.n - 942);
+        assert_eq!(sk.decrypt(&pk, &pk.neg(&c2).unwrap()).unwrap(), &pk.n - 58);
+        assert_eq!(sk.decrypt(&pk, &pk.mul_scalar(&c2, -2)).unwrap(), &pk.n - 116);
+        assert!(matches!(pk.neg(&Ciphertext(pk.n.clone())), Err(PaillierError::InvalidCiphertext)));
+    }
+
     #[test]
     fn rerandomize_changes_ciphertext() {
         let kp = Keypair::generate(1024);
@@ -357,6 +572,92 @@ mod tests {
         assert_eq!(d, m);
     }
 
+    #[test]
+    fn crt_decrypt_matches_lambda_path() {
+        let kp = Keypair::generate(1024);
+        let pk = kp.pk; let sk = kp.sk;
+        let mut rng = OsRng;
+        let edge = [BigInt::zero(), BigInt::one(), &pk.n - BigInt::one()];
+        for m in edge.iter().cloned().chain((0..20).map(|_| rng.gen_bigint_range(&BigInt::zero(), &pk.n))) {
+            let c = pk.encrypt(m.clone()).unwrap();
+            let d_crt = sk.decrypt(&pk, &c).unwrap();
+            let d_lambda = sk.decrypt_lambda(&pk, &c).unwrap();
+            assert_eq!(d_crt, d_lambda);
+            assert_eq!(d_crt, m);
+        }
+    }
+
+    #[test]
+    fn g_pow_shortcut_matches_modexp() {
+        let kp = Keypair::generate(1024);
+        let pk = kp.pk;
+        let mut rng = OsRng;
+        for _ in 0..10 {
+            let m = rng.gen_bigint_range(&BigInt::zero(), &pk.n);
+            assert_eq!(pk.g_pow(&m), modexp(pk.g.clone(), m, &pk.n2));
+        }
+    }
+
+    #[test]
+    fn pool_encrypt_and_rerandomize() {
+        let kp = Keypair::generate(1024);
+        let pk = kp.pk; let sk = kp.sk;
+        let mut pool = RandomnessPool::with_entries(&pk, 3);
+        assert_eq!(pool.len(), 3);
+
+        let m = BigInt::from(4242u32);
+        let c = pk.encrypt_with_pool(m.clone(), &mut pool).unwrap();
+        let c_rr = pk.rerandomize_with_pool(&c, &mut pool).unwrap();
+        assert_ne!(c, c_rr);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(sk.decrypt(&pk, &c).unwrap(), m);
+        assert_eq!(sk.decrypt(&pk, &c_rr).unwrap(), m);
+
+        // drained pool falls back to online randomness
+        let _ = pk.encrypt_with_pool(1u32, &mut pool).unwrap();
+        assert!(pool.is_empty());
+        let c2 = pk.encrypt_with_pool(m.clone(), &mut pool).unwrap();
+        assert_eq!(sk.decrypt(&pk, &c2).unwrap(), m);
+    }
+
+    #[test]
+    fn attached_pool_feeds_encrypt_and_rerandomize() {
+        let kp = Keypair::generate(1024);
+        let mut pk = kp.pk.clone();
+        assert_eq!(pk.pooled(), 0);
+        pk.attach_pool(RandomnessPool::with_entries(&pk, 3)).unwrap();
+        assert_eq!(pk.pooled(), 3);
+
+        let m = BigInt::from(4242u32);
+        let c = pk.encrypt(m.clone()).unwrap();
+        let c_rr = pk.rerandomize(&c).unwrap();
+        assert_ne!(c, c_rr);
+        let (r, r_to_n) = pk.randomness();
+        assert_eq!(r_to_n, pk.r_to_n(r.clone()));
+        assert_eq!(pk.pooled(), 0);
+        assert_eq!(kp.sk.decrypt(&pk, &c).unwrap(), m);
+        assert_eq!(kp.sk.decrypt(&pk, &c_rr).unwrap(), m);
+        assert_eq!(pk.encrypt_with_r(m.clone(), r).unwrap(), pk.encrypt_with_r_to_n(&m, &r_to_n));
+
+        // drained: online randomness; clones carry no pool but compare equal
+        assert_eq!(kp.sk.decrypt(&pk, &pk.encrypt(m.clone()).unwrap()).unwrap(), m);
+        pk.attach_pool(RandomnessPool::with_entries(&pk, 1)).unwrap();
+        assert_eq!(pk.clone().pooled(), 0);
+        assert_eq!(pk.clone(), pk);
+        let other = Keypair::generate(512).pk;
+        assert!(matches!(pk.attach_pool(RandomnessPool::new(&other)), Err(PaillierError::PoolKeyMismatch)));
+        assert_eq!(pk.pooled(), 1);
+    }
+
+    #[test]
+    fn pool_rejects_other_key() {
+        let pk1 = Keypair::generate(512).pk;
+        let pk2 = Keypair::generate(512).pk;
+        let mut pool = RandomnessPool::with_entries(&pk1, 1);
+        assert!(matches!(pk2.encrypt_with_pool(1u32, &mut pool), Err(PaillierError::PoolKeyMismatch)));
+        assert_eq!(pool.len(), 1);
+    }
+
     #[test]
     fn range_check() {
         let kp = Keypair::generate(1024);
@@ -0,0 +1,312 @@
+// src/group/secp256k1/5-damgard_jurik.rs
+// -----------------------------------------------------------------------------
+// Damgård–Jurik: Paillier generalized to modulus n^(s+1)
+// -----------------------------------------------------------------------------
+// Same key (n = p*q) as Paillier, but plaintexts live in Z_{n^s} and
+// ciphertexts in Z*_{n^(s+1)}:
+//
+//     Enc(m; r) = (1 + n)^m * r^(n^s)  mod n^(s+1),   m in [0, n^s)
+//
+// so one ciphertext carries s times as many plaintext bits for a ciphertext
+// expansion of (s+1)/s instead of 2. With s = 1 everything below reduces to
+// the `pailliar` module bit for bit.
+//
+// Decryption raises c to λ, which kills the randomness, and then recovers
+// i = m*λ mod n^s from (1 + n)^i with the recursive algorithm of Damgård and
+// Jurik (PKC 2001, Theorem 1), one n-adic digit per round.
+// -----------------------------------------------------------------------------
+
+use num_bigint::{BigInt, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use thiserror::Error;
+use crate::group::utils::montgomery::modpow_bigint;
+use crate::group::utils::primes::random_prime;
+use super::pailliar::{self, lcm, mod_positive, modinv};
+
+#[derive(Error, Debug)]
+pub enum DamgardJurikError {
+    #[error("message out of range (expected 0 <= m < n^s)")]
+    MessageOutOfRange,
+    #[error("invalid ciphertext")]
+    InvalidCiphertext,
+    #[error("randomness not invertible mod n")]
+    RNotInvertible,
+    #[error("s must be at least 1")]
+    InvalidS,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DjPublicKey {
+    pub n: BigInt,
+    pub s: u32,
+    pub ns: BigInt,  // n^s, the plaintext modulus
+    pub ns1: BigInt, // n^(s+1), the ciphertext modulus
+}
+
+#[derive(Debug)]
+pub struct DjPrivateKey {
+    // λ = lcm(p-1, q-1)
+    lambda: BigInt,
+    // λ^{-1} mod n^s
+    lambda_inv: BigInt,
+}
+
+impl Drop for DjPrivateKey {
+    fn drop(&mut self) {
+        // best-effort zeroization, same caveats as pailliar::PrivateKey
+        self.lambda = BigInt::zero();
+        self.lambda_inv = BigInt::zero();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DjCiphertext(pub BigInt);
+
+pub struct DjKeypair {
+    pub pk: DjPublicKey,
+    pub sk: DjPrivateKey,
+}
+
+impl DjKeypair {
+    pub fn generate(bits_n: usize, s: u32) -> Result<Self, DamgardJurikError> {
+        assert!(bits_n.is_multiple_of(2), "bits_n should be even (p and q similar size)");
+        let half = bits_n / 2;
+        let p = random_prime(half);
+        let mut q = random_prime(half);
+        while p == q { q = random_prime(half); }
+        Self::from_primes(&p, &q, s)
+    }
+
+    /// Lift an existing Paillier key to modulus n^(s+1)
+    pub fn from_paillier(kp: &pailliar::Keypair, s: u32) -> Result<Self, DamgardJurikError> {
+        let (p, q) = kp.sk.factors();
+        Self::from_primes(p, q, s)
+    }
+
+    fn from_primes(p: &BigInt, q: &BigInt, s: u32) -> Result<Self, DamgardJurikError> {
+        let pk = DjPublicKey::new(p * q, s)?;
+        let lambda = lcm(&(p - BigInt::one()), &(q - BigInt::one()));
+        let lambda_inv = modinv(&lambda, &pk.ns).expect("gcd(λ, n) = 1 for balanced primes");
+        Ok(DjKeypair { pk, sk: DjPrivateKey { lambda, lambda_inv } })
+    }
+}
+
+impl DjPublicKey {
+    pub fn new(n: BigInt, s: u32) -> Result<Self, DamgardJurikError> {
+        if s == 0 { return Err(DamgardJurikError::InvalidS); }
+        let ns = n.pow(s);
+        let ns1 = &ns * &n;
+        Ok(DjPublicKey { n, s, ns, ns1 })
+    }
+
+    /// View a Paillier public key as the s = 1 instance
+    pub fn from_paillier(pk: &pailliar::PublicKey) -> Self {
+        Self::new(pk.n.clone(), 1).expect("s = 1 is valid")
+    }
+
+    /// Encrypt m \in [0, n^s)
+    pub fn encrypt<M: Into<BigInt>>(&self, m: M) -> Result<DjCiphertext, DamgardJurikError> {
+        let m = m.into();
+        self.check_plaintext(&m)?;
+        let r = self.sample_zn_star();
+        self.encrypt_with_r(m, r)
+    }
+
+    /// Encrypt with externally provided randomness r \in Z*_n
+    pub fn encrypt_with_r(&self, m: BigInt, r: BigInt) -> Result<DjCiphertext, DamgardJurikError> {
+        self.check_plaintext(&m)?;
+        if r.gcd(&self.n) != BigInt::one() { return Err(DamgardJurikError::RNotInvertible); }
+        let r_to_ns = modpow_bigint(&r, &self.ns, &self.ns1);
+        Ok(DjCiphertext(mod_positive(self.g_pow(&m) * r_to_ns, &self.ns1)))
+    }
+
+    pub fn rerandomize(&self, c: &DjCiphertext) -> DjCiphertext {
+        let r = self.sample_zn_star();
+        let r_to_ns = modpow_bigint(&r, &self.ns, &self.ns1);
+        DjCiphertext(mod_positive(&c.0 * r_to_ns, &self.ns1))
+    }
+
+    pub fn add(&self, c1: &DjCiphertext, c2: &DjCiphertext) -> DjCiphertext {
+        DjCiphertext(mod_positive(&c1.0 * &c2.0, &self.ns1))
+    }
+
+    pub fn add_plain<M: Into<BigInt>>(&self, c: &DjCiphertext, m: M) -> Result<DjCiphertext, DamgardJurikError> {
+        let m = m.into();
+        self.check_plaintext(&m)?;
+        Ok(DjCiphertext(mod_positive(&c.0 * self.g_pow(&m), &self.ns1)))
+    }
+
+    /// Enc(k*m mod n^s) for any integer k; negative k is reduced mod n^s
+    /// first, as in `pailliar::PublicKey::mul_scalar`
+    pub fn mul_scalar<S: Into<BigInt>>(&self, c: &DjCiphertext, k: S) -> DjCiphertext {
+        let k = k.into().mod_floor(&self.ns);
+        DjCiphertext(modpow_bigint(&c.0, &k, &self.ns1))
+    }
+
+    fn check_plaintext(&self, m: &BigInt) -> Result<(), DamgardJurikError> {
+        if m.sign() == Sign::Minus || m >= &self.ns {
+            return Err(DamgardJurikError::MessageOutOfRange);
+        }
+        Ok(())
+    }
+
+    // (1 + n)^m mod n^(s+1) by the binomial expansion sum_{k=0..s} C(m, k) n^k;
+    // terms with k > s vanish mod n^(s+1)
+    fn g_pow(&self, m: &BigInt) -> BigInt {
+        let mut acc = BigInt::one();
+        let mut binom = BigInt::one(); // C(m, k)
+        let mut n_k = BigInt::one();   // n^k
+        for k in 1..=self.s {
+            binom = binom * (m - BigInt::from(k - 1)) / BigInt::from(k);
+            n_k *= &self.n;
+            acc += &binom * &n_k;
+        }
+        mod_positive(acc, &self.ns1)
+    }
+
+    fn sample_zn_star(&self) -> BigInt {
+        let mut rng = OsRng;
+        loop {
+            let r = rng.gen_bigint_range(&BigInt::one(), &self.n);
+            if r.gcd(&self.n) == BigInt::one() { return r; }
+        }
+    }
+
+    /// Recover i from a = (1 + n)^i mod n^(s+1), i in [0, n^s).
+    /// Round j lifts i mod n^(j-1) to i mod n^j by peeling the binomial terms
+    /// C(i, k) n^(k-1) for k = 2..j off L(a mod n^(j+1)).
+    pub fn dlog_one_plus_n(&self, a: &BigInt) -> BigInt {
+        let mut i = BigInt::zero();
+        let mut n_j = BigInt::one(); // n^j after the update below
+        for j in 1..=self.s {
+            n_j *= &self.n;
+            let n_j1 = &n_j * &self.n;
+            let mut t1 = (mod_positive(a.clone(), &n_j1) - BigInt::one()) / &self.n;
+            let mut t2 = i.clone();
+            let mut n_pow = BigInt::one(); // n^(k-1)
+            let mut k_fact = BigInt::one(); // k!
+            for k in 2..=j {
+                i -= BigInt::one();
+                t2 = mod_positive(&t2 * &i, &n_j);
+                n_pow *= &self.n;
+                k_fact *= BigInt::from(k);
+                let k_fact_inv = modinv(&k_fact, &n_j).expect("k! coprime to n for k <= s < min(p, q)");
+                t1 = mod_positive(t1 - &t2 * &n_pow * k_fact_inv, &n_j);
+            }
+            i = t1;
+        }
+        i
+    }
+}
+
+impl DjPrivateKey {
+    /// Decrypt c -> m in [0, n^s)
+    pub fn decrypt(&self, pk: &DjPublicKey, c: &DjCiphertext) -> Result<BigInt, DamgardJurikError> {
+        if c.0.sign() != Sign::Plus || c.0 >= pk.ns1 || c.0.gcd(&pk.n) != BigInt::one() {
+            return Err(DamgardJurikError::InvalidCiphertext);
+        }
+        // c^λ = (1 + n)^(m λ mod n^s) because r^(n^s λ) = 1 mod n^(s+1)
+        let a = modpow_bigint(&c.0, &self.lambda, &pk.ns1);
+        let m_lambda = pk.dlog_one_plus_n(&a);
+        Ok(mod_positive(m_lambda * &self.lambda_inv, &pk.ns))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::pailliar::Keypair;
+
+    #[test]
+    fn s1_matches_paillier_exactly() {
+        let kp = Keypair::generate(1024);
+        let dj = DjKeypair::from_paillier(&kp, 1).unwrap();
+        assert_eq!(dj.pk, DjPublicKey::from_paillier(&kp.pk));
+        assert_eq!(dj.pk.ns1, kp.pk.n2);
+
+        let mut rng = OsRng;
+        for _ in 0..5 {
+            let m = rng.gen_bigint_range(&BigInt::zero(), &kp.pk.n);
+            let r = dj.pk.sample_zn_star();
+            let c_p = kp.pk.encrypt_with_r(m.clone(), r.clone()).unwrap();
+            let c_dj = dj.pk.encrypt_with_r(m.clone(), r).unwrap();
+            assert_eq!(c_p.0, c_dj.0);
+
+            // cross-decrypt: either key opens the other's ciphertext
+            let as_dj = DjCiphertext(c_p.0.clone());
+            assert_eq!(dj.sk.decrypt(&dj.pk, &as_dj).unwrap(), m);
+            assert_eq!(kp.sk.decrypt(&kp.pk, &c_p).unwrap(), m);
+
+            // homomorphic ops agree too
+            let k = rng.gen_bigint_range(&BigInt::zero(), &kp.pk.n);
+            assert_eq!(kp.pk.mul_scalar(&c_p, k.clone()).0, dj.pk.mul_scalar(&c_dj, k.clone()).0);
+            assert_eq!(kp.pk.add(&c_p, &c_p).0, dj.pk.add(&c_dj, &c_dj).0);
+            assert_eq!(kp.pk.add_plain(&c_p, k.clone()).unwrap().0, dj.pk.add_plain(&c_dj, k).unwrap().0);
+        }
+    }
+
+    #[test]
+    fn roundtrip_large_plaintexts() {
+        for s in [2u32, 3, 4] {
+            let kp = DjKeypair::generate(512, s).unwrap();
+            let pk = &kp.pk;
+            let mut rng = OsRng;
+            let edge = [BigInt::zero(), BigInt::one(), &pk.ns - BigInt::one()];
+            for m in edge.iter().cloned().chain((0..3).map(|_| rng.gen_bigint_range(&pk.n, &pk.ns))) {
+                let c = pk.encrypt(m.clone()).unwrap();
+                assert_eq!(kp.sk.decrypt(pk, &c).unwrap(), m, "s = {}", s);
+            }
+        }
+    }
+
+    #[test]
+    fn homomorphic_ops_and_rerandomize() {
+        let kp = DjKeypair::generate(512, 3).unwrap();
+        let pk = &kp.pk;
+        let mut rng = OsRng;
+        let m1 = rng.gen_bigint_range(&BigInt::zero(), &pk.ns);
+        let m2 = rng.gen_bigint_range(&BigInt::zero(), &pk.ns);
+        let k = rng.gen_bigint_range(&BigInt::zero(), &pk.ns);
+        let c1 = pk.encrypt(m1.clone()).unwrap();
+        let c2 = pk.encrypt(m2.clone()).unwrap();
+
+        let sum = pk.add(&c1, &c2);
+        assert_eq!(kp.sk.decrypt(pk, &sum).unwrap(), (&m1 + &m2) % &pk.ns);
+        let plus = pk.add_plain(&c1, m2.clone()).unwrap();
+        assert_eq!(kp.sk.decrypt(pk, &plus).unwrap(), (&m1 + &m2) % &pk.ns);
+        let scaled = pk.mul_scalar(&c1, k.clone());
+        assert_eq!(kp.sk.decrypt(pk, &scaled).unwrap(), (&m1 * &k) % &pk.ns);
+        // negative scalars: Enc(-m1), and -k·m1 mod n^s
+        let negated = pk.mul_scalar(&c1, -1);
+        assert_eq!(kp.sk.decrypt(pk, &negated).unwrap(), (-&m1).mod_floor(&pk.ns));
+        let scaled = pk.mul_scalar(&c1, -&k);
+        assert_eq!(kp.sk.decrypt(pk, &scaled).unwrap(), (-&m1 * &k).mod_floor(&pk.ns));
+
+        let rr = pk.rerandomize(&c1);
+        assert_ne!(rr, c1);
+        assert_eq!(kp.sk.decrypt(pk, &rr).unwrap(), m1);
+    }
+
+    #[test]
+    fn dlog_inverts_g_pow() {
+        let pk = DjKeypair::generate(512, 4).unwrap().pk;
+        let mut rng = OsRng;
+        for _ in 0..5 {
+            let i = rng.gen_bigint_range(&BigInt::zero(), &pk.ns);
+            assert_eq!(pk.dlog_one_plus_n(&pk.g_pow(&i)), i);
+            assert_eq!(pk.g_pow(&i), modpow_bigint(&(&pk.n + BigInt::one()), &i, &pk.ns1));
+        }
+    }
+
+    #[test]
+    fn rejects_bad_inputs() {
+        let kp = DjKeypair::generate(512, 2).unwrap();
+        assert!(matches!(kp.pk.encrypt(kp.pk.ns.clone()), Err(DamgardJurikError::MessageOutOfRange)));
+        assert!(matches!(kp.pk.encrypt(BigInt::from(-1)), Err(DamgardJurikError::MessageOutOfRange)));
+        assert!(matches!(DjPublicKey::new(kp.pk.n.clone(), 0), Err(DamgardJurikError::InvalidS)));
+        let bad = DjCiphertext(kp.pk.n.clone());
+        assert!(matches!(kp.sk.decrypt(&kp.pk, &bad), Err(DamgardJurikError::InvalidCiphertext)));
+    }
+}
@@ -0,0 +1,546 @@
+// src/group/secp256k1/6-threshold_paillier.rs
+// -----------------------------------------------------------------------------
+// Threshold Paillier decryption (Shoup / Damgård–Jurik style, s = 1)
+// -----------------------------------------------------------------------------
+// The decryption exponent d = λ·μ satisfies d ≡ 0 (mod λ) and d ≡ 1 (mod n),
+// so c^d = (1 + n)^m for any ciphertext c = (1 + n)^m r^n. Instead of handing
+// d to one party, it is Shamir-shared among l validators; any t+1 of them can
+// decrypt, t or fewer learn nothing.
+//
+// Party i holds s_i = f(i) with f(0) = d and publishes the verification key
+// v_i = v^(Δ s_i), where Δ = l! and v generates the squares mod n^2. To
+// decrypt c it sends
+//
+//     c_i = c^(2 Δ s_i)  mod n^2
+//
+// with a proof that log_{c^4}(c_i^2) = log_v(v_i). The combiner keeps only
+// shares whose proofs verify and, for a set S of t+1 of them, computes
+//
+//     c' = prod_{i in S} c_i^(2 μ_i) = c^(4 Δ^2 d) = (1 + n)^(4 Δ^2 m),
+//     μ_i = Δ · prod_{j in S, j != i} j / (j - i)   (an integer),
+//
+// so m = L(c') · (4 Δ^2)^{-1} mod n.
+//
+// Two ways to produce the shares:
+// - `dealer_keygen` / `deal`: a trusted dealer picks f over Z_{nλ} (λ·n is the
+//   exponent of Z*_{n^2}) and then forgets everything.
+// - Distributed dealing: the key owner only splits d additively into l pieces
+//   and forgets the key; each validator then Shamir-shares its own piece over
+//   the integers with Feldman-style commitments in <v>, and every validator's
+//   final share is the sum of what it received. No single party ever holds
+//   the sharing polynomial. Generating the modulus itself without a trusted
+//   party (distributed biprime generation) is not covered here.
+//
+// p and q should be safe primes so the squares mod n^2 form a cyclic group and
+// the share proofs are sound; `dealer_keygen` takes care of that.
+// -----------------------------------------------------------------------------
+
+use num_bigint::{BigInt, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use std::collections::BTreeSet;
+use thiserror::Error;
+use crate::group::utils::montgomery::{modpow_bigint, modpow_signed};
+use crate::group::utils::primes::random_safe_prime_with_rng;
+use crate::group::utils::transcript::Transcript;
+use super::pailliar::{lcm, mod_positive, modinv, Ciphertext, Keypair, PublicKey};
+
+// statistical hiding slack for integer sharings and proof responses
+const STAT_BITS: u64 = 128;
+// Fiat–Shamir challenge length
+const CHALLENGE_BITS: u64 = 256;
+
+#[derive(Error, Debug)]
+pub enum ThresholdError {
+    #[error("invalid threshold parameters (need 1 <= t < l)")]
+    InvalidParameters,
+    #[error("share index {0} out of range")]
+    InvalidIndex(usize),
+    #[error("not enough valid decryption shares: need {needed}, got {got}")]
+    NotEnoughShares { needed: usize, got: usize },
+    #[error("dealing from party {0} failed verification")]
+    InvalidDealing(usize),
+    #[error("invalid ciphertext")]
+    InvalidCiphertext,
+}
+
+/// Public data every validator and the combiner need
+#[derive(Debug, Clone)]
+pub struct ThresholdPublicKey {
+    pub pk: PublicKey,
+    pub t: usize,         // any t+1 shares decrypt
+    pub l: usize,         // number of parties, indices 1..=l
+    pub delta: BigInt,    // Δ = l!
+    pub v: BigInt,        // generator of the squares mod n^2
+    pub vks: Vec<BigInt>, // vks[i-1] = v^(Δ s_i) mod n^2
+}
+
+/// Secret share s_i of d = λμ held by party `index`
+#[derive(Debug)]
+pub struct KeyShare {
+    pub index: usize,
+    s_i: BigInt,
+}
+
+impl Drop for KeyShare {
+    fn drop(&mut self) {
+        // best-effort zeroization, same caveats as pailliar::PrivateKey
+        self.s_i = BigInt::zero();
+    }
+}
+
+/// Proof that log_{c^4}(c_i^2) = log_v(v_i), as (challenge, response)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareProof {
+    pub e: BigInt,
+    pub z: BigInt,
+}
+
+/// Decryption share c_i = c^(2 Δ s_i) from party `index`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialDecryption {
+    pub index: usize,
+    pub c_i: BigInt,
+    pub proof: ShareProof,
+}
+
+/// Trusted-dealer key generation with safe primes of bits_n / 2 bits each
+pub fn dealer_keygen(bits_n: usize, t: usize, l: usize) -> Result<(ThresholdPublicKey, Vec<KeyShare>), ThresholdError> {
+    dealer_keygen_with_rng(bits_n, t, l, &mut OsRng)
+}
+
+/// As `dealer_keygen`, drawing the safe primes from `rng`
+pub fn dealer_keygen_with_rng(bits_n: usize, t: usize, l: usize, rng: &mut dyn RngCore) -> Result<(ThresholdPublicKey, Vec<KeyShare>), ThresholdError> {
+    check_params(t, l)?;
+    let half = bits_n / 2;
+    let p = random_safe_prime_with_rng(half, rng);
+    let mut q = random_safe_prime_with_rng(half, rng);
+    while p == q { q = random_safe_prime_with_rng(half, rng); }
+    deal(&Keypair::from_primes(p, q), t, l)
+}
+
+/// Trusted dealer: Shamir-share d = λμ of an existing key over Z_{nλ}
+pub fn deal(kp: &Keypair, t: usize, l: usize) -> Result<(ThresholdPublicKey, Vec<KeyShare>), ThresholdError> {
+    check_params(t, l)?;
+    let pk = kp.pk.clone();
+    let (d, n_lambda) = decryption_exponent(kp);
+
+    let mut rng = OsRng;
+    let mut coeffs = vec![d];
+    for _ in 0..t {
+        coeffs.push(rng.gen_bigint_range(&BigInt::zero(), &n_lambda));
+    }
+    let shares: Vec<KeyShare> = (1..=l)
+        .map(|i| KeyShare { index: i, s_i: mod_positive(eval_poly(&coeffs, i), &n_lambda) })
+        .collect();
+    for c in coeffs.iter_mut() { *c = BigInt::zero(); }
+
+    let delta = factorial(l);
+    let v = sample_square(&pk);
+    let vks = shares.iter().map(|s| modpow_bigint(&v, &(&delta * &s.s_i), &pk.n2)).collect();
+    Ok((ThresholdPublicKey { pk, t, l, delta, v, vks }, shares))
+}
+
+impl KeyShare {
+    /// Compute c_i = c^(2 Δ s_i) and prove it consistent with v_i
+    pub fn partial_decrypt(&self, tpk: &ThresholdPublicKey, c: &Ciphertext) -> Result<PartialDecryption, ThresholdError> {
+        check_ciphertext(&tpk.pk, c)?;
+        let n2 = &tpk.pk.n2;
+        let witness = &tpk.delta * &self.s_i; // Δ s_i
+        let c_i = modpow_bigint(&c.0, &(&witness * 2), n2);
+
+        // Schnorr-style equality of discrete logs in an unknown-order group:
+        // r must hide e·Δ s_i statistically, so it is CHALLENGE + STAT bits longer
+        let c4 = modpow_bigint(&c.0, &BigInt::from(4), n2);
+        let ci2 = mod_positive(&c_i * &c_i, n2);
+        let mut rng = OsRng;
+        let r = rng.gen_bigint_range(&BigInt::zero(), &(BigInt::one() << (witness.bits() + CHALLENGE_BITS + STAT_BITS)));
+        let a = modpow_bigint(&c4, &r, n2);
+        let b = modpow_bigint(&tpk.v, &r, n2);
+        let e = share_challenge(tpk, self.index, &c4, &ci2, &a, &b);
+        let z = r + &e * witness;
+        Ok(PartialDecryption { index: self.index, c_i, proof: ShareProof { e, z } })
+    }
+}
+
+impl ThresholdPublicKey {
+    /// Check the proof attached to a decryption share of c
+    pub fn verify_partial(&self, c: &Ciphertext, partial: &PartialDecryption) -> bool {
+        let i = partial.index;
+        if i == 0 || i > self.l || check_ciphertext(&self.pk, c).is_err() {
+            return false;
+        }
+        let n2 = &self.pk.n2;
+        let vi = &self.vks[i - 1];
+        let ShareProof { e, z } = &partial.proof;
+        if z.sign() == Sign::Minus || e.sign() == Sign::Minus || e.bits() > CHALLENGE_BITS {
+            return false;
+        }
+        let c4 = modpow_bigint(&c.0, &BigInt::from(4), n2);
+        let ci2 = mod_positive(&partial.c_i * &partial.c_i, n2);
+        // a = c4^z / ci2^e, b = v^z / v_i^e
+        let (Some(ci2_inv), Some(vi_inv)) = (modinv(&ci2, n2), modinv(vi, n2)) else {
+            return false;
+        };
+        let a = mod_positive(modpow_bigint(&c4, z, n2) * modpow_bigint(&ci2_inv, e, n2), n2);
+        let b = mod_positive(modpow_bigint(&self.v, z, n2) * modpow_bigint(&vi_inv, e, n2), n2);
+        share_challenge(self, i, &c4, &ci2, &a, &b) == *e
+    }
+
+    /// Combine decryption shares of c. Shares with bad proofs, unknown indices
+    /// or duplicate indices are skipped; the first t+1 valid ones are used.
+    pub fn combine(&self, c: &Ciphertext, partials: &[PartialDecryption]) -> Result<BigInt, ThresholdError> {
+        check_ciphertext(&self.pk, c)?;
+        let mut seen = BTreeSet::new();
+        let valid: Vec<&PartialDecryption> = partials
+            .iter()
+            .filter(|p| !seen.contains(&p.index) && self.verify_partial(c, p) && seen.insert(p.index))
+            .take(self.t + 1)
+            .collect();
+        if valid.len() < self.t + 1 {
+            return Err(ThresholdError::NotEnoughShares { needed: self.t + 1, got: valid.len() });
+        }
+
+        let n = &self.pk.n;
+        let n2 = &self.pk.n2;
+        let set: Vec<usize> = valid.iter().map(|p| p.index).collect();
+        let mut acc = BigInt::one();
+        for p in &valid {
+            let mu = self.lagrange_at_zero(p.index, &set);
+            let term = modpow_signed(&p.c_i, &(mu * 2), n2).ok_or(ThresholdError::InvalidCiphertext)?;
+            acc = mod_positive(acc * term, n2);
+        }
+        // acc = (1 + n)^(4 Δ^2 m)
+        let l_val = (acc - BigInt::one()) / n;
+        let four_delta2 = BigInt::from(4) * &self.delta * &self.delta;
+        let inv = modinv(&four_delta2, n).ok_or(ThresholdError::InvalidParameters)?;
+        Ok(mod_positive(l_val * inv, n))
+    }
+
+    // μ_i = Δ · prod_{j in S, j != i} j / (j - i), exact over the integers
+    fn lagrange_at_zero(&self, i: usize, set: &[usize]) -> BigInt {
+        let mut num = self.delta.clone();
+        let mut den = BigInt::one();
+        for &j in set.iter().filter(|&&j| j != i) {
+            num *= BigInt::from(j);
+            den *= BigInt::from(j as i64 - i as i64);
+        }
+        num / den
+    }
+}
+
+// -----------------------------
+// Distributed dealing
+// -----------------------------
+
+/// Public parameters of a distributed dealing, fixed by the key owner
+#[derive(Debug, Clone)]
+pub struct DistributedSetup {
+    pub pk: PublicKey,
+    pub t: usize,
+    pub l: usize,
+    pub v: BigInt,
+}
+
+/// Additive piece d_j of d = λμ (sum over all j equals d mod nλ)
+#[derive(Debug)]
+pub struct AdditivePiece {
+    pub index: usize,
+    value: BigInt,
+}
+
+impl Drop for AdditivePiece {
+    fn drop(&mut self) {
+        self.value = BigInt::zero();
+    }
+}
+
+/// One party's integer Shamir sharing of its piece, with Feldman commitments
+/// C_k = v^(a_k) to the coefficients. `shares[i-1]` goes privately to party i.
+#[derive(Debug, Clone)]
+pub struct Dealing {
+    pub from: usize,
+    pub commitments: Vec<BigInt>,
+    shares: Vec<BigInt>,
+}
+
+impl Dealing {
+    /// The sub-share addressed to party i (delivered over a private channel)
+    pub fn share_for(&self, i: usize) -> Option<&BigInt> {
+        if i == 0 { return None; }
+        self.shares.get(i - 1)
+    }
+
+    /// Party i checks v^(f_j(i)) = prod_k C_k^(i^k)
+    pub fn verify_share(&self, setup: &DistributedSetup, i: usize) -> bool {
+        match self.share_for(i) {
+            Some(share) if share.sign() != Sign::Minus => {
+                let n2 = &setup.pk.n2;
+                modpow_bigint(&setup.v, share, n2) == commitment_eval(&self.commitments, i, n2)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Key owner: split d = λμ into l additive pieces, one per party. The owner
+/// must discard the key afterwards; nobody learns more than their own piece.
+pub fn split_for_distributed_dealing(kp: &Keypair, t: usize, l: usize) -> Result<(DistributedSetup, Vec<AdditivePiece>), ThresholdError> {
+    check_params(t, l)?;
+    let (d, n_lambda) = decryption_exponent(kp);
+    let mut rng = OsRng;
+    let bound = &n_lambda << STAT_BITS;
+    let mut pieces: Vec<AdditivePiece> = (1..l)
+        .map(|j| AdditivePiece { index: j, value: rng.gen_bigint_range(&BigInt::zero(), &bound) })
+        .collect();
+    let sum: BigInt = pieces.iter().map(|p| &p.value).sum();
+    // last piece fixes the sum mod nλ and is padded to look like the others
+    let last = mod_positive(d - sum, &n_lambda) + &n_lambda * rng.gen_bigint_range(&BigInt::zero(), &(BigInt::one() << STAT_BITS));
+    pieces.push(AdditivePiece { index: l, value: last });
+
+    let pk = kp.pk.clone();
+    let v = sample_square(&pk);
+    Ok((DistributedSetup { pk, t, l, v }, pieces))
+}
+
+/// Party j: share its piece with a random degree-t integer polynomial
+pub fn deal_piece(setup: &DistributedSetup, piece: &AdditivePiece) -> Dealing {
+    let mut rng = OsRng;
+    let coeff_bound = BigInt::one() << (piece.value.bits() + STAT_BITS);
+    let mut coeffs = vec![piece.value.clone()];
+    for _ in 0..setup.t {
+        coeffs.push(rng.gen_bigint_range(&BigInt::zero(), &coeff_bound));
+    }
+    let n2 = &setup.pk.n2;
+    let commitments = coeffs.iter().map(|a| modpow_bigint(&setup.v, a, n2)).collect();
+    let shares = (1..=setup.l).map(|i| eval_poly(&coeffs, i)).collect();
+    for c in coeffs.iter_mut() { *c = BigInt::zero(); }
+    Dealing { from: piece.index, commitments, shares }
+}
+
+impl KeyShare {
+    /// Party i: check every dealing addressed to it and sum the sub-shares
+    pub fn from_dealings(setup: &DistributedSetup, i: usize, dealings: &[Dealing]) -> Result<KeyShare, ThresholdError> {
+        if i == 0 || i > setup.l { return Err(ThresholdError::InvalidIndex(i)); }
+        check_dealers(setup, dealings)?;
+        let mut s_i = BigInt::zero();
+        for d in dealings {
+            if !d.verify_share(setup, i) { return Err(ThresholdError::InvalidDealing(d.from)); }
+            s_i += d.share_for(i).expect("checked by verify_share");
+        }
+        Ok(KeyShare { index: i, s_i })
+    }
+}
+
+impl ThresholdPublicKey {
+    /// Anyone: derive the verification keys from the public commitments
+    pub fn from_dealings(setup: &DistributedSetup, dealings: &[Dealing]) -> Result<ThresholdPublicKey, ThresholdError> {
+        check_dealers(setup, dealings)?;
+        let n2 = &setup.pk.n2;
+        let delta = factorial(setup.l);
+        let vks = (1..=setup.l)
+            .map(|i| {
+                let v_si = dealings
+                    .iter()
+                    .fold(BigInt::one(), |acc, d| mod_positive(acc * commitment_eval(&d.commitments, i, n2), n2));
+                modpow_bigint(&v_si, &delta, n2)
+            })
+            .collect();
+        Ok(ThresholdPublicKey { pk: setup.pk.clone(), t: setup.t, l: setup.l, delta, v: setup.v.clone(), vks })
+    }
+}
+
+// -----------------------------
+// Helpers
+// -----------------------------
+fn check_params(t: usize, l: usize) -> Result<(), ThresholdError> {
+    if t == 0 || t >= l { return Err(ThresholdError::InvalidParameters); }
+    Ok(())
+}
+
+fn check_ciphertext(pk: &PublicKey, c: &Ciphertext) -> Result<(), ThresholdError> {
+    if c.0.sign() != Sign::Plus || c.0 >= pk.n2 || c.0.gcd(&pk.n) != BigInt::one() {
+        return Err(ThresholdError::InvalidCiphertext);
+    }
+    Ok(())
+}
+
+// every party 1..=l contributes exactly one well-formed dealing
+fn check_dealers(setup: &DistributedSetup, dealings: &[Dealing]) -> Result<(), ThresholdError> {
+    let from: BTreeSet<usize> = dealings.iter().map(|d| d.from).collect();
+    if from.len() != dealings.len() || from != (1..=setup.l).collect() {
+        return Err(ThresholdError::InvalidParameters);
+    }
+    if let Some(d) = dealings.iter().find(|d| d.commitments.len() != setup.t + 1 || d.shares.len() != setup.l) {
+        return Err(ThresholdError::InvalidDealing(d.from));
+    }
+    Ok(())
+}
+
+// (d = λμ, nλ) for a key with known factors
+fn decryption_exponent(kp: &Keypair) -> (BigInt, BigInt) {
+    let (p, q) = kp.sk.factors();
+    let n = &kp.pk.n;
+    let lambda = lcm(&(p - BigInt::one()), &(q - BigInt::one()));
+    let mu = modinv(&lambda, n).expect("lambda invertible mod n");
+    (&lambda * mu, n * lambda)
+}
+
+// random square in Z*_{n^2}; generates the squares w.h.p. for safe primes
+fn sample_square(pk: &PublicKey) -> BigInt {
+    let mut rng = OsRng;
+    loop {
+        let r = rng.gen_bigint_range(&BigInt::from(2), &pk.n2);
+        if r.gcd(&pk.n) == BigInt::one() {
+            return mod_positive(&r * &r, &pk.n2);
+        }
+    }
+}
+
+fn factorial(l: usize) -> BigInt {
+    (1..=l).fold(BigInt::one(), |acc, k| acc * BigInt::from(k))
+}
+
+fn eval_poly(coeffs: &[BigInt], x: usize) -> BigInt {
+    let x = BigInt::from(x);
+    coeffs.iter().rev().fold(BigInt::zero(), |acc, a| acc * &x + a)
+}
+
+// prod_k C_k^(i^k) mod n^2
+fn commitment_eval(commitments: &[BigInt], i: usize, n2: &BigInt) -> BigInt {
+    let mut acc = BigInt::one();
+    let mut i_k = BigInt::one();
+    for c in commitments {
+        acc = mod_positive(acc * modpow_bigint(c, &i_k, n2), n2);
+        i_k *= BigInt::from(i);
+    }
+    acc
+}
+
+fn share_challenge(tpk: &ThresholdPublicKey, i: usize, c4: &BigInt, ci2: &BigInt, a: &BigInt, b: &BigInt) -> BigInt {
+    let mut tr = Transcript::new(b"threshold-paillier/decryption-share");
+    tr.append_int(b"n", &tpk.pk.n);
+    tr.append_int(b"v", &tpk.v);
+    tr.append_u64(b"i", i as u64);
+    tr.append_int(b"v_i", &tpk.vks[i - 1]);
+    tr.append_int(b"c^4", c4);
+    tr.append_int(b"c_i^2", ci2);
+    tr.append_int(b"a", a);
+    tr.append_int(b"b", b);
+    tr.challenge_bits(b"e", CHALLENGE_BITS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group::utils::primes::random_safe_prime;
+
+    fn partials(tpk: &ThresholdPublicKey, shares: &[KeyShare], c: &Ciphertext) -> Vec<PartialDecryption> {
+        shares.iter().map(|s| s.partial_decrypt(tpk, c).unwrap()).collect()
+    }
+
+    #[test]
+    fn dealer_any_t_plus_one_subset_decrypts() {
+        let (tpk, shares) = dealer_keygen(512, 2, 5).unwrap();
+        let m = BigInt::from(123456789u64);
+        let c = tpk.pk.encrypt(m.clone()).unwrap();
+        let all = partials(&tpk, &shares, &c);
+        assert!(all.iter().all(|p| tpk.verify_partial(&c, p)));
+
+        for subset in [[0usize, 1, 2], [2, 3, 4], [0, 2, 4], [4, 1, 3]] {
+            let chosen: Vec<_> = subset.iter().map(|&k| all[k].clone()).collect();
+            assert_eq!(tpk.combine(&c, &chosen).unwrap(), m);
+        }
+        // t shares are not enough
+        assert!(matches!(
+            tpk.combine(&c, &all[..2]),
+            Err(ThresholdError::NotEnoughShares { needed: 3, got: 2 })
+        ));
+    }
+
+    #[test]
+    fn dishonest_partials_are_rejected() {
+        let (tpk, shares) = dealer_keygen(512, 1, 4).unwrap();
+        let m = BigInt::from(4242u32);
+        let c = tpk.pk.encrypt(m.clone()).unwrap();
+        let mut all = partials(&tpk, &shares, &c);
+
+        // party 1 sends a wrong share with its old proof
+        all[0].c_i = mod_positive(&all[0].c_i * BigInt::from(4), &tpk.pk.n2);
+        // party 2 claims to be party 3
+        all[1].index = 3;
+        // party 3 tampers with its response
+        all[2].proof.z += BigInt::one();
+        for p in &all[..3] {
+            assert!(!tpk.verify_partial(&c, p));
+        }
+        // only party 4 is honest: one valid share is not enough for t = 1
+        assert!(matches!(tpk.combine(&c, &all), Err(ThresholdError::NotEnoughShares { needed: 2, got: 1 })));
+
+        // party 1's honest share plus party 4 suffices despite the noise
+        let honest1 = shares[0].partial_decrypt(&tpk, &c).unwrap();
+        all.push(honest1);
+        assert_eq!(tpk.combine(&c, &all).unwrap(), m);
+    }
+
+    #[test]
+    fn duplicate_shares_count_once() {
+        let (tpk, shares) = dealer_keygen(512, 2, 3).unwrap();
+        let c = tpk.pk.encrypt(7u32).unwrap();
+        let p1 = shares[0].partial_decrypt(&tpk, &c).unwrap();
+        let p2 = shares[1].partial_decrypt(&tpk, &c).unwrap();
+        let dup = vec![p1.clone(), p1, p2.clone(), p2];
+        assert!(matches!(tpk.combine(&c, &dup), Err(ThresholdError::NotEnoughShares { needed: 3, got: 2 })));
+    }
+
+    #[test]
+    fn threshold_decrypts_homomorphic_results() {
+        let (tpk, shares) = dealer_keygen(512, 1, 3).unwrap();
+        let pk = &tpk.pk;
+        let c1 = pk.encrypt(40u32).unwrap();
+        let c2 = pk.encrypt(2u32).unwrap();
+        let c = pk.mul_scalar(&pk.add(&c1, &c2), 10u32);
+        let ps = partials(&tpk, &shares[1..], &c);
+        assert_eq!(tpk.combine(&c, &ps).unwrap(), BigInt::from(420));
+    }
+
+    #[test]
+    fn distributed_dealing_decrypts() {
+        let kp = Keypair::from_primes(random_safe_prime(256), random_safe_prime(256));
+        let m = BigInt::from(31337u32);
+        let c = kp.pk.encrypt(m.clone()).unwrap();
+
+        let (setup, pieces) = split_for_distributed_dealing(&kp, 2, 4).unwrap();
+        let dealings: Vec<Dealing> = pieces.iter().map(|p| deal_piece(&setup, p)).collect();
+        let tpk = ThresholdPublicKey::from_dealings(&setup, &dealings).unwrap();
+        let shares: Vec<KeyShare> = (1..=4).map(|i| KeyShare::from_dealings(&setup, i, &dealings).unwrap()).collect();
+
+        let ps = partials(&tpk, &shares, &c);
+        assert_eq!(tpk.combine(&c, &ps[1..]).unwrap(), m);
+        assert_eq!(tpk.combine(&c, &[ps[3].clone(), ps[0].clone(), ps[2].clone()]).unwrap(), m);
+    }
+
+    #[test]
+    fn distributed_dealing_detects_bad_subshare() {
+        let kp = Keypair::from_primes(random_safe_prime(256), random_safe_prime(256));
+        let (setup, pieces) = split_for_distributed_dealing(&kp, 1, 3).unwrap();
+        let mut dealings: Vec<Dealing> = pieces.iter().map(|p| deal_piece(&setup, p)).collect();
+        dealings[1].shares[2] += BigInt::one(); // dealer 2 cheats party 3
+        assert!(dealings[1].verify_share(&setup, 1));
+        assert!(!dealings[1].verify_share(&setup, 3));
+        assert!(matches!(KeyShare::from_dealings(&setup, 3, &dealings), Err(ThresholdError::InvalidDealing(2))));
+        assert!(matches!(
+            ThresholdPublicKey::from_dealings(&setup, &dealings[..2]),
+            Err(ThresholdError::InvalidParameters)
+        ));
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        let kp = Keypair::generate(512);
+        assert!(matches!(deal(&kp, 0, 3), Err(ThresholdError::InvalidParameters)));
+        assert!(matches!(deal(&kp, 3, 3), Err(ThresholdError::InvalidParameters)));
+    }
+}
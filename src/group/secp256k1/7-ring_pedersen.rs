@@ -0,0 +1,304 @@
+// src/group/secp256k1/7-ring_pedersen.rs
+// -----------------------------------------------------------------------------
+// Ring-Pedersen commitments (CGGMP21 §1.2 / Definition 1.3) and Π^prm (Fig. 17)
+// -----------------------------------------------------------------------------
+// Public parameters (N̂, s, t) with N̂ = p·q a product of safe primes, t a
+// random square mod N̂ and s = t^λ for a secret λ. A commitment to x with
+// randomness y is
+//
+//     C = s^x · t^y  mod N̂
+//
+// It is perfectly hiding when s and t generate the same group, and binding as
+// long as nobody knows both the factorization of N̂ and λ. The CGGMP-style
+// Paillier proofs use it to commit to integers that are too large or too
+// structured to handle directly. Each party publishes its own parameters;
+// a party proves statements against the *verifier's* parameters.
+//
+// Π^prm — the owner proves s ∈ ⟨t⟩ by showing knowledge of λ = log_t(s) in
+// PRM_ROUNDS binary-challenge rounds: A_i = t^a_i, z_i = a_i + e_i·λ mod φ(N̂),
+// and the verifier checks t^z_i = A_i · s^e_i. With safe primes QR_N̂ is
+// cyclic of order p'q', so a random square t generates it except with
+// negligible probability.
+// -----------------------------------------------------------------------------
+
+use num_bigint::{BigInt, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use thiserror::Error;
+use crate::group::utils::codec::{CodecError, Reader, Writer};
+use crate::group::utils::montgomery::{modpow_bigint, modpow_signed};
+use crate::group::utils::primes::{is_prime, random_safe_prime_with_rng};
+use crate::group::utils::transcript::Transcript;
+use super::pailliar::mod_positive;
+
+/// Number of Π^prm rounds; soundness error 2^-PRM_ROUNDS
+pub const PRM_ROUNDS: usize = 80;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum RingPedersenError {
+    #[error("p and q must be distinct safe primes")]
+    NotSafePrimes,
+    #[error("modulus has {bits} bits, expected at least {min}")]
+    ModulusTooSmall { bits: u64, min: u64 },
+    #[error("s or t is not a valid element mod N̂")]
+    InvalidElement,
+    #[error("Π^prm proof did not verify")]
+    InvalidProof,
+}
+
+/// Public ring-Pedersen parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingPedersenParams {
+    pub n_hat: BigInt,
+    pub s: BigInt,
+    pub t: BigInt,
+}
+
+/// Trapdoor of the parameters: s = t^λ and φ(N̂)
+#[derive(Debug)]
+pub struct RingPedersenSecret {
+    lambda: BigInt,
+    phi: BigInt,
+}
+
+impl Drop for RingPedersenSecret {
+    fn drop(&mut self) {
+        self.lambda = BigInt::zero();
+        self.phi = BigInt::zero();
+    }
+}
+
+/// Non-interactive proof that s ∈ ⟨t⟩ (Π^prm)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrmProof {
+    pub a: Vec<BigInt>,
+    pub z: Vec<BigInt>,
+}
+
+impl RingPedersenParams {
+    /// Fresh parameters over a `bits`-bit product of safe primes
+    pub fn generate(bits: usize) -> (Self, RingPedersenSecret) {
+        Self::generate_with_rng(bits, &mut OsRng)
+    }
+
+    /// As `generate`, drawing the safe primes from `rng`
+    pub fn generate_with_rng(bits: usize, rng: &mut dyn RngCore) -> (Self, RingPedersenSecret) {
+        let half = bits / 2;
+        let p = random_safe_prime_with_rng(half, rng);
+        let mut q = random_safe_prime_with_rng(half, rng);
+        while p == q { q = random_safe_prime_with_rng(half, rng); }
+        Self::from_safe_primes(&p, &q).expect("freshly generated safe primes")
+    }
+
+    /// Parameters over N̂ = p·q for caller-supplied safe primes
+    pub fn from_safe_primes(p: &BigInt, q: &BigInt) -> Result<(Self, RingPedersenSecret), RingPedersenError> {
+        if p == q || !is_safe_prime(p) || !is_safe_prime(q) {
+            return Err(RingPedersenError::NotSafePrimes);
+        }
+        let n_hat = p * q;
+        let phi = (p - BigInt::one()) * (q - BigInt::one());
+
+        let mut rng = OsRng;
+        let r = loop {
+            let r = rng.gen_bigint_range(&BigInt::from(2), &n_hat);
+            if r.gcd(&n_hat).is_one() { break r; }
+        };
+        let t = mod_positive(&r * &r, &n_hat);
+        let lambda = rng.gen_bigint_range(&BigInt::one(), &phi);
+        let s = modpow_bigint(&t, &lambda, &n_hat);
+        Ok((RingPedersenParams { n_hat, s, t }, RingPedersenSecret { lambda, phi }))
+    }
+
+    /// C = s^x t^y mod N̂; x and y may be negative
+    pub fn commit(&self, x: &BigInt, y: &BigInt) -> BigInt {
+        let sx = modpow_signed(&self.s, x, &self.n_hat).expect("s is a unit mod N̂");
+        let ty = modpow_signed(&self.t, y, &self.n_hat).expect("t is a unit mod N̂");
+        mod_positive(sx * ty, &self.n_hat)
+    }
+
+    /// Π^prm: prove knowledge of λ with s = t^λ
+    pub fn prove(&self, secret: &RingPedersenSecret) -> PrmProof {
+        let mut rng = OsRng;
+        let a_secret: Vec<BigInt> = (0..PRM_ROUNDS).map(|_| rng.gen_bigint_range(&BigInt::zero(), &secret.phi)).collect();
+        let a: Vec<BigInt> = a_secret.iter().map(|ai| modpow_bigint(&self.t, ai, &self.n_hat)).collect();
+        let e = self.prm_challenge(&a);
+        let z = a_secret
+            .into_iter()
+            .zip(e)
+            .map(|(ai, ei)| if ei { (ai + &secret.lambda).mod_floor(&secret.phi) } else { ai })
+            .collect();
+        PrmProof { a, z }
+    }
+
+    /// Check a peer's published parameters before using them in any proof
+    pub fn verify_peer(&self, proof: &PrmProof, min_bits: u64) -> Result<(), RingPedersenError> {
+        let n_hat = &self.n_hat;
+        if n_hat.bits() < min_bits {
+            return Err(RingPedersenError::ModulusTooSmall { bits: n_hat.bits(), min: min_bits });
+        }
+        if n_hat.is_even() {
+            return Err(RingPedersenError::InvalidElement);
+        }
+        for x in [&self.s, &self.t] {
+            if x <= &BigInt::one() || x >= n_hat || !x.gcd(n_hat).is_one() {
+                return Err(RingPedersenError::InvalidElement);
+            }
+        }
+        if proof.a.len() != PRM_ROUNDS || proof.z.len() != PRM_ROUNDS {
+            return Err(RingPedersenError::InvalidProof);
+        }
+        let e = self.prm_challenge(&proof.a);
+        for ((ai, zi), ei) in proof.a.iter().zip(&proof.z).zip(e) {
+            if ai.sign() != Sign::Plus || ai >= n_hat || zi.sign() == Sign::Minus || zi >= n_hat {
+                return Err(RingPedersenError::InvalidProof);
+            }
+            let rhs = if ei { mod_positive(ai * &self.s, n_hat) } else { ai.clone() };
+            if modpow_bigint(&self.t, zi, n_hat) != rhs {
+                return Err(RingPedersenError::InvalidProof);
+            }
+        }
+        Ok(())
+    }
+
+    fn prm_challenge(&self, a: &[BigInt]) -> Vec<bool> {
+        let mut tr = Transcript::new(b"cggmp21/pi-prm");
+        tr.append_int(b"N^", &self.n_hat);
+        tr.append_int(b"s", &self.s);
+        tr.append_int(b"t", &self.t);
+        for ai in a {
+            tr.append_int(b"A", ai);
+        }
+        let e = tr.challenge_bits(b"e", PRM_ROUNDS as u64);
+        (0..PRM_ROUNDS as u64).map(|i| e.bit(i)).collect()
+    }
+}
+
+impl PrmProof {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+        for x in self.a.iter().chain(&self.z) {
+            w.put_int(x);
+        }
+        w.finish()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        let mut r = Reader::new(bytes);
+        let a = (0..PRM_ROUNDS).map(|_| r.get_int()).collect::<Result<_, _>>()?;
+        let z = (0..PRM_ROUNDS).map(|_| r.get_int()).collect::<Result<_, _>>()?;
+        r.finish()?;
+        Ok(PrmProof { a, z })
+    }
+}
+
+fn is_safe_prime(p: &BigInt) -> bool {
+    let q: BigInt = (p - BigInt::one()) >> 1;
+    p.is_odd() && is_prime(&q) && is_prime(p)
+}
+
+// 512-bit safe primes, so tests do not pay for safe-prime generation
+#[cfg(test)]
+const TEST_SAFE_PRIMES: [&str; 6] = [
+    concat!(
+        "F9CDEBFB4FEC8523DA4019329D31C25D85F71EB314A0FBC14F2034DCBF4A168E",
+        "EE8CBB3C2BDC4EE5050DBB384056491E1C05200CC2D1B9FB779EE882EC2AC65B",
+    ),
+    concat!(
+        "EF21B585F4D2719992BF94579FDAAF2FCF858D99F78DDBF4EBAEDCD3DFE135D5",
+        "C544D055865FDD5ACED580194356BCF2964A4913A61A2761038589973765B667",
+    ),
+    concat!(
+        "DDCAA626A4BA259E60FC0BA3C1B438DB847EA7701B80BC723CFC517BC98F8D84",
+        "D0B7CA027F723DFAAD0A73D1C6690BFFEB025ABCC2993073E255F20DFAB69503",
+    ),
+    concat!(
+        "C2325AA37F34EA4D6C9A528D560A30237FE8778DA270710F738C40DC6E106CBA",
+        "48E21E0895528508E6C009E840157A1928A6B8079176095346EC6D79D32BBA1B",
+    ),
+    concat!(
+        "8A89FBBA998D377D1DC052FBF4A0A37AA6B2FF826FA9E454A7AA338DDDE2A7A1",
+        "0BFD32DF9897B53B157B5D2AC720C13C8D1D8A55BD3794D88EE6CE5B6305041B",
+    ),
+    concat!(
+        "97C6EE3C21CC9378B9843BD7C71B158E66DA56B5AE7E530E415937B197B335B8",
+        "3AC6420A5ABB1A7DB1420A3765AB9FCD50835B46675277318328ABEDE1AE936F",
+    ),
+];
+
+/// Parameters over one of three fixed 1024-bit test moduli (i in 0..3)
+#[cfg(test)]
+pub(crate) fn test_params(i: usize) -> (RingPedersenParams, RingPedersenSecret) {
+    let p = BigInt::parse_bytes(TEST_SAFE_PRIMES[2 * i].as_bytes(), 16).unwrap();
+    let q = BigInt::parse_bytes(TEST_SAFE_PRIMES[2 * i + 1].as_bytes(), 16).unwrap();
+    RingPedersenParams::from_safe_primes(&p, &q).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group::utils::primes::random_prime;
+
+    #[test]
+    fn commitments_are_homomorphic() {
+        let (rp, sk) = test_params(0);
+        assert_eq!(modpow_bigint(&rp.t, &sk.lambda, &rp.n_hat), rp.s);
+        let (x1, y1) = (BigInt::from(-1234), BigInt::from(99));
+        let (x2, y2) = (BigInt::from(5678), BigInt::from(-7));
+        let c = mod_positive(rp.commit(&x1, &y1) * rp.commit(&x2, &y2), &rp.n_hat);
+        assert_eq!(c, rp.commit(&(x1 + x2), &(y1 + y2)));
+        // with the trapdoor a commitment opens to anything: s^x t^y = t^(λx + y)
+        let x = BigInt::from(42);
+        assert_eq!(rp.commit(&x, &BigInt::zero()), rp.commit(&BigInt::zero(), &(&sk.lambda * &x)));
+        assert!(sk.phi > BigInt::zero());
+    }
+
+    #[test]
+    fn generate_uses_safe_primes_and_proves() {
+        let (rp, sk) = RingPedersenParams::generate(512);
+        assert!(rp.n_hat.bits() >= 511); // product of two 256-bit primes
+        assert!(sk.phi.is_multiple_of(&BigInt::from(4)));
+        let proof = rp.prove(&sk);
+        assert_eq!(rp.verify_peer(&proof, 511), Ok(()));
+    }
+
+    #[test]
+    fn prm_proof_roundtrip_and_rejections() {
+        let (rp, sk) = test_params(0);
+        let proof = rp.prove(&sk);
+        assert_eq!(rp.verify_peer(&proof, 1024), Ok(()));
+        assert_eq!(PrmProof::from_bytes(&proof.to_bytes()).unwrap(), proof);
+        assert_eq!(
+            rp.verify_peer(&proof, 2048),
+            Err(RingPedersenError::ModulusTooSmall { bits: 1024, min: 2048 })
+        );
+
+        // proof for other parameters
+        let (rp2, _) = test_params(1);
+        assert_eq!(rp2.verify_peer(&proof, 1024), Err(RingPedersenError::InvalidProof));
+
+        // s outside ⟨t⟩: t is a square, -1 is not, so s = -t is not a power of t
+        let bad = RingPedersenParams { s: mod_positive(-&rp.t, &rp.n_hat), ..rp.clone() };
+        let forged = bad.prove(&sk);
+        assert_eq!(bad.verify_peer(&forged, 1024), Err(RingPedersenError::InvalidProof));
+
+        // degenerate elements
+        let bad = RingPedersenParams { s: BigInt::one(), ..rp.clone() };
+        assert_eq!(bad.verify_peer(&proof, 1024), Err(RingPedersenError::InvalidElement));
+
+        let mut tampered = proof.clone();
+        tampered.z[5] += 1;
+        assert_eq!(rp.verify_peer(&tampered, 1024), Err(RingPedersenError::InvalidProof));
+        tampered.z.pop();
+        assert_eq!(rp.verify_peer(&tampered, 1024), Err(RingPedersenError::InvalidProof));
+    }
+
+    #[test]
+    fn rejects_non_safe_primes() {
+        let (p, q) = (random_prime(256), random_prime(256));
+        assert!(matches!(RingPedersenParams::from_safe_primes(&p, &q), Err(RingPedersenError::NotSafePrimes)));
+        let s = BigInt::parse_bytes(TEST_SAFE_PRIMES[0].as_bytes(), 16).unwrap();
+        assert!(matches!(RingPedersenParams::from_safe_primes(&s, &s), Err(RingPedersenError::NotSafePrimes)));
+    }
+}
@@ -0,0 +1,428 @@
+// src/group/secp256k1/8-modulus_proofs.rs
+// -----------------------------------------------------------------------------
+// Proofs that a Paillier modulus is well formed (CGGMP21, Fig. 16 and Fig. 26)
+// -----------------------------------------------------------------------------
+// Anyone who encrypts under someone else's Paillier key (MtA, Lindell'17)
+// must know that N really is a product of two suitable primes, otherwise the
+// key owner can learn more than the protocol intends.
+//
+// Π^mod — N is a Paillier-Blum integer: N = p·q with p ≡ q ≡ 3 (mod 4) and
+// gcd(N, φ(N)) = 1. The prover publishes w with Jacobi symbol (w/N) = -1 and,
+// for each of M challenges y_i, answers
+//
+//     z_i = y_i^(N^{-1} mod φ(N))              (so z_i^N = y_i)
+//     x_i = ((-1)^a_i · w^b_i · y_i)^(1/4)     (a_i, b_i make it a QR)
+//
+// Only a Blum integer with two prime factors has a fourth root for one of the
+// four twists of every y_i; anything else fails each round with probability
+// at least 1/2.
+//
+// Π^fac — no small factor: p, q > 2^(-ℓ-ε)·√N (roughly). The prover commits to
+// p and q under the verifier's ring-Pedersen parameters (N̂, s, t) and shows
+// that the committed values multiply to N and are bounded by √N·2^(ℓ+ε).
+//
+// Both are made non-interactive with a Fiat–Shamir transcript over all public
+// inputs.
+// -----------------------------------------------------------------------------
+
+use num_bigint::{BigInt, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Signed};
+use rand::rngs::OsRng;
+use thiserror::Error;
+use crate::group::utils::montgomery::{modpow_bigint, modpow_signed};
+use crate::group::utils::primes::is_prime;
+use crate::group::utils::quadratic::jacobi;
+use crate::group::utils::transcript::Transcript;
+use super::pailliar::{mod_positive, modinv, PrivateKey, PublicKey};
+use super::ring_pedersen::RingPedersenParams;
+
+/// Number of Π^mod rounds; soundness error 2^-MOD_ROUNDS
+pub const MOD_ROUNDS: usize = 80;
+
+#[derive(Error, Debug)]
+pub enum ModulusProofError {
+    #[error("modulus is not a product of two Blum primes")]
+    NotPaillierBlum,
+    #[error("gcd(N, φ(N)) != 1")]
+    NotCoprimeToPhi,
+}
+
+// -----------------------------
+// Π^mod
+// -----------------------------
+
+/// One Π^mod round: x^4 = (-1)^a w^b y and z^N = y (mod N)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModRound {
+    pub x: BigInt,
+    pub a: bool,
+    pub b: bool,
+    pub z: BigInt,
+}
+
+/// Non-interactive Paillier-Blum modulus proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModProof {
+    pub w: BigInt,
+    pub rounds: Vec<ModRound>,
+}
+
+impl ModProof {
+    /// Prove that pk.n is a Paillier-Blum modulus
+    pub fn prove(pk: &PublicKey, sk: &PrivateKey) -> Result<Self, ModulusProofError> {
+        let (p, q) = sk.factors();
+        Self::prove_with_factors(&pk.n, p, q)
+    }
+
+    fn prove_with_factors(n: &BigInt, p: &BigInt, q: &BigInt) -> Result<Self, ModulusProofError> {
+        let three = BigInt::from(3);
+        let four = BigInt::from(4);
+        if &(p * q) != n || p.mod_floor(&four) != three || q.mod_floor(&four) != three {
+            return Err(ModulusProofError::NotPaillierBlum);
+        }
+        let phi = (p - BigInt::one()) * (q - BigInt::one());
+        let n_inv = modinv(n, &phi).ok_or(ModulusProofError::NotCoprimeToPhi)?;
+
+        // w with (w/N) = -1: a QR mod exactly one of p, q
+        let mut rng = OsRng;
+        let w = loop {
+            let w = rng.gen_bigint_range(&BigInt::from(2), n);
+            if jacobi(&w, n) == -1 { break w; }
+        };
+
+        let ys = mod_challenges(n, &w);
+        let mut rounds = Vec::with_capacity(MOD_ROUNDS);
+        for y in ys {
+            let z = modpow_bigint(&y, &n_inv, n);
+            let mut found = None;
+            'twist: for a in [false, true] {
+                for b in [false, true] {
+                    let y_twist = twist(&y, a, b, &w, n);
+                    if let Some(x) = fourth_root(&y_twist, p, q) {
+                        found = Some(ModRound { x, a, b, z: z.clone() });
+                        break 'twist;
+                    }
+                }
+            }
+            rounds.push(found.ok_or(ModulusProofError::NotPaillierBlum)?);
+        }
+        Ok(ModProof { w, rounds })
+    }
+
+    /// Verify the proof against pk.n
+    pub fn verify(&self, pk: &PublicKey) -> bool {
+        self.verify_modulus(&pk.n)
+    }
+
+    fn verify_modulus(&self, n: &BigInt) -> bool {
+        let two = BigInt::from(2);
+        if n <= &two || n.is_even() || is_prime(n) {
+            return false;
+        }
+        if self.rounds.len() != MOD_ROUNDS || jacobi(&self.w, n) != -1 {
+            return false;
+        }
+        let ys = mod_challenges(n, &self.w);
+        let four = BigInt::from(4);
+        self.rounds.iter().zip(ys).all(|(r, y)| {
+            r.x.sign() == Sign::Plus && &r.x < n && r.z.sign() == Sign::Plus && &r.z < n
+                && modpow_bigint(&r.z, n, n) == y
+                && modpow_bigint(&r.x, &four, n) == twist(&y, r.a, r.b, &self.w, n)
+        })
+    }
+}
+
+// (-1)^a · w^b · y mod N
+fn twist(y: &BigInt, a: bool, b: bool, w: &BigInt, n: &BigInt) -> BigInt {
+    let mut v = y.clone();
+    if b { v = mod_positive(v * w, n); }
+    if a { v = mod_positive(-v, n); }
+    v
+}
+
+// fourth root of y mod p·q (p, q ≡ 3 mod 4) if y is a QR mod both
+fn fourth_root(y: &BigInt, p: &BigInt, q: &BigInt) -> Option<BigInt> {
+    let root_p = fourth_root_prime(y, p)?;
+    let root_q = fourth_root_prime(y, q)?;
+    // CRT: x = root_q + q·((root_p - root_q)·q^{-1} mod p)
+    let q_inv = modinv(q, p)?;
+    let h = mod_positive((root_p - &root_q) * q_inv, p);
+    Some(root_q + h * q)
+}
+
+// for p ≡ 3 mod 4 and a QR y, y^((p+1)/4) is the square root that is itself a
+// QR, so applying the map twice gives a fourth root
+fn fourth_root_prime(y: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let y = y.mod_floor(p);
+    if jacobi(&y, p) != 1 {
+        return None;
+    }
+    let e: BigInt = (p + BigInt::one()) >> 2;
+    let e2: BigInt = &e * &e;
+    let x = modpow_bigint(&y, &(e2.mod_floor(&(p - BigInt::one()))), p);
+    (modpow_bigint(&x, &BigInt::from(4), p) == y).then_some(x)
+}
+
+fn mod_challenges(n: &BigInt, w: &BigInt) -> Vec<BigInt> {
+    let mut tr = Transcript::new(b"cggmp21/pi-mod");
+    tr.append_int(b"N", n);
+    tr.append_int(b"w", w);
+    (0..MOD_ROUNDS).map(|_| tr.challenge_mod(b"y", n)).collect()
+}
+
+// -----------------------------
+// Π^fac
+// -----------------------------
+
+/// Range parameters for Π^fac: factors are proven to lie in ±√N·2^(ℓ+ε)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FacParams {
+    pub ell: u64,
+    pub eps: u64,
+}
+
+impl Default for FacParams {
+    /// CGGMP21 choice for secp256k1: ℓ = 256, ε = 2ℓ
+    fn default() -> Self {
+        FacParams { ell: 256, eps: 512 }
+    }
+}
+
+/// Non-interactive no-small-factor proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacProof {
+    pub p_commit: BigInt, // P = s^p t^μ
+    pub q_commit: BigInt, // Q = s^q t^ν
+    pub a: BigInt,        // A = s^α t^x
+    pub b: BigInt,        // B = s^β t^y
+    pub t: BigInt,        // T = Q^α t^r
+    pub sigma: BigInt,
+    pub z1: BigInt,
+    pub z2: BigInt,
+    pub w1: BigInt,
+    pub w2: BigInt,
+    pub v: BigInt,
+}
+
+impl FacProof {
+    /// Prove that pk.n has no small factor, against the verifier's parameters
+    pub fn prove(pk: &PublicKey, sk: &PrivateKey, rp: &RingPedersenParams, params: &FacParams) -> Self {
+        let (p, q) = sk.factors();
+        Self::prove_with_factors(&pk.n, p, q, rp, params)
+    }
+
+    fn prove_with_factors(n0: &BigInt, p: &BigInt, q: &BigInt, rp: &RingPedersenParams, params: &FacParams) -> Self {
+        let n_hat = &rp.n_hat;
+        let sqrt_n0 = n0.sqrt();
+        let l = BigInt::one() << params.ell;
+        let le = BigInt::one() << (params.ell + params.eps);
+
+        let alpha = sample_pm(&(&le * &sqrt_n0));
+        let beta = sample_pm(&(&le * &sqrt_n0));
+        let mu = sample_pm(&(&l * n_hat));
+        let nu = sample_pm(&(&l * n_hat));
+        let sigma = sample_pm(&(&l * n0 * n_hat));
+        let r = sample_pm(&(&le * n0 * n_hat));
+        let x = sample_pm(&(&le * n_hat));
+        let y = sample_pm(&(&le * n_hat));
+
+        let p_commit = rp.commit(p, &mu);
+        let q_commit = rp.commit(q, &nu);
+        let a = rp.commit(&alpha, &x);
+        let b = rp.commit(&beta, &y);
+        let t = mod_positive(
+            modpow_signed(&q_commit, &alpha, n_hat).expect("Q is a unit") * modpow_signed(&rp.t, &r, n_hat).expect("t is a unit"),
+            n_hat,
+        );
+
+        let e = fac_challenge(n0, rp, &p_commit, &q_commit, &a, &b, &t, &sigma, params);
+        let sigma_hat = &sigma - &nu * p;
+        FacProof {
+            z1: &alpha + &e * p,
+            z2: &beta + &e * q,
+            w1: &x + &e * &mu,
+            w2: &y + &e * &nu,
+            v: &r + &e * sigma_hat,
+            p_commit, q_commit, a, b, t, sigma,
+        }
+    }
+
+    /// Verify against pk.n and the verifier's own ring-Pedersen parameters
+    pub fn verify(&self, pk: &PublicKey, rp: &RingPedersenParams, params: &FacParams) -> bool {
+        self.verify_modulus(&pk.n, rp, params)
+    }
+
+    fn verify_modulus(&self, n0: &BigInt, rp: &RingPedersenParams, params: &FacParams) -> bool {
+        let n_hat = &rp.n_hat;
+        for c in [&self.p_commit, &self.q_commit, &self.a, &self.b, &self.t] {
+            if c.sign() != Sign::Plus || c >= n_hat || !c.gcd(n_hat).is_one() {
+                return false;
+            }
+        }
+        let bound = n0.sqrt() << (params.ell + params.eps);
+        if self.z1.abs() > bound || self.z2.abs() > bound {
+            return false;
+        }
+        let e = fac_challenge(n0, rp, &self.p_commit, &self.q_commit, &self.a, &self.b, &self.t, &self.sigma, params);
+        let pow = |base: &BigInt, exp: &BigInt| modpow_signed(base, exp, n_hat);
+        let (Some(pe), Some(qe), Some(qz1), Some(tv)) =
+            (pow(&self.p_commit, &e), pow(&self.q_commit, &e), pow(&self.q_commit, &self.z1), pow(&rp.t, &self.v))
+        else {
+            return false;
+        };
+        // R = s^N0 t^σ
+        let r_commit = rp.commit(n0, &self.sigma);
+        let Some(re) = pow(&r_commit, &e) else { return false; };
+
+        rp.commit(&self.z1, &self.w1) == mod_positive(&self.a * pe, n_hat)
+            && rp.commit(&self.z2, &self.w2) == mod_positive(&self.b * qe, n_hat)
+            && mod_positive(qz1 * tv, n_hat) == mod_positive(&self.t * re, n_hat)
+    }
+}
+
+// uniform in [-bound, bound]
+fn sample_pm(bound: &BigInt) -> BigInt {
+    let mut rng = OsRng;
+    rng.gen_bigint_range(&-bound, &(bound + BigInt::one()))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn fac_challenge(
+    n0: &BigInt, rp: &RingPedersenParams, p: &BigInt, q: &BigInt, a: &BigInt, b: &BigInt, t: &BigInt,
+    sigma: &BigInt, params: &FacParams,
+) -> BigInt {
+    let mut tr = Transcript::new(b"cggmp21/pi-fac");
+    tr.append_int(b"N0", n0);
+    tr.append_int(b"N^", &rp.n_hat);
+    tr.append_int(b"s", &rp.s);
+    tr.append_int(b"t", &rp.t);
+    tr.append_u64(b"ell", params.ell);
+    tr.append_u64(b"eps", params.eps);
+    for (label, x) in [(&b"P"[..], p), (b"Q", q), (b"A", a), (b"B", b), (b"T", t), (b"sigma", sigma)] {
+        tr.append_int(label, x);
+    }
+    // e uniform in [-2^ℓ, 2^ℓ)
+    tr.challenge_bits(b"e", params.ell + 1) - (BigInt::one() << params.ell)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group::utils::primes::{gen_prime_congruent, random_blum_prime, random_prime, BailliePsw};
+    use super::super::pailliar::Keypair;
+    use super::super::ring_pedersen::test_params;
+
+    // prime of the given size with p ≡ 1 (mod 4)
+    fn non_blum_prime(bits: usize) -> BigInt {
+        gen_prime_congruent(bits, 1, 4, &mut OsRng, &BailliePsw)
+    }
+
+    #[test]
+    fn mod_proof_roundtrip() {
+        let kp = Keypair::generate_blum(512);
+        let proof = ModProof::prove(&kp.pk, &kp.sk).unwrap();
+        assert!(proof.verify(&kp.pk));
+
+        // bound to its modulus
+        let other = Keypair::generate_blum(512);
+        assert!(!proof.verify(&other.pk));
+    }
+
+    #[test]
+    fn mod_proof_rejects_tampering() {
+        let kp = Keypair::generate_blum(512);
+        let proof = ModProof::prove(&kp.pk, &kp.sk).unwrap();
+
+        let mut bad = proof.clone();
+        bad.rounds[7].x = mod_positive(&bad.rounds[7].x + BigInt::one(), &kp.pk.n);
+        assert!(!bad.verify(&kp.pk));
+
+        let mut bad = proof.clone();
+        bad.rounds[3].a = !bad.rounds[3].a;
+        assert!(!bad.verify(&kp.pk));
+
+        let mut bad = proof.clone();
+        bad.rounds.pop();
+        assert!(!bad.verify(&kp.pk));
+
+        let mut bad = proof;
+        bad.w = mod_positive(&bad.w * &bad.w, &kp.pk.n); // Jacobi +1
+        assert!(!bad.verify(&kp.pk));
+    }
+
+    #[test]
+    fn mod_proof_rejects_crafted_moduli() {
+        // p ≡ 1 (mod 4): not a Blum integer, honest prover cannot answer
+        let p = non_blum_prime(256);
+        let q = random_blum_prime(256);
+        let n = &p * &q;
+        assert!(matches!(ModProof::prove_with_factors(&n, &p, &q), Err(ModulusProofError::NotPaillierBlum)));
+
+        // a cheating prover that ignores the residuosity check still fails:
+        // for w with (w/N) = -1 half of all y have no fourth-root twist
+        let w = loop {
+            let w = OsRng.gen_bigint_range(&BigInt::from(2), &n);
+            if jacobi(&w, &n) == -1 { break w; }
+        };
+        let ys = mod_challenges(&n, &w);
+        let answerable = ys.iter().filter(|y| {
+            [(false, false), (false, true), (true, false), (true, true)]
+                .iter()
+                .any(|&(a, b)| fourth_root(&twist(y, a, b, &w, &n), &p, &q).is_some())
+        }).count();
+        assert!(answerable < MOD_ROUNDS);
+
+        // prime N
+        let prime = random_blum_prime(512);
+        let fake = ModProof { w: BigInt::from(2), rounds: vec![] };
+        assert!(!fake.verify_modulus(&prime));
+
+        // N = p^2 q: gcd(N, φ(N)) != 1 and the square has no valid proof
+        let p = random_blum_prime(256);
+        let q = random_blum_prime(256);
+        assert!(matches!(ModProof::prove_with_factors(&(&p * &p * &q), &(&p * &p), &q), Err(ModulusProofError::NotPaillierBlum)));
+
+        // even N
+        assert!(!fake.verify_modulus(&(&q * 2)));
+    }
+
+    #[test]
+    fn fac_proof_roundtrip() {
+        let kp = Keypair::generate(1024);
+        let (rp, _) = test_params(0);
+        let params = FacParams::default();
+        let proof = FacProof::prove(&kp.pk, &kp.sk, &rp, &params);
+        assert!(proof.verify(&kp.pk, &rp, &params));
+
+        // bound to the verifier's parameters and to N
+        let (rp2, _) = test_params(1);
+        assert!(!proof.verify(&kp.pk, &rp2, &params));
+        let other = Keypair::generate(1024);
+        assert!(!proof.verify(&other.pk, &rp, &params));
+
+        let mut bad = proof;
+        bad.w1 += BigInt::one();
+        assert!(!bad.verify(&kp.pk, &rp, &params));
+    }
+
+    #[test]
+    fn fac_proof_rejects_small_factor() {
+        // with ℓ = 32, ε = 64 the proof bounds the factors by √N·2^96;
+        // a 100-bit factor forces the other one above that bound
+        let params = FacParams { ell: 32, eps: 64 };
+        let (rp, _) = test_params(0);
+        let small = random_prime(100);
+        let big = random_prime(412);
+        let n = &small * &big;
+        let proof = FacProof::prove_with_factors(&n, &small, &big, &rp, &params);
+        assert!(!proof.verify_modulus(&n, &rp, &params));
+
+        // balanced factors pass with the same parameters
+        let p = random_prime(256);
+        let q = random_prime(256);
+        let n = &p * &q;
+        let proof = FacProof::prove_with_factors(&n, &p, &q, &rp, &params);
+        assert!(proof.verify_modulus(&n, &rp, &params));
+    }
+}
@@ -0,0 +1,699 @@
+// src/group/secp256k1/9-paillier_proofs.rs
+// -----------------------------------------------------------------------------
+// Paillier encryption-in-range (Π^enc), affine-operation (Π^aff-g) and
+// encryption-of-discrete-log (Π^log*) proofs (CGGMP21, Fig. 14, 15 and 25)
+// -----------------------------------------------------------------------------
+// All three proofs are stated over a prover's Paillier key N0 and checked against
+// the *verifier's* ring-Pedersen parameters (N̂, s, t), which bind the prover
+// to integer values without revealing them.
+//
+// Π^enc — K = (1+N0)^k ρ^N0 mod N0² with k ∈ ±2^ℓ.
+//
+// Π^aff-g — given C under N0, the prover knows x ∈ ±2^ℓ, y ∈ ±2^ℓ' with
+//
+//     D = C^x (1+N0)^y ρ^N0   mod N0²      (an affine operation x·C + Enc(y))
+//     Y = (1+N1)^y ρy^N1      mod N1²      (y encrypted under its own key N1)
+//     X = x·G                              (x matches a secp256k1 point)
+//
+// Π^log* — C = (1+N0)^x ρ^N0 mod N0² and X = x·G with x ∈ ±2^ℓ: Π^enc with
+// the response also checked in the curve group.
+//
+// The challenge e is taken in ±q (q the secp256k1 group order) so the
+// relation with X can be checked in the curve group. Responses are integers
+// masked by ε extra bits; the verifier only enforces the ±2^(ℓ+ε) slack,
+// which is where the range guarantee comes from.
+//
+// Every proof serializes with `to_bytes`/`from_bytes` (group::utils::codec).
+// -----------------------------------------------------------------------------
+
+use num_bigint::{BigInt, BigUint, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Signed};
+use rand::rngs::OsRng;
+use crate::group::utils::codec::{CodecError, Reader, Writer};
+use crate::group::utils::montgomery::modpow_signed;
+use crate::group::utils::transcript::Transcript;
+use super::pailliar::{mod_positive, Ciphertext, PublicKey};
+use super::params::Secp256k1;
+use super::point::{point_add, scalar_mul, scalar_mul_base, AffinePoint};
+use super::ring_pedersen::RingPedersenParams;
+
+/// Bit lengths for the range statements (CGGMP21 §6: ℓ = κ, ℓ' = 5κ, ε = 2κ)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeParams {
+    pub ell: u64,
+    pub ell_prime: u64,
+    pub eps: u64,
+}
+
+impl Default for RangeParams {
+    fn default() -> Self {
+        RangeParams { ell: 256, ell_prime: 1280, eps: 512 }
+    }
+}
+
+/// (1+N)^m ρ^N mod N² for any integer m (the binomial shortcut holds for
+/// negative m as well)
+pub fn encrypt_signed(pk: &PublicKey, m: &BigInt, rho: &BigInt) -> Ciphertext {
+    let gm = mod_positive(BigInt::one() + m * &pk.n, &pk.n2);
+    let rn = modpow_signed(rho, &pk.n, &pk.n2).expect("positive exponent");
+    Ciphertext(mod_positive(gm * rn, &pk.n2))
+}
+
+/// `encrypt_signed` with ρ from `PublicKey::randomness`, so an attached pool
+/// saves the ρ^N; returns ρ as well for the proof witness
+pub fn encrypt_signed_fresh(pk: &PublicKey, m: &BigInt) -> (Ciphertext, BigInt) {
+    let (rho, rn) = pk.randomness();
+    let gm = mod_positive(BigInt::one() + m * &pk.n, &pk.n2);
+    (Ciphertext(mod_positive(gm * rn, &pk.n2)), rho)
+}
+
+// -----------------------------
+// Π^enc
+// -----------------------------
+
+/// Proof that a ciphertext encrypts a value in ±2^ℓ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncProof {
+    pub s_commit: BigInt, // S = s^k t^μ
+    pub a: BigInt,        // A = (1+N0)^α r^N0
+    pub c_commit: BigInt, // C = s^α t^γ
+    pub z1: BigInt,
+    pub z2: BigInt,
+    pub z3: BigInt,
+}
+
+impl EncProof {
+    /// Prove K = Enc(k; ρ) with |k| <= 2^ℓ
+    pub fn prove(pk: &PublicKey, k_ct: &Ciphertext, k: &BigInt, rho: &BigInt, rp: &RingPedersenParams, params: &RangeParams) -> Self {
+        let n_hat = &rp.n_hat;
+        let q = curve_order();
+        let alpha = sample_pm(params.ell + params.eps, &BigInt::one());
+        let mu = sample_pm(params.ell, n_hat);
+        let gamma = sample_pm(params.ell + params.eps, n_hat);
+        let r = sample_unit(&pk.n);
+
+        let s_commit = rp.commit(k, &mu);
+        let a = encrypt_signed(pk, &alpha, &r).0;
+        let c_commit = rp.commit(&alpha, &gamma);
+
+        let e = enc_challenge(pk, k_ct, rp, params, &s_commit, &a, &c_commit, &q);
+        let rho_e = modpow_signed(rho, &e, &pk.n).expect("ρ is a unit mod N0");
+        EncProof {
+            z1: &alpha + &e * k,
+            z2: mod_positive(r * rho_e, &pk.n),
+            z3: gamma + &e * mu,
+            s_commit, a, c_commit,
+        }
+    }
+
+    pub fn verify(&self, pk: &PublicKey, k_ct: &Ciphertext, rp: &RingPedersenParams, params: &RangeParams) -> bool {
+        let n_hat = &rp.n_hat;
+        if !in_range(&self.z1, params.ell + params.eps)
+            || !is_unit(&self.z2, &pk.n)
+            || !is_unit(&self.a, &pk.n2)
+            || !is_unit(&k_ct.0, &pk.n2)
+            || !is_unit(&self.s_commit, n_hat)
+            || !is_unit(&self.c_commit, n_hat)
+        {
+            return false;
+        }
+        let q = curve_order();
+        let e = enc_challenge(pk, k_ct, rp, params, &self.s_commit, &self.a, &self.c_commit, &q);
+
+        // (1+N0)^z1 z2^N0 = A K^e mod N0²
+        let lhs = encrypt_signed(pk, &self.z1, &self.z2).0;
+        let Some(ke) = modpow_signed(&k_ct.0, &e, &pk.n2) else { return false; };
+        if lhs != mod_positive(&self.a * ke, &pk.n2) {
+            return false;
+        }
+        // s^z1 t^z3 = C S^e mod N̂
+        let Some(se) = modpow_signed(&self.s_commit, &e, n_hat) else { return false; };
+        rp.commit(&self.z1, &self.z3) == mod_positive(&self.c_commit * se, n_hat)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+        for x in [&self.s_commit, &self.a, &self.c_commit, &self.z1, &self.z2, &self.z3] {
+            w.put_int(x);
+        }
+        w.finish()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        let mut r = Reader::new(bytes);
+        let proof = EncProof {
+            s_commit: r.get_int()?,
+            a: r.get_int()?,
+            c_commit: r.get_int()?,
+            z1: r.get_int()?,
+            z2: r.get_int()?,
+            z3: r.get_int()?,
+        };
+        r.finish()?;
+        Ok(proof)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn enc_challenge(
+    pk: &PublicKey, k_ct: &Ciphertext, rp: &RingPedersenParams, params: &RangeParams,
+    s_commit: &BigInt, a: &BigInt, c_commit: &BigInt, q: &BigInt,
+) -> BigInt {
+    let mut tr = Transcript::new(b"cggmp21/pi-enc");
+    append_setup(&mut tr, rp, params);
+    tr.append_int(b"N0", &pk.n);
+    tr.append_int(b"K", &k_ct.0);
+    tr.append_int(b"S", s_commit);
+    tr.append_int(b"A", a);
+    tr.append_int(b"C", c_commit);
+    challenge_pm_q(&mut tr, q)
+}
+
+// -----------------------------
+// Π^log*
+// -----------------------------
+
+/// Proof that a ciphertext encrypts the discrete log of a point, in ±2^ℓ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogStarProof {
+    pub s_commit: BigInt, // S = s^x t^μ
+    pub a: BigInt,        // A = (1+N0)^α r^N0
+    pub y: AffinePoint,   // Y = α·G
+    pub d_commit: BigInt, // D = s^α t^γ
+    pub z1: BigInt,
+    pub z2: BigInt,
+    pub z3: BigInt,
+}
+
+impl LogStarProof {
+    /// Prove C = Enc(x; ρ) and X = x·G with |x| <= 2^ℓ
+    pub fn prove(pk: &PublicKey, c: &Ciphertext, x_point: &AffinePoint, x: &BigInt, rho: &BigInt, rp: &RingPedersenParams, params: &RangeParams) -> Self {
+        let curve = Secp256k1::new();
+        let n_hat = &rp.n_hat;
+        let q = curve_order();
+        let alpha = sample_pm(params.ell + params.eps, &BigInt::one());
+        let mu = sample_pm(params.ell, n_hat);
+        let gamma = sample_pm(params.ell + params.eps, n_hat);
+        let r = sample_unit(&pk.n);
+
+        let mut proof = LogStarProof {
+            s_commit: rp.commit(x, &mu),
+            a: encrypt_signed(pk, &alpha, &r).0,
+            y: scalar_mul_base(&to_scalar(&alpha, &q), &curve),
+            d_commit: rp.commit(&alpha, &gamma),
+            z1: BigInt::one(), z2: BigInt::one(), z3: BigInt::one(),
+        };
+        let e = log_star_challenge(pk, c, x_point, rp, params, &proof, &q);
+        let rho_e = modpow_signed(rho, &e, &pk.n).expect("ρ is a unit mod N0");
+        proof.z1 = alpha + &e * x;
+        proof.z2 = mod_positive(r * rho_e, &pk.n);
+        proof.z3 = gamma + &e * mu;
+        proof
+    }
+
+    pub fn verify(&self, pk: &PublicKey, c: &Ciphertext, x_point: &AffinePoint, rp: &RingPedersenParams, params: &RangeParams) -> bool {
+        let curve = Secp256k1::new();
+        let n_hat = &rp.n_hat;
+        if !in_range(&self.z1, params.ell + params.eps)
+            || !is_unit(&self.z2, &pk.n)
+            || !is_unit(&self.a, &pk.n2)
+            || !is_unit(&c.0, &pk.n2)
+            || !is_unit(&self.s_commit, n_hat)
+            || !is_unit(&self.d_commit, n_hat)
+            || !x_point.is_on_curve(&curve)
+            || !self.y.is_on_curve(&curve)
+        {
+            return false;
+        }
+        let q = curve_order();
+        let e = log_star_challenge(pk, c, x_point, rp, params, self, &q);
+        let pow = |b: &BigInt, x: &BigInt, m: &BigInt| modpow_signed(b, x, m).expect("checked unit");
+
+        // (1+N0)^z1 z2^N0 = A C^e mod N0²
+        if encrypt_signed(pk, &self.z1, &self.z2).0 != mod_positive(&self.a * pow(&c.0, &e, &pk.n2), &pk.n2) {
+            return false;
+        }
+        // z1·G = Y + e·X
+        let lhs = scalar_mul_base(&to_scalar(&self.z1, &q), &curve);
+        if lhs != point_add(&self.y, &scalar_mul(&to_scalar(&e, &q), x_point, &curve), &curve) {
+            return false;
+        }
+        // s^z1 t^z3 = D S^e mod N̂
+        rp.commit(&self.z1, &self.z3) == mod_positive(&self.d_commit * pow(&self.s_commit, &e, n_hat), n_hat)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+        w.put_int(&self.s_commit);
+        w.put_int(&self.a);
+        w.put_bytes(&self.y.to_compressed());
+        for x in [&self.d_commit, &self.z1, &self.z2, &self.z3] {
+            w.put_int(x);
+        }
+        w.finish()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        let curve = Secp256k1::new();
+        let mut r = Reader::new(bytes);
+        let s_commit = r.get_int()?;
+        let a = r.get_int()?;
+        let proof = LogStarProof {
+            s_commit,
+            a,
+            y: read_point(&mut r, &curve)?,
+            d_commit: r.get_int()?,
+            z1: r.get_int()?,
+            z2: r.get_int()?,
+            z3: r.get_int()?,
+        };
+        r.finish()?;
+        Ok(proof)
+    }
+}
+
+// the response fields of `proof` are not absorbed
+fn log_star_challenge(
+    pk: &PublicKey, c: &Ciphertext, x_point: &AffinePoint, rp: &RingPedersenParams, params: &RangeParams,
+    proof: &LogStarProof, q: &BigInt,
+) -> BigInt {
+    let mut tr = Transcript::new(b"cggmp21/pi-log*");
+    append_setup(&mut tr, rp, params);
+    tr.append_int(b"N0", &pk.n);
+    tr.append_int(b"C", &c.0);
+    tr.append_bytes(b"X", &x_point.to_compressed());
+    tr.append_int(b"S", &proof.s_commit);
+    tr.append_int(b"A", &proof.a);
+    tr.append_bytes(b"Y", &proof.y.to_compressed());
+    tr.append_int(b"D", &proof.d_commit);
+    challenge_pm_q(&mut tr, q)
+}
+
+// -----------------------------
+// Π^aff-g
+// -----------------------------
+
+/// Public inputs of Π^aff-g
+pub struct AffGStatement<'a> {
+    pub pk0: &'a PublicKey,   // key of C and D
+    pub pk1: &'a PublicKey,   // key of Y
+    pub c: &'a Ciphertext,
+    pub d: &'a Ciphertext,
+    pub y_ct: &'a Ciphertext,
+    pub x_point: &'a AffinePoint,
+}
+
+/// Secret inputs of Π^aff-g
+pub struct AffGWitness<'a> {
+    pub x: &'a BigInt,
+    pub y: &'a BigInt,
+    pub rho: &'a BigInt,
+    pub rho_y: &'a BigInt,
+}
+
+/// Proof that D = x·C + Enc(y), Y = Enc(y) and X = x·G with x, y in range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffGProof {
+    pub a: BigInt,           // A = C^α (1+N0)^β r^N0
+    pub b_x: AffinePoint,    // Bx = α·G
+    pub b_y: BigInt,         // By = (1+N1)^β ry^N1
+    pub e_commit: BigInt,    // E = s^α t^γ
+    pub s_commit: BigInt,    // S = s^x t^m
+    pub f_commit: BigInt,    // F = s^β t^δ
+    pub t_commit: BigInt,    // T = s^y t^μ
+    pub z1: BigInt,
+    pub z2: BigInt,
+    pub z3: BigInt,
+    pub z4: BigInt,
+    pub w: BigInt,
+    pub w_y: BigInt,
+}
+
+impl AffGProof {
+    pub fn prove(st: &AffGStatement, wit: &AffGWitness, rp: &RingPedersenParams, params: &RangeParams) -> Self {
+        let curve = Secp256k1::new();
+        let q = curve_order();
+        let n_hat = &rp.n_hat;
+        let (pk0, pk1) = (st.pk0, st.pk1);
+
+        let alpha = sample_pm(params.ell + params.eps, &BigInt::one());
+        let beta = sample_pm(params.ell_prime + params.eps, &BigInt::one());
+        let r = sample_unit(&pk0.n);
+        let r_y = sample_unit(&pk1.n);
+        let gamma = sample_pm(params.ell + params.eps, n_hat);
+        let delta = sample_pm(params.ell + params.eps, n_hat);
+        let m = sample_pm(params.ell, n_hat);
+        let mu = sample_pm(params.ell, n_hat);
+
+        let c_alpha = modpow_signed(&st.c.0, &alpha, &pk0.n2).expect("C is a unit mod N0²");
+        let a = mod_positive(c_alpha * encrypt_signed(pk0, &beta, &r).0, &pk0.n2);
+        let b_x = scalar_mul_base(&to_scalar(&alpha, &q), &curve);
+        let b_y = encrypt_signed(pk1, &beta, &r_y).0;
+        let e_commit = rp.commit(&alpha, &gamma);
+        let s_commit = rp.commit(wit.x, &m);
+        let f_commit = rp.commit(&beta, &delta);
+        let t_commit = rp.commit(wit.y, &mu);
+
+        let mut proof = AffGProof {
+            a, b_x, b_y, e_commit, s_commit, f_commit, t_commit,
+            z1: BigInt::one(), z2: BigInt::one(), z3: BigInt::one(), z4: BigInt::one(),
+            w: BigInt::one(), w_y: BigInt::one(),
+        };
+        let e = aff_g_challenge(st, rp, params, &proof, &q);
+        let rho_e = modpow_signed(wit.rho, &e, &pk0.n).expect("ρ is a unit mod N0");
+        let rho_y_e = modpow_signed(wit.rho_y, &e, &pk1.n).expect("ρy is a unit mod N1");
+        proof.z1 = alpha + &e * wit.x;
+        proof.z2 = beta + &e * wit.y;
+        proof.z3 = gamma + &e * m;
+        proof.z4 = delta + &e * mu;
+        proof.w = mod_positive(r * rho_e, &pk0.n);
+        proof.w_y = mod_positive(r_y * rho_y_e, &pk1.n);
+        proof
+    }
+
+    pub fn verify(&self, st: &AffGStatement, rp: &RingPedersenParams, params: &RangeParams) -> bool {
+        let curve = Secp256k1::new();
+        let q = curve_order();
+        let n_hat = &rp.n_hat;
+        let (pk0, pk1) = (st.pk0, st.pk1);
+
+        if !in_range(&self.z1, params.ell + params.eps)
+            || !in_range(&self.z2, params.ell_prime + params.eps)
+            || !is_unit(&self.w, &pk0.n)
+            || !is_unit(&self.w_y, &pk1.n)
+            || !is_unit(&self.a, &pk0.n2)
+            || !is_unit(&self.b_y, &pk1.n2)
+            || !st.x_point.is_on_curve(&curve)
+            || !self.b_x.is_on_curve(&curve)
+        {
+            return false;
+        }
+        for c in [&self.e_commit, &self.s_commit, &self.f_commit, &self.t_commit] {
+            if !is_unit(c, n_hat) {
+                return false;
+            }
+        }
+        for ct in [st.c, st.d] {
+            if !is_unit(&ct.0, &pk0.n2) {
+                return false;
+            }
+        }
+        if !is_unit(&st.y_ct.0, &pk1.n2) {
+            return false;
+        }
+        let e = aff_g_challenge(st, rp, params, self, &q);
+        let pow = |b: &BigInt, x: &BigInt, m: &BigInt| modpow_signed(b, x, m).expect("checked unit");
+
+        // C^z1 (1+N0)^z2 w^N0 = A D^e mod N0²
+        let lhs = mod_positive(pow(&st.c.0, &self.z1, &pk0.n2) * encrypt_signed(pk0, &self.z2, &self.w).0, &pk0.n2);
+        if lhs != mod_positive(&self.a * pow(&st.d.0, &e, &pk0.n2), &pk0.n2) {
+            return false;
+        }
+        // z1·G = Bx + e·X
+        let lhs = scalar_mul_base(&to_scalar(&self.z1, &q), &curve);
+        let rhs = point_add(&self.b_x, &scalar_mul(&to_scalar(&e, &q), st.x_point, &curve), &curve);
+        if lhs != rhs {
+            return false;
+        }
+        // (1+N1)^z2 wy^N1 = By Y^e mod N1²
+        if encrypt_signed(pk1, &self.z2, &self.w_y).0 != mod_positive(&self.b_y * pow(&st.y_ct.0, &e, &pk1.n2), &pk1.n2) {
+            return false;
+        }
+        // s^z1 t^z3 = E S^e and s^z2 t^z4 = F T^e mod N̂
+        rp.commit(&self.z1, &self.z3) == mod_positive(&self.e_commit * pow(&self.s_commit, &e, n_hat), n_hat)
+            && rp.commit(&self.z2, &self.z4) == mod_positive(&self.f_commit * pow(&self.t_commit, &e, n_hat), n_hat)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+        w.put_int(&self.a);
+        w.put_bytes(&self.b_x.to_compressed());
+        for x in [
+            &self.b_y, &self.e_commit, &self.s_commit, &self.f_commit, &self.t_commit,
+            &self.z1, &self.z2, &self.z3, &self.z4, &self.w, &self.w_y,
+        ] {
+            w.put_int(x);
+        }
+        w.finish()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        let curve = Secp256k1::new();
+        let mut r = Reader::new(bytes);
+        let a = r.get_int()?;
+        let b_x = read_point(&mut r, &curve)?;
+        let proof = AffGProof {
+            a,
+            b_x,
+            b_y: r.get_int()?,
+            e_commit: r.get_int()?,
+            s_commit: r.get_int()?,
+            f_commit: r.get_int()?,
+            t_commit: r.get_int()?,
+            z1: r.get_int()?,
+            z2: r.get_int()?,
+            z3: r.get_int()?,
+            z4: r.get_int()?,
+            w: r.get_int()?,
+            w_y: r.get_int()?,
+        };
+        r.finish()?;
+        Ok(proof)
+    }
+}
+
+// the response fields of `proof` are not absorbed
+fn aff_g_challenge(st: &AffGStatement, rp: &RingPedersenParams, params: &RangeParams, proof: &AffGProof, q: &BigInt) -> BigInt {
+    let mut tr = Transcript::new(b"cggmp21/pi-aff-g");
+    append_setup(&mut tr, rp, params);
+    tr.append_int(b"N0", &st.pk0.n);
+    tr.append_int(b"N1", &st.pk1.n);
+    tr.append_int(b"C", &st.c.0);
+    tr.append_int(b"D", &st.d.0);
+    tr.append_int(b"Y", &st.y_ct.0);
+    tr.append_bytes(b"X", &st.x_point.to_compressed());
+    tr.append_int(b"A", &proof.a);
+    tr.append_bytes(b"Bx", &proof.b_x.to_compressed());
+    tr.append_int(b"By", &proof.b_y);
+    tr.append_int(b"E", &proof.e_commit);
+    tr.append_int(b"S", &proof.s_commit);
+    tr.append_int(b"F", &proof.f_commit);
+    tr.append_int(b"T", &proof.t_commit);
+    challenge_pm_q(&mut tr, q)
+}
+
+// -----------------------------
+// Helpers
+// -----------------------------
+
+pub(crate) fn curve_order() -> BigInt {
+    BigInt::from_biguint(Sign::Plus, Secp256k1::new().n)
+}
+
+fn append_setup(tr: &mut Transcript, rp: &RingPedersenParams, params: &RangeParams) {
+    tr.append_int(b"N^", &rp.n_hat);
+    tr.append_int(b"s", &rp.s);
+    tr.append_int(b"t", &rp.t);
+    tr.append_u64(b"ell", params.ell);
+    tr.append_u64(b"ell'", params.ell_prime);
+    tr.append_u64(b"eps", params.eps);
+}
+
+// e uniform in [-q, q]
+fn challenge_pm_q(tr: &mut Transcript, q: &BigInt) -> BigInt {
+    let width: BigInt = q * 2 + 1;
+    tr.challenge_mod(b"e", &width) - q
+}
+
+// uniform in ±2^bits·scale
+fn sample_pm(bits: u64, scale: &BigInt) -> BigInt {
+    let bound = (BigInt::one() << bits) * scale;
+    OsRng.gen_bigint_range(&-&bound, &(&bound + 1))
+}
+
+pub(crate) fn sample_unit(n: &BigInt) -> BigInt {
+    loop {
+        let r = OsRng.gen_bigint_range(&BigInt::one(), n);
+        if r.gcd(n).is_one() { return r; }
+    }
+}
+
+fn in_range(x: &BigInt, bits: u64) -> bool {
+    x.abs() <= BigInt::one() << bits
+}
+
+fn is_unit(x: &BigInt, m: &BigInt) -> bool {
+    x.sign() == Sign::Plus && x < m && x.gcd(m).is_one()
+}
+
+fn to_scalar(k: &BigInt, q: &BigInt) -> BigUint {
+    k.mod_floor(q).to_biguint().expect("reduced mod q")
+}
+
+fn read_point(r: &mut Reader, curve: &Secp256k1) -> Result<AffinePoint, CodecError> {
+    let tag = r.get_u8()?;
+    let mut bytes = vec![tag];
+    if tag != 0x00 {
+        bytes.extend_from_slice(r.get_bytes(32)?);
+    }
+    AffinePoint::from_compressed(&bytes, curve).ok_or(CodecError::InvalidField("curve point"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::pailliar::Keypair;
+    use super::super::ring_pedersen::test_params;
+
+    struct Setup {
+        kp0: Keypair,
+        kp1: Keypair,
+        rp: RingPedersenParams,
+    }
+
+    fn setup() -> Setup {
+        let (rp, _) = test_params(0);
+        Setup { kp0: Keypair::generate(1024), kp1: Keypair::generate(1024), rp }
+    }
+
+    #[test]
+    fn enc_proof_roundtrip_and_negatives() {
+        let Setup { kp0, kp1, rp } = setup();
+        let params = RangeParams::default();
+        let pk = &kp0.pk;
+        let k = -(BigInt::one() << 200usize);
+        let rho = sample_unit(&pk.n);
+        let k_ct = encrypt_signed(pk, &k, &rho);
+        assert_eq!(kp0.sk.decrypt(pk, &k_ct).unwrap(), mod_positive(k.clone(), &pk.n));
+
+        let proof = EncProof::prove(pk, &k_ct, &k, &rho, &rp, &params);
+        assert!(proof.verify(pk, &k_ct, &rp, &params));
+
+        // serialization
+        let bytes = proof.to_bytes();
+        assert_eq!(EncProof::from_bytes(&bytes).unwrap(), proof);
+        assert_eq!(EncProof::from_bytes(&bytes[..bytes.len() - 1]), Err(CodecError::Truncated));
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert_eq!(EncProof::from_bytes(&extra), Err(CodecError::TrailingBytes));
+
+        // wrong ciphertext, key and verifier parameters
+        let other_ct = encrypt_signed(pk, &(&k + 1), &rho);
+        assert!(!proof.verify(pk, &other_ct, &rp, &params));
+        assert!(!proof.verify(&kp1.pk, &k_ct, &rp, &params));
+        let (rp2, _) = test_params(1);
+        assert!(!proof.verify(pk, &k_ct, &rp2, &params));
+
+        // tampered response
+        let mut bad = proof.clone();
+        bad.z3 += 1;
+        assert!(!bad.verify(pk, &k_ct, &rp, &params));
+
+        // plaintext out of range: honest prover algorithm, z1 exceeds the slack
+        let big = BigInt::one() << (params.ell + params.eps + 10);
+        let big_ct = encrypt_signed(pk, &big, &rho);
+        let proof = EncProof::prove(pk, &big_ct, &big, &rho, &rp, &params);
+        assert!(!proof.verify(pk, &big_ct, &rp, &params));
+    }
+
+    #[test]
+    fn log_star_proof_roundtrip_and_negatives() {
+        let Setup { kp0, kp1, rp } = setup();
+        let params = RangeParams::default();
+        let curve = Secp256k1::new();
+        let pk = &kp0.pk;
+        let x = BigInt::from(0x1234_5678u64) << 220usize;
+        let x_point = scalar_mul_base(&to_scalar(&x, &curve_order()), &curve);
+        let rho = sample_unit(&pk.n);
+        let c = encrypt_signed(pk, &x, &rho);
+
+        let proof = LogStarProof::prove(pk, &c, &x_point, &x, &rho, &rp, &params);
+        assert!(proof.verify(pk, &c, &x_point, &rp, &params));
+        let bytes = proof.to_bytes();
+        assert_eq!(LogStarProof::from_bytes(&bytes).unwrap(), proof);
+
+        // point or ciphertext for a different value, other key, other parameters
+        let wrong_point = point_add(&x_point, &AffinePoint::generator(&curve), &curve);
+        assert!(!proof.verify(pk, &c, &wrong_point, &rp, &params));
+        assert!(!proof.verify(pk, &encrypt_signed(pk, &(&x + 1), &rho), &x_point, &rp, &params));
+        assert!(!proof.verify(&kp1.pk, &c, &x_point, &rp, &params));
+        let (rp2, _) = test_params(1);
+        assert!(!proof.verify(pk, &c, &x_point, &rp2, &params));
+        let mut bad = proof.clone();
+        bad.y = AffinePoint::generator(&curve);
+        assert!(!bad.verify(pk, &c, &x_point, &rp, &params));
+    }
+
+    fn aff_instance(s: &Setup, x: &BigInt, y: &BigInt) -> (Ciphertext, Ciphertext, Ciphertext, AffinePoint, BigInt, BigInt) {
+        let (pk0, pk1) = (&s.kp0.pk, &s.kp1.pk);
+        let c = pk0.encrypt(BigInt::from(123_456_789u64)).unwrap();
+        let rho = sample_unit(&pk0.n);
+        let rho_y = sample_unit(&pk1.n);
+        let cx = modpow_signed(&c.0, x, &pk0.n2).unwrap();
+        let d = Ciphertext(mod_positive(cx * encrypt_signed(pk0, y, &rho).0, &pk0.n2));
+        let y_ct = encrypt_signed(pk1, y, &rho_y);
+        let x_point = scalar_mul_base(&to_scalar(x, &curve_order()), &Secp256k1::new());
+        (c, d, y_ct, x_point, rho, rho_y)
+    }
+
+    #[test]
+    fn aff_g_proof_roundtrip_and_negatives() {
+        let s = setup();
+        let params = RangeParams::default();
+        let x = BigInt::from(0xdead_beefu64) << 200usize;
+        let y = -(BigInt::from(42) << 900usize);
+        let (c, d, y_ct, x_point, rho, rho_y) = aff_instance(&s, &x, &y);
+        let st = AffGStatement { pk0: &s.kp0.pk, pk1: &s.kp1.pk, c: &c, d: &d, y_ct: &y_ct, x_point: &x_point };
+        let wit = AffGWitness { x: &x, y: &y, rho: &rho, rho_y: &rho_y };
+
+        // D decrypts to x·123456789 + y
+        let expected = mod_positive(&x * 123_456_789u64 + &y, &s.kp0.pk.n);
+        assert_eq!(s.kp0.sk.decrypt(&s.kp0.pk, &d).unwrap(), expected);
+
+        let proof = AffGProof::prove(&st, &wit, &s.rp, &params);
+        assert!(proof.verify(&st, &s.rp, &params));
+
+        let bytes = proof.to_bytes();
+        assert_eq!(AffGProof::from_bytes(&bytes).unwrap(), proof);
+        let mut bad_point = bytes.clone();
+        let at = 5 + proof.a.to_bytes_be().1.len(); // Bx follows A
+        bad_point[at] = 0x05; // invalid SEC1 tag
+        assert_eq!(AffGProof::from_bytes(&bad_point), Err(CodecError::InvalidField("curve point")));
+
+        // X that does not match x
+        let wrong_x = point_add(&x_point, &AffinePoint::generator(&Secp256k1::new()), &Secp256k1::new());
+        let st_bad = AffGStatement { x_point: &wrong_x, ..st };
+        assert!(!proof.verify(&st_bad, &s.rp, &params));
+
+        // D from a different affine operation
+        let (_, d2, _, _, _, _) = aff_instance(&s, &x, &(&y + 1));
+        let st_bad = AffGStatement { d: &d2, ..st };
+        assert!(!proof.verify(&st_bad, &s.rp, &params));
+
+        // swapped keys, wrong verifier parameters, tampered fields
+        let st_bad = AffGStatement { pk0: &s.kp1.pk, pk1: &s.kp0.pk, ..st };
+        assert!(!proof.verify(&st_bad, &s.rp, &params));
+        let (rp2, _) = test_params(1);
+        assert!(!proof.verify(&st, &rp2, &params));
+        let mut bad = proof.clone();
+        bad.w_y = mod_positive(&bad.w_y + 1, &s.kp1.pk.n);
+        assert!(!bad.verify(&st, &s.rp, &params));
+        let mut bad = proof.clone();
+        bad.b_x = AffinePoint::generator(&Secp256k1::new());
+        assert!(!bad.verify(&st, &s.rp, &params));
+    }
+
+    #[test]
+    fn aff_g_rejects_out_of_range_witness() {
+        let s = setup();
+        let params = RangeParams::default();
+        let x = BigInt::one() << (params.ell + params.eps + 8);
+        let y = BigInt::from(7);
+        let (c, d, y_ct, x_point, rho, rho_y) = aff_instance(&s, &x, &y);
+        let st = AffGStatement { pk0: &s.kp0.pk, pk1: &s.kp1.pk, c: &c, d: &d, y_ct: &y_ct, x_point: &x_point };
+        let wit = AffGWitness { x: &x, y: &y, rho: &rho, rho_y: &rho_y };
+        let proof = AffGProof::prove(&st, &wit, &s.rp, &params);
+        assert!(!proof.verify(&st, &s.rp, &params));
+    }
+}
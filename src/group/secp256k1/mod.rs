@@ -7,4 +7,37 @@ pub mod field;
 pub mod point;
 
 #[path ="4-pailliar.rs"]
-pub mod pailliar;
\ No newline at end of file
+pub mod pailliar;
+
+#[path ="5-damgard_jurik.rs"]
+pub mod damgard_jurik;
+
+#[path ="6-threshold_paillier.rs"]
+pub mod threshold_paillier;
+
+#[path ="7-ring_pedersen.rs"]
+pub mod ring_pedersen;
+
+#[path ="8-modulus_proofs.rs"]
+pub mod modulus_proofs;
+
+#[path ="9-paillier_proofs.rs"]
+pub mod paillier_proofs;
+
+#[path ="10-encoding.rs"]
+pub mod encoding;
+
+#[path ="11-packing.rs"]
+pub mod packing;
+
+#[path ="12-mta.rs"]
+pub mod mta;
+
+#[path ="13-ecdsa.rs"]
+pub mod ecdsa;
+
+#[path ="14-lindell17.rs"]
+pub mod lindell17;
+
+#[path ="15-decryption_proofs.rs"]
+pub mod decryption_proofs;
@@ -0,0 +1,12 @@
+//! src/group/utils.rs
+//! Shared arithmetic used by several of the group modules.
+
+pub mod codec;
+pub mod der;
+pub mod factor;
+pub mod montgomery;
+pub mod number_theory;
+pub mod pem;
+pub mod primes;
+pub mod quadratic;
+pub mod transcript;
@@ -0,0 +1,120 @@
+//! src/group/utils/codec.rs
+//! Minimal length-prefixed binary encoding for proofs and protocol messages.
+//!
+//! Integers are written as `sign (1 byte) || len (u32 BE) || magnitude (BE)`
+//! and fixed-size fields (points, flags) as raw bytes. Decoding rejects
+//! truncated input, non-canonical integers and trailing bytes, so every value
+//! has exactly one encoding.
+
+use num_bigint::{BigInt, Sign};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CodecError {
+    #[error("input truncated")]
+    Truncated,
+    #[error("trailing bytes after message")]
+    TrailingBytes,
+    #[error("non-canonical integer encoding")]
+    NonCanonical,
+    #[error("invalid field: {0}")]
+    InvalidField(&'static str),
+}
+
+#[derive(Default)]
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Writer { buf: Vec::new() }
+    }
+
+    pub fn put_int(&mut self, x: &BigInt) {
+        let (sign, mag) = x.to_bytes_be();
+        let mag: &[u8] = if sign == Sign::NoSign { &[] } else { &mag };
+        self.buf.push(if sign == Sign::Minus { 1 } else { 0 });
+        self.buf.extend_from_slice(&(mag.len() as u32).to_be_bytes());
+        self.buf.extend_from_slice(mag);
+    }
+
+    pub fn put_bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    pub fn put_u8(&mut self, x: u8) {
+        self.buf.push(x);
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+pub struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Reader { buf }
+    }
+
+    pub fn get_bytes(&mut self, len: usize) -> Result<&'a [u8], CodecError> {
+        if self.buf.len() < len {
+            return Err(CodecError::Truncated);
+        }
+        let (head, tail) = self.buf.split_at(len);
+        self.buf = tail;
+        Ok(head)
+    }
+
+    pub fn get_u8(&mut self) -> Result<u8, CodecError> {
+        Ok(self.get_bytes(1)?[0])
+    }
+
+    pub fn get_int(&mut self) -> Result<BigInt, CodecError> {
+        let sign = self.get_u8()?;
+        let len = u32::from_be_bytes(self.get_bytes(4)?.try_into().expect("4 bytes")) as usize;
+        let mag = self.get_bytes(len)?;
+        // no leading zeros, no "-0", sign byte 0 or 1
+        if sign > 1 || mag.first() == Some(&0) || (len == 0 && sign == 1) {
+            return Err(CodecError::NonCanonical);
+        }
+        let sign = match (len, sign) {
+            (0, _) => Sign::NoSign,
+            (_, 1) => Sign::Minus,
+            _ => Sign::Plus,
+        };
+        Ok(BigInt::from_bytes_be(sign, mag))
+    }
+
+    /// Fails unless the whole input was consumed
+    pub fn finish(self) -> Result<(), CodecError> {
+        if self.buf.is_empty() { Ok(()) } else { Err(CodecError::TrailingBytes) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_roundtrip_and_canonical() {
+        let values = [BigInt::from(0), BigInt::from(-1), BigInt::from(255), BigInt::from(-(1i64 << 40))];
+        let mut w = Writer::new();
+        for v in &values { w.put_int(v); }
+        let bytes = w.finish();
+        let mut r = Reader::new(&bytes);
+        for v in &values { assert_eq!(&r.get_int().unwrap(), v); }
+        r.finish().unwrap();
+
+        // leading zero, negative zero, bad sign byte
+        for bad in [&[0u8, 0, 0, 0, 2, 0, 1][..], &[1, 0, 0, 0, 0], &[2, 0, 0, 0, 1, 1]] {
+            assert_eq!(Reader::new(bad).get_int(), Err(CodecError::NonCanonical));
+        }
+        assert_eq!(Reader::new(&[0, 0, 0, 0, 3, 1]).get_int(), Err(CodecError::Truncated));
+        assert_eq!(Reader::new(&[0]).finish(), Err(CodecError::TrailingBytes));
+    }
+}
@@ -0,0 +1,229 @@
+//! src/group/utils/der.rs
+//! Strict DER (X.690) for the handful of types key formats need: SEQUENCE,
+//! non-negative INTEGER, NULL, OBJECT IDENTIFIER, OCTET STRING and BIT STRING.
+//!
+//! Like `codec`, decoding accepts exactly one encoding per value: definite
+//! minimal-length headers, minimal two's-complement integers, no trailing
+//! bytes. Anything else is rejected rather than repaired.
+
+use num_bigint::BigUint;
+use thiserror::Error;
+
+pub const TAG_INTEGER: u8 = 0x02;
+pub const TAG_BIT_STRING: u8 = 0x03;
+pub const TAG_OCTET_STRING: u8 = 0x04;
+pub const TAG_NULL: u8 = 0x05;
+pub const TAG_OID: u8 = 0x06;
+pub const TAG_SEQUENCE: u8 = 0x30;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DerError {
+    #[error("input truncated")]
+    Truncated,
+    #[error("trailing bytes after value")]
+    TrailingBytes,
+    #[error("expected tag {expected:#04x}, found {found:#04x}")]
+    UnexpectedTag { expected: u8, found: u8 },
+    #[error("non-canonical DER: {0}")]
+    NonCanonical(&'static str),
+    #[error("negative integer")]
+    NegativeInteger,
+}
+
+#[derive(Default)]
+pub struct DerWriter {
+    buf: Vec<u8>,
+}
+
+impl DerWriter {
+    pub fn new() -> Self {
+        DerWriter { buf: Vec::new() }
+    }
+
+    /// Raw TLV with the given tag and content
+    pub fn put_tlv(&mut self, tag: u8, content: &[u8]) {
+        self.buf.push(tag);
+        let len = content.len();
+        if len < 0x80 {
+            self.buf.push(len as u8);
+        } else {
+            let bytes = len.to_be_bytes();
+            let skip = bytes.iter().take_while(|&&b| b == 0).count();
+            self.buf.push(0x80 | (bytes.len() - skip) as u8);
+            self.buf.extend_from_slice(&bytes[skip..]);
+        }
+        self.buf.extend_from_slice(content);
+    }
+
+    pub fn put_uint(&mut self, x: &BigUint) {
+        let mut mag = x.to_bytes_be();
+        // a set top bit would read as negative
+        if mag[0] & 0x80 != 0 {
+            mag.insert(0, 0);
+        }
+        self.put_tlv(TAG_INTEGER, &mag);
+    }
+
+    pub fn put_null(&mut self) {
+        self.put_tlv(TAG_NULL, &[]);
+    }
+
+    /// OID from its encoded content bytes
+    pub fn put_oid(&mut self, oid: &[u8]) {
+        self.put_tlv(TAG_OID, oid);
+    }
+
+    pub fn put_octet_string(&mut self, bytes: &[u8]) {
+        self.put_tlv(TAG_OCTET_STRING, bytes);
+    }
+
+    /// BIT STRING with no unused bits
+    pub fn put_bit_string(&mut self, bytes: &[u8]) {
+        let mut content = Vec::with_capacity(bytes.len() + 1);
+        content.push(0);
+        content.extend_from_slice(bytes);
+        self.put_tlv(TAG_BIT_STRING, &content);
+    }
+
+    pub fn put_sequence(&mut self, f: impl FnOnce(&mut DerWriter)) {
+        let mut inner = DerWriter::new();
+        f(&mut inner);
+        self.put_tlv(TAG_SEQUENCE, &inner.buf);
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+pub struct DerReader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> DerReader<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        DerReader { buf }
+    }
+
+    /// Next TLV; returns its content
+    pub fn get_tlv(&mut self, tag: u8) -> Result<&'a [u8], DerError> {
+        let (&found, rest) = self.buf.split_first().ok_or(DerError::Truncated)?;
+        if found != tag {
+            return Err(DerError::UnexpectedTag { expected: tag, found });
+        }
+        let (&first, mut rest) = rest.split_first().ok_or(DerError::Truncated)?;
+        let len = if first < 0x80 {
+            first as usize
+        } else {
+            let n = (first & 0x7f) as usize;
+            if n == 0 {
+                return Err(DerError::NonCanonical("indefinite length"));
+            }
+            if n > std::mem::size_of::<usize>() {
+                return Err(DerError::Truncated);
+            }
+            if rest.len() < n {
+                return Err(DerError::Truncated);
+            }
+            let (len_bytes, tail) = rest.split_at(n);
+            rest = tail;
+            if len_bytes[0] == 0 {
+                return Err(DerError::NonCanonical("length has leading zero"));
+            }
+            let len = len_bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+            if len < 0x80 {
+                return Err(DerError::NonCanonical("long form for short length"));
+            }
+            len
+        };
+        if rest.len() < len {
+            return Err(DerError::Truncated);
+        }
+        let (content, tail) = rest.split_at(len);
+        self.buf = tail;
+        Ok(content)
+    }
+
+    pub fn get_uint(&mut self) -> Result<BigUint, DerError> {
+        let content = self.get_tlv(TAG_INTEGER)?;
+        match content {
+            [] => Err(DerError::NonCanonical("empty integer")),
+            [b, ..] if b & 0x80 != 0 => Err(DerError::NegativeInteger),
+            [0, b, ..] if b & 0x80 == 0 => Err(DerError::NonCanonical("integer has leading zero")),
+            _ => Ok(BigUint::from_bytes_be(content)),
+        }
+    }
+
+    pub fn get_null(&mut self) -> Result<(), DerError> {
+        if !self.get_tlv(TAG_NULL)?.is_empty() {
+            return Err(DerError::NonCanonical("NULL with content"));
+        }
+        Ok(())
+    }
+
+    /// OID content bytes, for comparison against a known encoding
+    pub fn get_oid(&mut self) -> Result<&'a [u8], DerError> {
+        self.get_tlv(TAG_OID)
+    }
+
+    pub fn get_octet_string(&mut self) -> Result<&'a [u8], DerError> {
+        self.get_tlv(TAG_OCTET_STRING)
+    }
+
+    /// BIT STRING contents; only whole-byte strings are accepted
+    pub fn get_bit_string(&mut self) -> Result<&'a [u8], DerError> {
+        match self.get_tlv(TAG_BIT_STRING)? {
+            [0, rest @ ..] => Ok(rest),
+            [] => Err(DerError::Truncated),
+            _ => Err(DerError::NonCanonical("bit string with unused bits")),
+        }
+    }
+
+    /// Reader over the contents of the next SEQUENCE
+    pub fn get_sequence(&mut self) -> Result<DerReader<'a>, DerError> {
+        Ok(DerReader::new(self.get_tlv(TAG_SEQUENCE)?))
+    }
+
+    /// Fails unless the whole input was consumed
+    pub fn finish(self) -> Result<(), DerError> {
+        if self.buf.is_empty() { Ok(()) } else { Err(DerError::TrailingBytes) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_and_rejects_non_canonical() {
+        let values = [BigUint::from(0u32), BigUint::from(127u32), BigUint::from(128u32), BigUint::from(1u32) << 1000usize];
+        let mut w = DerWriter::new();
+        w.put_sequence(|s| {
+            for v in &values { s.put_uint(v); }
+            s.put_null();
+            s.put_octet_string(&[0xaa; 200]);
+            s.put_bit_string(&[1, 2]);
+        });
+        let bytes = w.finish();
+        assert_eq!(&bytes[..4], &[0x30, 0x82, 0x01, 0x5c]);
+
+        let mut r = DerReader::new(&bytes);
+        let mut seq = r.get_sequence().unwrap();
+        for v in &values { assert_eq!(&seq.get_uint().unwrap(), v); }
+        seq.get_null().unwrap();
+        assert_eq!(seq.get_octet_string().unwrap(), &[0xaa; 200][..]);
+        assert_eq!(seq.get_bit_string().unwrap(), &[1, 2]);
+        seq.finish().unwrap();
+        r.finish().unwrap();
+
+        let int = |b: &[u8]| DerReader::new(b).get_uint();
+        assert_eq!(int(&[0x02, 0x01, 0x80]), Err(DerError::NegativeInteger));
+        assert!(matches!(int(&[0x02, 0x02, 0x00, 0x7f]), Err(DerError::NonCanonical(_))));
+        assert!(matches!(int(&[0x02, 0x00]), Err(DerError::NonCanonical(_))));
+        assert!(matches!(int(&[0x02, 0x81, 0x01, 0x05]), Err(DerError::NonCanonical(_))));
+        assert!(matches!(int(&[0x02, 0x80, 0x05, 0x00, 0x00]), Err(DerError::NonCanonical(_))));
+        assert_eq!(int(&[0x02, 0x02, 0x01]), Err(DerError::Truncated));
+        assert_eq!(int(&[0x04, 0x01, 0x01]), Err(DerError::UnexpectedTag { expected: TAG_INTEGER, found: TAG_OCTET_STRING }));
+        assert_eq!(DerReader::new(&[0x05, 0x00, 0x00]).finish(), Err(DerError::TrailingBytes));
+    }
+}
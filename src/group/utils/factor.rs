@@ -0,0 +1,219 @@
+//! src/group/utils/factor.rs
+//! Integer factorization for the number-theory helpers (φ, element orders).
+//!
+//! `factorize` peels off small primes by trial division, then splits each
+//! remaining composite with Pollard p−1 (finds p when p − 1 is smooth) and
+//! Pollard rho in Brent's form (expected ~√p steps for the smallest factor p),
+//! recursing until every part passes the Baillie–PSW test from super::primes.
+//!
+//! Rho has a step budget, so a modulus built from two large primes (an RSA or
+//! Paillier n) makes `factorize` return None instead of running forever.
+
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use super::montgomery::modpow;
+use super::primes::{is_prime, small_primes};
+
+/// Smoothness bound for p−1; primes come from the small-prime table
+pub const P_MINUS_1_BOUND: u32 = 8191;
+
+/// Total rho iterations tried across all polynomials before giving up;
+/// rho needs about √p steps, so this covers factors up to roughly 36 bits
+/// (every composite i64 has one below 2^32)
+pub const RHO_MAX_STEPS: u64 = 1 << 20;
+
+// products of |x - y| accumulated between gcds in Brent's loop
+const RHO_BATCH: u64 = 128;
+
+// polynomials x^2 + c tried by rho
+const RHO_MAX_C: u32 = 16;
+
+/// Prime factorization of n > 0 as (prime, exponent), primes ascending.
+/// None when some composite part resisted p−1 and rho.
+pub fn factorize(n: &BigUint) -> Option<Vec<(BigUint, u32)>> {
+    assert!(!n.is_zero(), "cannot factor zero");
+    let (mut factors, rest) = trial_divide(n, P_MINUS_1_BOUND);
+    let mut pending = vec![rest];
+    while let Some(m) = pending.pop() {
+        if m.is_one() {
+            continue;
+        }
+        if is_prime(&BigInt::from(m.clone())) {
+            factors.push((m, 1));
+            continue;
+        }
+        let d = pollard_p_minus_1(&m, P_MINUS_1_BOUND).or_else(|| pollard_rho(&m))?;
+        pending.push(&m / &d);
+        pending.push(d);
+    }
+    factors.sort();
+    let mut merged: Vec<(BigUint, u32)> = Vec::with_capacity(factors.len());
+    for (p, e) in factors {
+        match merged.last_mut() {
+            Some((q, f)) if *q == p => *f += e,
+            _ => merged.push((p, e)),
+        }
+    }
+    Some(merged)
+}
+
+/// Divide out 2 and the odd primes up to `bound` (capped by the table);
+/// returns the factors found and the cofactor
+pub fn trial_divide(n: &BigUint, bound: u32) -> (Vec<(BigUint, u32)>, BigUint) {
+    let mut rest = n.clone();
+    let mut factors = Vec::new();
+    let mut exhausted = true;
+    for p in std::iter::once(2).chain(small_primes().iter().copied()).take_while(|&p| p <= bound) {
+        let p_big = BigUint::from(p);
+        if &p_big * &p_big > rest {
+            exhausted = false;
+            break;
+        }
+        let mut e = 0;
+        while (&rest % &p_big).is_zero() {
+            rest /= &p_big;
+            e += 1;
+        }
+        if e > 0 {
+            factors.push((p_big, e));
+        }
+    }
+    // stopping below p^2 means what is left is 1 or prime
+    if !exhausted && !rest.is_one() {
+        factors.push((rest, 1));
+        rest = BigUint::one();
+    }
+    (factors, rest)
+}
+
+/// Pollard p−1: a = 3^E with E the product of prime powers up to `bound`;
+/// gcd(a − 1, n) is a factor whenever some p | n has (bound)-smooth p − 1.
+/// Base 3 rather than 2, which has tiny order modulo Fermat and Mersenne numbers.
+pub fn pollard_p_minus_1(n: &BigUint, bound: u32) -> Option<BigUint> {
+    for small in [2u32, 3] {
+        if (n % small).is_zero() {
+            return (n > &BigUint::from(small)).then(|| BigUint::from(small));
+        }
+    }
+    let mut a = BigUint::from(3u32);
+    for q in std::iter::once(2).chain(small_primes().iter().copied()).take_while(|&q| q <= bound) {
+        let mut qk = q as u64;
+        while qk * q as u64 <= bound as u64 {
+            qk *= q as u64;
+        }
+        a = modpow(&a, &BigUint::from(qk), n);
+    }
+    // gcd(3, n) = 1, so a is a unit and a − 1 >= 0
+    let g = (a - 1u32).gcd(n);
+    (!g.is_one() && &g != n).then_some(g)
+}
+
+/// Pollard rho with Brent's cycle detection on x ↦ x² + c; a nontrivial
+/// factor of the composite n, or None once the step budget is spent
+pub fn pollard_rho(n: &BigUint) -> Option<BigUint> {
+    if n.is_even() {
+        return (n > &BigUint::from(2u32)).then(|| BigUint::from(2u32));
+    }
+    let diff = |a: &BigUint, b: &BigUint| if a > b { a - b } else { b - a };
+    let mut steps = 0u64;
+    for c in 1..=RHO_MAX_C {
+        let f = |x: &BigUint| (x * x + c) % n;
+        let mut y = BigUint::from(2u32);
+        let mut x = y.clone();
+        let mut ys = y.clone();
+        let mut q = BigUint::one();
+        let mut g = BigUint::one();
+        let mut r = 1u64;
+        while g.is_one() {
+            if steps > RHO_MAX_STEPS {
+                return None;
+            }
+            x = y.clone();
+            for _ in 0..r {
+                y = f(&y);
+            }
+            let mut k = 0;
+            while k < r && g.is_one() {
+                ys = y.clone();
+                let batch = RHO_BATCH.min(r - k);
+                for _ in 0..batch {
+                    y = f(&y);
+                    q = q * diff(&x, &y) % n;
+                }
+                g = q.gcd(n);
+                k += batch;
+            }
+            steps += 2 * r;
+            r *= 2;
+        }
+        if &g == n {
+            // the batch overshot: replay it one gcd at a time
+            loop {
+                ys = f(&ys);
+                g = diff(&x, &ys).gcd(n);
+                if !g.is_one() {
+                    break;
+                }
+            }
+        }
+        if &g != n {
+            return Some(g);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group::utils::primes::{gen_prime, gen_safe_prime, BailliePsw};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn product(factors: &[(BigUint, u32)]) -> BigUint {
+        factors.iter().map(|(p, e)| p.pow(*e)).product()
+    }
+
+    #[test]
+    fn small_numbers_match_naive() {
+        for n in 1u64..3000 {
+            let f = factorize(&BigUint::from(n)).unwrap();
+            assert_eq!(product(&f), BigUint::from(n), "n = {n}");
+            for (p, _) in &f {
+                assert!(is_prime(&BigInt::from(p.clone())), "n = {n}");
+            }
+        }
+        let f = factorize(&BigUint::from(720u32)).unwrap();
+        assert_eq!(f, vec![(BigUint::from(2u32), 4), (BigUint::from(3u32), 2), (BigUint::from(5u32), 1)]);
+    }
+
+    #[test]
+    fn rho_and_p_minus_1_split_larger_composites() {
+        let mut rng = StdRng::seed_from_u64(45);
+        let p = gen_prime(30, &mut rng, &BailliePsw).to_biguint().unwrap();
+        let q = gen_prime(34, &mut rng, &BailliePsw).to_biguint().unwrap();
+        let r = gen_prime(90, &mut rng, &BailliePsw).to_biguint().unwrap();
+        let n = &p * &p * &q * &r;
+        let f = factorize(&n).unwrap();
+        let mut expected = vec![(p.clone(), 2), (q.clone(), 1), (r, 1)];
+        expected.sort();
+        assert_eq!(f, expected);
+
+        let d = pollard_rho(&(&p * &q)).unwrap();
+        assert!(d == p || d == q);
+        // 2^64 + 1 = 274177 · 67280421310721, and 274176 = 2^8·3^2·7·17
+        let fermat6 = (BigUint::one() << 64) + 1u32;
+        assert_eq!(pollard_p_minus_1(&fermat6, P_MINUS_1_BOUND), Some(BigUint::from(274_177u32)));
+    }
+
+    #[test]
+    fn gives_up_on_balanced_semiprimes() {
+        // safe primes, so p - 1 is not smooth and rho needs ~2^32 steps
+        let mut rng = StdRng::seed_from_u64(4545);
+        let p = gen_safe_prime(64, &mut rng, &BailliePsw).to_biguint().unwrap();
+        let q = gen_safe_prime(64, &mut rng, &BailliePsw).to_biguint().unwrap();
+        assert_eq!(factorize(&(&p * &q)), None);
+        assert_eq!(factorize(&p), Some(vec![(p, 1)]));
+    }
+}
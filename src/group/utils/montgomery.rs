@@ -0,0 +1,304 @@
+//! src/group/utils/montgomery.rs
+//! Montgomery-form modular exponentiation shared by Paillier, the class-group
+//! prototype, primality testing and RSA.
+//!
+//! For an odd modulus n with s 64-bit limbs we fix R = 2^(64 s) and keep
+//! residues as a·R mod n. Multiplication uses CIOS (coarsely integrated operand
+//! scanning) Montgomery reduction, so there is no division by n inside the
+//! exponentiation loop; only the precomputation of R mod n and R^2 mod n divides.
+//! Exponentiation uses a left-to-right sliding window over odd powers.
+//!
+//! Not constant-time: the window schedule follows the exponent bits and the
+//! final conditional subtraction is data dependent.
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+/// Precomputed Montgomery context for one odd modulus n
+#[derive(Debug, Clone)]
+pub struct Montgomery {
+    n: BigUint,
+    limbs: Vec<u64>, // n, little-endian 64-bit limbs
+    n_prime: u64,    // -n^{-1} mod 2^64
+    r: Vec<u64>,     // R mod n (Montgomery form of 1)
+    r2: Vec<u64>,    // R^2 mod n (converts into Montgomery form)
+}
+
+impl Montgomery {
+    /// Build a context for an odd modulus n > 1; returns None otherwise.
+    pub fn new(n: &BigUint) -> Option<Self> {
+        if n <= &BigUint::one() || !n.bit(0) {
+            return None;
+        }
+        let limbs = n.to_u64_digits();
+        let s = limbs.len();
+
+        // Newton iteration for n0^{-1} mod 2^64 (each step doubles the correct bits)
+        let n0 = limbs[0];
+        let mut inv: u64 = 1;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(n0.wrapping_mul(inv)));
+        }
+        let n_prime = inv.wrapping_neg();
+
+        let r = (BigUint::one() << (64 * s)) % n;
+        let r2 = (&r * &r) % n;
+        Some(Montgomery {
+            n: n.clone(),
+            n_prime,
+            r: to_limbs(&r, s),
+            r2: to_limbs(&r2, s),
+            limbs,
+        })
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.n
+    }
+
+    /// a·b mod n for ordinary (non-Montgomery) residues
+    pub fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        let am = self.to_mont(a);
+        let bm = self.to_mont(b);
+        self.to_normal(&self.mont_mul(&am, &bm))
+    }
+
+    /// base^exp mod n
+    pub fn pow(&self, base: &BigUint, exp: &BigUint) -> BigUint {
+        if exp.is_zero() {
+            return BigUint::one() % &self.n;
+        }
+        let bits = exp.bits();
+        let w = window_size(bits);
+
+        // odd powers base^1, base^3, ..., base^(2^w - 1) in Montgomery form
+        let b = self.to_mont(base);
+        let mut table = Vec::with_capacity(1 << (w - 1));
+        table.push(b.clone());
+        if w > 1 {
+            let b2 = self.mont_mul(&b, &b);
+            for i in 1..(1usize << (w - 1)) {
+                let next = self.mont_mul(&table[i - 1], &b2);
+                table.push(next);
+            }
+        }
+
+        let mut acc = self.r.clone();
+        let mut i = bits as i64 - 1;
+        while i >= 0 {
+            if !exp.bit(i as u64) {
+                acc = self.mont_mul(&acc, &acc);
+                i -= 1;
+                continue;
+            }
+            // longest window exp[i..=j] of width <= w that ends in a set bit
+            let mut j = (i - w as i64 + 1).max(0);
+            while !exp.bit(j as u64) {
+                j += 1;
+            }
+            let mut val = 0usize;
+            for k in (j..=i).rev() {
+                val = (val << 1) | exp.bit(k as u64) as usize;
+            }
+            for _ in j..=i {
+                acc = self.mont_mul(&acc, &acc);
+            }
+            acc = self.mont_mul(&acc, &table[val >> 1]);
+            i = j - 1;
+        }
+        self.to_normal(&acc)
+    }
+
+    fn to_mont(&self, a: &BigUint) -> Vec<u64> {
+        let a = if a >= &self.n { a % &self.n } else { a.clone() };
+        self.mont_mul(&to_limbs(&a, self.limbs.len()), &self.r2)
+    }
+
+    fn to_normal(&self, a: &[u64]) -> BigUint {
+        let mut one = vec![0u64; self.limbs.len()];
+        one[0] = 1;
+        from_limbs(&self.mont_mul(a, &one))
+    }
+
+    // CIOS Montgomery product: a·b·R^{-1} mod n, inputs and output in [0, n)
+    fn mont_mul(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let n = &self.limbs;
+        let s = n.len();
+        let mut t = vec![0u64; s + 2];
+        for &bi in b.iter().take(s) {
+            // t += a * b_i
+            let mut c: u64 = 0;
+            for j in 0..s {
+                let uv = t[j] as u128 + a[j] as u128 * bi as u128 + c as u128;
+                t[j] = uv as u64;
+                c = (uv >> 64) as u64;
+            }
+            let uv = t[s] as u128 + c as u128;
+            t[s] = uv as u64;
+            t[s + 1] = (uv >> 64) as u64;
+
+            // t = (t + m * n) / 2^64 with m chosen so the low limb vanishes
+            let m = t[0].wrapping_mul(self.n_prime);
+            let uv = t[0] as u128 + m as u128 * n[0] as u128;
+            let mut c = (uv >> 64) as u64;
+            for j in 1..s {
+                let uv = t[j] as u128 + m as u128 * n[j] as u128 + c as u128;
+                t[j - 1] = uv as u64;
+                c = (uv >> 64) as u64;
+            }
+            let uv = t[s] as u128 + c as u128;
+            t[s - 1] = uv as u64;
+            t[s] = t[s + 1] + (uv >> 64) as u64;
+            t[s + 1] = 0;
+        }
+
+        // t < 2n here; one conditional subtraction brings it into [0, n)
+        if t[s] != 0 || !less_than(&t[..s], n) {
+            let mut borrow = 0u64;
+            for j in 0..s {
+                let (d1, b1) = t[j].overflowing_sub(n[j]);
+                let (d2, b2) = d1.overflowing_sub(borrow);
+                t[j] = d2;
+                borrow = (b1 || b2) as u64;
+            }
+        }
+        t.truncate(s);
+        t
+    }
+}
+
+/// base^exp mod m, through Montgomery for odd m and square-and-multiply otherwise
+pub fn modpow(base: &BigUint, exp: &BigUint, m: &BigUint) -> BigUint {
+    assert!(!m.is_zero(), "modulus must be nonzero");
+    match Montgomery::new(m) {
+        Some(ctx) => ctx.pow(base, exp),
+        None => {
+            let mut result = BigUint::one() % m;
+            let mut b = base % m;
+            for i in 0..exp.bits() {
+                if exp.bit(i) {
+                    result = (&result * &b) % m;
+                }
+                b = (&b * &b) % m;
+            }
+            result
+        }
+    }
+}
+
+/// Signed front end for `modpow`: base may be negative, exp must not be.
+/// Returns a representative in [0, m).
+pub fn modpow_bigint(base: &BigInt, exp: &BigInt, m: &BigInt) -> BigInt {
+    assert!(exp.sign() != Sign::Minus, "negative exponent");
+    assert!(m.sign() == Sign::Plus, "modulus must be positive");
+    let m_u = m.magnitude();
+    let b = base.magnitude() % m_u;
+    let b = if base.sign() == Sign::Minus && !b.is_zero() { m_u - b } else { b };
+    BigInt::from_biguint(Sign::Plus, modpow(&b, exp.magnitude(), m_u))
+}
+
+/// base^exp mod m for any integer exponent; a negative exponent inverts base
+/// first and yields None when base is not a unit mod m.
+pub fn modpow_signed(base: &BigInt, exp: &BigInt, m: &BigInt) -> Option<BigInt> {
+    if exp.sign() != Sign::Minus {
+        return Some(modpow_bigint(base, exp, m));
+    }
+    let b = base.mod_floor(m);
+    let ext = b.extended_gcd(m);
+    if !ext.gcd.is_one() {
+        return None;
+    }
+    Some(modpow_bigint(&ext.x, &-exp, m))
+}
+
+// window width for an exponent of the given bit length
+fn window_size(bits: u64) -> usize {
+    match bits {
+        0..=24 => 1,
+        25..=80 => 3,
+        81..=240 => 4,
+        241..=672 => 5,
+        _ => 6,
+    }
+}
+
+fn to_limbs(x: &BigUint, s: usize) -> Vec<u64> {
+    let mut v = x.to_u64_digits();
+    v.resize(s, 0);
+    v
+}
+
+fn from_limbs(limbs: &[u64]) -> BigUint {
+    let digits: Vec<u32> = limbs.iter().flat_map(|&l| [l as u32, (l >> 32) as u32]).collect();
+    BigUint::new(digits)
+}
+
+// a < b for equal-length little-endian limb slices
+fn less_than(a: &[u64], b: &[u64]) -> bool {
+    for (x, y) in a.iter().rev().zip(b.iter().rev()) {
+        if x != y {
+            return x < y;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::RandBigInt;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn matches_biguint_modpow() {
+        let mut rng = OsRng;
+        for bits in [3u64, 61, 64, 65, 127, 128, 521, 1024, 2048] {
+            for _ in 0..4 {
+                let mut n = rng.gen_biguint(bits);
+                n.set_bit(bits - 1, true);
+                n.set_bit(0, true);
+                let base = rng.gen_biguint(bits + 7); // also exercises base >= n
+                let exp = rng.gen_biguint(bits);
+                assert_eq!(modpow(&base, &exp, &n), base.modpow(&exp, &n), "bits = {}", bits);
+            }
+        }
+    }
+
+    #[test]
+    fn all_ones_modulus_and_edge_exponents() {
+        // top limb full forces the t[s] carry path in the reduction
+        let n = (BigUint::one() << 256) - BigUint::one();
+        let ctx = Montgomery::new(&n).unwrap();
+        let a = &n - BigUint::from(2u32);
+        assert_eq!(ctx.pow(&a, &BigUint::zero()), BigUint::one());
+        assert_eq!(ctx.pow(&a, &BigUint::one()), a);
+        assert_eq!(ctx.pow(&BigUint::zero(), &BigUint::from(5u32)), BigUint::zero());
+        assert_eq!(ctx.mul(&a, &a), (&a * &a) % &n);
+    }
+
+    #[test]
+    fn even_modulus_falls_back() {
+        assert!(Montgomery::new(&BigUint::from(1000u32)).is_none());
+        let r = modpow(&BigUint::from(2u32), &BigUint::from(10u32), &BigUint::from(1000u32));
+        assert_eq!(r, BigUint::from(24u32));
+        assert_eq!(modpow(&BigUint::from(7u32), &BigUint::from(3u32), &BigUint::one()), BigUint::zero());
+    }
+
+    #[test]
+    fn signed_exponent() {
+        let m = BigInt::from(97);
+        let inv3 = modpow_signed(&BigInt::from(3), &BigInt::from(-1), &m).unwrap();
+        assert_eq!((inv3 * 3) % &m, BigInt::one());
+        let r = modpow_signed(&BigInt::from(5), &BigInt::from(-7), &m).unwrap();
+        assert_eq!((r * modpow_bigint(&BigInt::from(5), &BigInt::from(7), &m)) % &m, BigInt::one());
+        assert!(modpow_signed(&BigInt::from(6), &BigInt::from(-1), &BigInt::from(9)).is_none());
+    }
+
+    #[test]
+    fn signed_base() {
+        let m = BigInt::from(97);
+        let r = modpow_bigint(&BigInt::from(-3), &BigInt::from(3), &m);
+        assert_eq!(r, BigInt::from(-27 + 97));
+    }
+}
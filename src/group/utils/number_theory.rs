@@ -0,0 +1,239 @@
+//! src/group/utils/number_theory.rs
+//! gcd, Bézout, inverses, exponentiation, CRT, φ and element orders for any
+//! integer type implementing `NtInt`: `i64`, `BigInt` and `BigUint`.
+//!
+//! Everything is computed on `BigInt` and converted back, so the `i64`
+//! versions cannot overflow in intermediate products the way the first
+//! group::rsa lessons did; those lessons now call into this module.
+//! Exponentiation goes through the Montgomery engine (super::montgomery) and
+//! φ / orders factor with super::factor, so they return None when the
+//! modulus cannot be factored (an RSA n, by design).
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use super::factor::factorize;
+use super::montgomery::modpow_bigint;
+
+/// Integer types the helpers accept. `Signed` holds Bézout coefficients,
+/// which may be negative even when the inputs are not.
+pub trait NtInt: Clone {
+    type Signed;
+
+    fn to_big(&self) -> BigInt;
+
+    /// None when x does not fit in Self
+    fn from_big(x: &BigInt) -> Option<Self>;
+
+    fn signed_from_big(x: &BigInt) -> Option<Self::Signed>;
+}
+
+impl NtInt for i64 {
+    type Signed = i64;
+
+    fn to_big(&self) -> BigInt {
+        BigInt::from(*self)
+    }
+
+    fn from_big(x: &BigInt) -> Option<Self> {
+        x.to_i64()
+    }
+
+    fn signed_from_big(x: &BigInt) -> Option<i64> {
+        x.to_i64()
+    }
+}
+
+impl NtInt for BigInt {
+    type Signed = BigInt;
+
+    fn to_big(&self) -> BigInt {
+        self.clone()
+    }
+
+    fn from_big(x: &BigInt) -> Option<Self> {
+        Some(x.clone())
+    }
+
+    fn signed_from_big(x: &BigInt) -> Option<BigInt> {
+        Some(x.clone())
+    }
+}
+
+impl NtInt for BigUint {
+    type Signed = BigInt;
+
+    fn to_big(&self) -> BigInt {
+        BigInt::from(self.clone())
+    }
+
+    fn from_big(x: &BigInt) -> Option<Self> {
+        x.to_biguint()
+    }
+
+    fn signed_from_big(x: &BigInt) -> Option<BigInt> {
+        Some(x.clone())
+    }
+}
+
+fn back<T: NtInt>(x: &BigInt) -> T {
+    T::from_big(x).expect("result is bounded by the inputs")
+}
+
+/// gcd(a, b) >= 0
+pub fn gcd<T: NtInt>(a: &T, b: &T) -> T {
+    back(&a.to_big().gcd(&b.to_big()))
+}
+
+/// (g, x, y) with a·x + b·y = g = gcd(a, b) >= 0
+pub fn extended_gcd<T: NtInt>(a: &T, b: &T) -> (T, T::Signed, T::Signed) {
+    let ext = a.to_big().extended_gcd(&b.to_big());
+    // num-integer keeps the sign of the inputs in g; normalise to g >= 0
+    let (g, x, y) = if ext.gcd.is_negative() { (-ext.gcd, -ext.x, -ext.y) } else { (ext.gcd, ext.x, ext.y) };
+    let conv = |v: &BigInt| T::signed_from_big(v).expect("Bézout coefficients are bounded by the inputs");
+    (back(&g), conv(&x), conv(&y))
+}
+
+/// a^{-1} mod m in [0, m), None when gcd(a, m) != 1
+pub fn mod_inverse<T: NtInt>(a: &T, m: &T) -> Option<T> {
+    let m = m.to_big();
+    assert!(m.is_positive(), "modulus must be positive");
+    let ext = a.to_big().mod_floor(&m).extended_gcd(&m);
+    ext.gcd.is_one().then(|| back(&ext.x.mod_floor(&m)))
+}
+
+/// base^exp mod m in [0, m); exp must be non-negative
+pub fn mod_pow<T: NtInt>(base: &T, exp: &T, m: &T) -> T {
+    back(&modpow_bigint(&base.to_big(), &exp.to_big(), &m.to_big()))
+}
+
+/// Chinese Remainder Theorem by incremental Garner steps: the x in [0, N)
+/// with x ≡ a_i (mod n_i), N = product(n). None when the moduli are not
+/// pairwise coprime or (for i64) N does not fit.
+pub fn crt<T: NtInt>(a: &[T], n: &[T]) -> Option<(T, T)> {
+    assert!(a.len() == n.len(), "Input arrays must have same length");
+    let mut x = BigInt::zero();
+    let mut big_n = BigInt::one();
+    for (ai, ni) in a.iter().zip(n) {
+        let ni = ni.to_big();
+        assert!(ni.is_positive(), "moduli must be positive");
+        // x + N·t ≡ a_i (mod n_i)
+        let ext = big_n.mod_floor(&ni).extended_gcd(&ni);
+        if !ext.gcd.is_one() {
+            return None;
+        }
+        let t = ((ai.to_big() - &x) * ext.x).mod_floor(&ni);
+        x += &big_n * t;
+        big_n *= ni;
+    }
+    Some((T::from_big(&x)?, T::from_big(&big_n)?))
+}
+
+/// Prime factorization of |n| > 0; None when it cannot be completed
+pub fn factor<T: NtInt>(n: &T) -> Option<Vec<(T, u32)>> {
+    let n = n.to_big();
+    assert!(!n.is_zero(), "cannot factor zero");
+    let factors = factorize(n.magnitude())?;
+    Some(factors.iter().map(|(p, e)| (back(&BigInt::from(p.clone())), *e)).collect())
+}
+
+/// Euler's totient φ(n) for n >= 1; None when n cannot be factored
+pub fn phi<T: NtInt>(n: &T) -> Option<T> {
+    let n_big = n.to_big();
+    assert!(n_big.is_positive(), "φ is defined for n >= 1");
+    Some(back(&totient(n_big.magnitude())?))
+}
+
+fn totient(n: &BigUint) -> Option<BigInt> {
+    let mut result = BigUint::one();
+    for (p, e) in factorize(n)? {
+        result *= p.pow(e - 1) * (&p - 1u32);
+    }
+    Some(BigInt::from_biguint(Sign::Plus, result))
+}
+
+/// Multiplicative order of a mod n: the least k > 0 with a^k ≡ 1.
+/// None when a is not a unit mod n or φ(n) cannot be factored.
+pub fn order_of_element<T: NtInt>(a: &T, n: &T) -> Option<T> {
+    let n = n.to_big();
+    assert!(n.is_positive(), "modulus must be positive");
+    let a = a.to_big().mod_floor(&n);
+    if !a.gcd(&n).is_one() {
+        return None;
+    }
+    // start from φ(n) and strip every prime the order does not need
+    let phi_n = totient(n.magnitude())?;
+    let mut order = phi_n.clone();
+    for (q, _) in factorize(phi_n.magnitude())? {
+        let q = BigInt::from(q);
+        while order.is_multiple_of(&q) && modpow_bigint(&a, &(&order / &q), &n).is_one() {
+            order /= &q;
+        }
+    }
+    Some(back(&order))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn i64_matches_small_cases() {
+        assert_eq!(gcd(&48i64, &18), 6);
+        assert_eq!(gcd(&-48i64, &18), 6);
+        let (g, x, y) = extended_gcd(&240i64, &46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(mod_inverse(&3i64, &7), Some(5));
+        assert_eq!(mod_inverse(&-3i64, &7), Some(2));
+        assert_eq!(mod_inverse(&6i64, &9), None);
+        assert_eq!(mod_pow(&-2i64, &5, &13), 7);
+        assert_eq!(crt(&[2i64, 3, 2], &[3, 5, 7]), Some((23, 105)));
+        assert_eq!(crt(&[1i64, 2], &[4, 6]), None);
+        assert_eq!(phi(&1i64), Some(1));
+        assert_eq!(phi(&36i64), Some(12));
+        assert_eq!(order_of_element(&2i64, &7), Some(3));
+        assert_eq!(order_of_element(&3i64, &7), Some(6));
+        assert_eq!(order_of_element(&6i64, &9), None);
+    }
+
+    #[test]
+    fn i64_no_longer_overflows() {
+        // products of two residues exceed i64 for a modulus near 2^61
+        let m = (1i64 << 61) - 1; // Mersenne prime
+        assert_eq!(mod_pow(&3i64, &(m - 1), &m), 1);
+        assert_eq!(phi(&m), Some(m - 1));
+        let (x, n) = crt(&[1i64, 2], &[(1 << 31) - 1, (1 << 31) + 11]).unwrap();
+        assert_eq!((x % ((1 << 31) - 1), x % ((1 << 31) + 11)), (1, 2));
+        assert_eq!(n, ((1i64 << 31) - 1) * ((1 << 31) + 11));
+        // N = product no longer fits in i64
+        assert_eq!(crt(&[0i64, 0, 0], &[m, (1 << 31) - 1, 3]), None);
+    }
+
+    #[test]
+    fn big_types_agree() {
+        // Mersenne primes; q − 1 is smooth, so p−1 splits n
+        let p: BigUint = (BigUint::one() << 127u32) - 1u32;
+        let q: BigUint = (BigUint::one() << 31u32) - 1u32;
+        let n = &p * &q;
+        let phi_n: BigUint = (&p - 1u32) * (&q - 1u32);
+        assert_eq!(phi(&n), Some(phi_n.clone()));
+        let a = BigUint::from(5u32);
+        let ord = order_of_element(&a, &n).unwrap();
+        assert!(mod_pow(&a, &ord, &n).is_one());
+        assert!(phi_n.is_multiple_of(&ord));
+        for (r, _) in factor(&ord).unwrap() {
+            assert!(!mod_pow(&a, &(&ord / r), &n).is_one());
+        }
+
+        let (g, x, y) = extended_gcd(&BigUint::from(240u32), &BigUint::from(46u32));
+        assert_eq!((g, BigInt::from(240) * x + BigInt::from(46) * y), (BigUint::from(2u32), BigInt::from(2)));
+        let a_i = [BigInt::from(-1), BigInt::from(3)];
+        let n_i = [BigInt::from(p.clone()), BigInt::from(q.clone())];
+        let (x, big_n) = crt(&a_i, &n_i).unwrap();
+        assert_eq!(big_n, BigInt::from(n.clone()));
+        assert_eq!(x.mod_floor(&n_i[0]), &n_i[0] - 1);
+        assert_eq!(x.mod_floor(&n_i[1]), BigInt::from(3));
+        let inv: BigInt = mod_inverse(&BigInt::from(-7), &BigInt::from(n.clone())).unwrap();
+        assert!((inv * BigInt::from(-7)).mod_floor(&BigInt::from(n)).is_one());
+    }
+}
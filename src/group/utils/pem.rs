@@ -0,0 +1,138 @@
+//! src/group/utils/pem.rs
+//! PEM armor (RFC 7468): `-----BEGIN <label>-----`, base64 body, matching
+//! `-----END <label>-----`. Encoding wraps at 64 columns with `\n`.
+//!
+//! Decoding is strict: one block, the expected label, no headers (so no
+//! legacy encrypted PEM), and standard base64 with canonical padding.
+//! Surrounding whitespace and `\r\n` line endings are accepted.
+
+use thiserror::Error;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PemError {
+    #[error("missing or malformed BEGIN/END line")]
+    Armor,
+    #[error("expected label {expected:?}, found {found:?}")]
+    Label { expected: String, found: String },
+    #[error("invalid base64 body")]
+    Base64,
+}
+
+pub fn encode(label: &str, der: &[u8]) -> String {
+    let body = base64_encode(der);
+    let mut out = format!("-----BEGIN {label}-----\n");
+    for chunk in body.as_bytes().chunks(64) {
+        out.push_str(std::str::from_utf8(chunk).expect("base64 is ASCII"));
+        out.push('\n');
+    }
+    out.push_str(&format!("-----END {label}-----\n"));
+    out
+}
+
+pub fn decode(label: &str, pem: &str) -> Result<Vec<u8>, PemError> {
+    let mut lines = pem.trim().lines().map(str::trim_end);
+    let found = lines
+        .next()
+        .and_then(|l| l.strip_prefix("-----BEGIN "))
+        .and_then(|l| l.strip_suffix("-----"))
+        .ok_or(PemError::Armor)?;
+    if found != label {
+        return Err(PemError::Label { expected: label.to_string(), found: found.to_string() });
+    }
+    let end = format!("-----END {label}-----");
+    let mut body = String::new();
+    let mut closed = false;
+    for line in lines {
+        if closed {
+            return Err(PemError::Armor);
+        }
+        if line == end {
+            closed = true;
+        } else if line.contains(':') || line.starts_with("-----") {
+            return Err(PemError::Armor);
+        } else {
+            body.push_str(line);
+        }
+    }
+    if !closed {
+        return Err(PemError::Armor);
+    }
+    base64_decode(&body)
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn base64_decode(s: &str) -> Result<Vec<u8>, PemError> {
+    let s = s.as_bytes();
+    if !s.len().is_multiple_of(4) {
+        return Err(PemError::Base64);
+    }
+    let mut out = Vec::with_capacity(s.len() / 4 * 3);
+    for (i, chunk) in s.chunks(4).enumerate() {
+        let last = i == s.len() / 4 - 1;
+        let pad = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if pad > 2 || (pad > 0 && !last) {
+            return Err(PemError::Base64);
+        }
+        let mut n = 0u32;
+        for &c in &chunk[..4 - pad] {
+            let v = ALPHABET.iter().position(|&a| a == c).ok_or(PemError::Base64)?;
+            n = n << 6 | v as u32;
+        }
+        n <<= 6 * pad;
+        let bytes = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        // leftover bits under the padding must be zero
+        if pad > 0 && bytes[3 - pad..].iter().any(|&b| b != 0) {
+            return Err(PemError::Base64);
+        }
+        out.extend_from_slice(&bytes[..3 - pad]);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_rfc4648_vectors() {
+        let cases = [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foob", "Zm9vYg=="), ("fooba", "Zm9vYmE="), ("foobar", "Zm9vYmFy")];
+        for (plain, encoded) in cases {
+            assert_eq!(base64_encode(plain.as_bytes()), encoded);
+            assert_eq!(base64_decode(encoded).unwrap(), plain.as_bytes());
+        }
+        for bad in ["Zg=", "Zh==", "Z===", "Zg==Zg==", "Zm9*", "Zm 9v"] {
+            assert_eq!(base64_decode(bad), Err(PemError::Base64), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn armor_roundtrip_and_errors() {
+        let data: Vec<u8> = (0..=255).collect();
+        let pem = encode("TEST DATA", &data);
+        assert!(pem.lines().all(|l| l.len() <= 64));
+        assert_eq!(decode("TEST DATA", &pem).unwrap(), data);
+        assert_eq!(decode("TEST DATA", &pem.replace('\n', "\r\n")).unwrap(), data);
+
+        assert!(matches!(decode("OTHER", &pem), Err(PemError::Label { .. })));
+        assert_eq!(decode("TEST DATA", &pem.replace("-----END TEST DATA-----\n", "")), Err(PemError::Armor));
+        assert_eq!(decode("TEST DATA", &pem.replacen('\n', "\nProc-Type: 4,ENCRYPTED\n", 1)), Err(PemError::Armor));
+        assert_eq!(decode("TEST DATA", &format!("{pem}{pem}")), Err(PemError::Armor));
+    }
+}
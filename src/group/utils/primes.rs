@@ -0,0 +1,478 @@
+//! src/group/utils/primes.rs
+//! Prime generation shared by Paillier, RSA, DSA and ring-Pedersen setup.
+//!
+//! Candidates are found by incremental search: draw one random starting point
+//! of the requested size, compute its residues modulo a table of small primes
+//! once, then step through the arithmetic progression and only run the
+//! expensive test on values that no small prime divides.
+//!
+//! The final test is pluggable (`PrimalityTest`). `BailliePsw` — Miller–Rabin
+//! to base 2 plus a strong Lucas test — is the default; no composite passing
+//! it is known. `MillerRabin` with random bases is kept for comparison. Every
+//! generator takes its randomness as `&mut dyn RngCore` so tests can use a
+//! seeded RNG; the `random_*` helpers use `OsRng`.
+
+use std::sync::OnceLock;
+use num_bigint::{BigInt, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use super::montgomery::{modpow_bigint, Montgomery};
+use super::quadratic::jacobi;
+
+/// Small primes below this bound are used for sieving and trial division
+const SIEVE_BOUND: usize = 1 << 13;
+
+/// Restart with a fresh random start after this many steps
+const MAX_STEPS: u64 = 1 << 16;
+
+/// Odd primes below `SIEVE_BOUND`
+pub fn small_primes() -> &'static [u32] {
+    static PRIMES: OnceLock<Vec<u32>> = OnceLock::new();
+    PRIMES.get_or_init(|| {
+        let mut composite = vec![false; SIEVE_BOUND];
+        let mut out = Vec::new();
+        for i in 3..SIEVE_BOUND {
+            if i % 2 == 1 && !composite[i] {
+                out.push(i as u32);
+                for j in (i * i..SIEVE_BOUND).step_by(2 * i) {
+                    composite[j] = true;
+                }
+            }
+        }
+        out
+    })
+}
+
+// -----------------------------
+// Primality tests
+// -----------------------------
+
+/// A probable-prime test; `rng` feeds randomized tests and is ignored by
+/// deterministic ones
+pub trait PrimalityTest {
+    fn is_probable_prime(&self, n: &BigInt, rng: &mut dyn RngCore) -> bool;
+}
+
+/// Miller–Rabin with `rounds` random bases (error <= 4^-rounds)
+#[derive(Debug, Clone, Copy)]
+pub struct MillerRabin {
+    pub rounds: usize,
+}
+
+/// Baillie–PSW: Miller–Rabin base 2 and a strong Lucas test (Selfridge A)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BailliePsw;
+
+impl PrimalityTest for MillerRabin {
+    fn is_probable_prime(&self, n: &BigInt, rng: &mut dyn RngCore) -> bool {
+        if let Some(decided) = trial_division(n) {
+            return decided;
+        }
+        let ctx = Montgomery::new(n.magnitude()).expect("odd n after trial division");
+        let two = BigInt::from(2);
+        let n_minus_1 = n - BigInt::one();
+        (0..self.rounds).all(|_| {
+            let a = rng.gen_bigint_range(&two, &n_minus_1);
+            miller_rabin_base(n, &a, &ctx)
+        })
+    }
+}
+
+impl PrimalityTest for BailliePsw {
+    fn is_probable_prime(&self, n: &BigInt, _rng: &mut dyn RngCore) -> bool {
+        is_prime(n)
+    }
+}
+
+/// Baillie–PSW primality test
+pub fn is_prime(n: &BigInt) -> bool {
+    if let Some(decided) = trial_division(n) {
+        return decided;
+    }
+    let ctx = Montgomery::new(n.magnitude()).expect("odd n after trial division");
+    miller_rabin_base(n, &BigInt::from(2), &ctx) && strong_lucas(n)
+}
+
+/// Some(answer) when trial division by the small-prime table decides n
+pub fn trial_division(n: &BigInt) -> Option<bool> {
+    if n < &BigInt::from(2) {
+        return Some(false);
+    }
+    if n.is_even() {
+        return Some(n == &BigInt::from(2));
+    }
+    for &p in small_primes() {
+        let p = BigInt::from(p);
+        if n == &p {
+            return Some(true);
+        }
+        if (n % &p).is_zero() {
+            return Some(false);
+        }
+    }
+    // no factor below SIEVE_BOUND and n < SIEVE_BOUND^2 means n is prime
+    if n < &BigInt::from(SIEVE_BOUND * SIEVE_BOUND) {
+        return Some(true);
+    }
+    None
+}
+
+/// One Miller–Rabin round to base `a` for odd n > 3
+pub fn miller_rabin_base(n: &BigInt, a: &BigInt, ctx: &Montgomery) -> bool {
+    let n_minus_1 = n - BigInt::one();
+    let s = n_minus_1.trailing_zeros().expect("n > 1");
+    let d = &n_minus_1 >> s;
+    let mut x = BigInt::from(ctx.pow(a.mod_floor(n).magnitude(), d.magnitude()));
+    if x.is_one() || x == n_minus_1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = (&x * &x) % n;
+        if x == n_minus_1 {
+            return true;
+        }
+    }
+    false
+}
+
+/// Strong Lucas probable-prime test with Selfridge's parameters: D is the
+/// first of 5, -7, 9, -11, ... with (D/n) = -1, P = 1, Q = (1 - D)/4
+pub fn strong_lucas(n: &BigInt) -> bool {
+    // a perfect square has no D with (D/n) = -1
+    let root = n.sqrt();
+    if &(&root * &root) == n {
+        return false;
+    }
+    let mut d = 5i64;
+    loop {
+        match jacobi(&BigInt::from(d), n) {
+            -1 => break,
+            0 if BigInt::from(d.abs()) != *n => return false,
+            _ => d = if d > 0 { -(d + 2) } else { -d + 2 },
+        }
+    }
+    let big_d = BigInt::from(d);
+    let q = BigInt::from((1 - d) / 4);
+
+    // n + 1 = k * 2^s with k odd
+    let n_plus_1 = n + BigInt::one();
+    let s = n_plus_1.trailing_zeros().expect("n + 1 > 0");
+    let k = &n_plus_1 >> s;
+
+    // halving mod odd n
+    let half = |x: BigInt| -> BigInt {
+        let x = if x.is_odd() { x + n } else { x };
+        (x >> 1usize).mod_floor(n)
+    };
+
+    // left-to-right ladder over the bits of k, starting from (U_1, V_1, Q^1)
+    let mut u = BigInt::one();
+    let mut v = BigInt::one();
+    let mut qk = q.mod_floor(n);
+    for i in (0..k.bits() - 1).rev() {
+        // double: U_2j = U_j V_j, V_2j = V_j^2 - 2 Q^j
+        u = (&u * &v).mod_floor(n);
+        v = (&v * &v - (&qk << 1usize)).mod_floor(n);
+        qk = (&qk * &qk).mod_floor(n);
+        if k.bit(i) {
+            // increment: U_j+1 = (U_j + V_j)/2, V_j+1 = (D U_j + V_j)/2
+            let u_next = half(&u + &v);
+            let v_next = half(&big_d * &u + &v);
+            u = u_next;
+            v = v_next;
+            qk = (&qk * &q).mod_floor(n);
+        }
+    }
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = (&v * &v - (&qk << 1usize)).mod_floor(n);
+        if v.is_zero() {
+            return true;
+        }
+        qk = (&qk * &qk).mod_floor(n);
+    }
+    false
+}
+
+// -----------------------------
+// Generation
+// -----------------------------
+
+/// Uniform-start prime of exactly `bits` bits (top two bits set, so a product
+/// of two such primes has exactly 2·bits bits)
+pub fn gen_prime(bits: usize, rng: &mut dyn RngCore, test: &dyn PrimalityTest) -> BigInt {
+    gen_prime_congruent(bits, 1, 2, rng, test)
+}
+
+/// Prime p ≡ residue (mod modulus) of exactly `bits` bits; modulus must be
+/// even and residue odd so every candidate is odd
+pub fn gen_prime_congruent(bits: usize, residue: u64, modulus: u64, rng: &mut dyn RngCore, test: &dyn PrimalityTest) -> BigInt {
+    assert!(bits >= 3, "need at least 3 bits");
+    assert!(modulus.is_multiple_of(2) && residue % 2 == 1 && residue < modulus, "residue must be odd mod an even modulus");
+    let step = BigInt::from(modulus);
+    loop {
+        let start = random_start(bits, rng);
+        let start = &start + (BigInt::from(residue) - &start).mod_floor(&step);
+        let residues = sieve_residues(&start);
+        for i in 0..MAX_STEPS {
+            let delta = i * modulus;
+            let candidate = &start + delta;
+            if candidate.bits() as usize != bits {
+                break;
+            }
+            let sieved = small_primes()
+                .iter()
+                .zip(&residues)
+                .any(|(&p, &r)| (r + delta % p as u64).is_multiple_of(p as u64) && candidate != BigInt::from(p));
+            if !sieved && test.is_probable_prime(&candidate, rng) {
+                return candidate;
+            }
+        }
+    }
+}
+
+/// Safe prime p = 2q + 1 (q prime) of exactly `bits` bits; sieves p and q
+/// together and runs the full test only when both pass a base-2 round
+pub fn gen_safe_prime(bits: usize, rng: &mut dyn RngCore, test: &dyn PrimalityTest) -> BigInt {
+    assert!(bits >= 4, "need at least 4 bits");
+    let two = BigInt::from(2);
+    loop {
+        let q_start = random_start(bits - 1, rng) | BigInt::one();
+        let residues = sieve_residues(&q_start);
+        for i in 0..MAX_STEPS {
+            let delta = 2 * i;
+            let q = &q_start + delta;
+            if q.bits() as usize != bits - 1 {
+                break;
+            }
+            // p = 2q + 1 ≡ 2r + 1 (mod small prime)
+            let sieved = small_primes().iter().zip(&residues).any(|(&sp, &r)| {
+                let sp = sp as u64;
+                let rq = (r + delta % sp) % sp;
+                (rq == 0 || (2 * rq + 1).is_multiple_of(sp)) && q > BigInt::from(sp)
+            });
+            if sieved {
+                continue;
+            }
+            let p: BigInt = (&q << 1) + BigInt::one();
+            let quick = |x: &BigInt| match Montgomery::new(x.magnitude()) {
+                Some(ctx) => x <= &BigInt::from(3) || miller_rabin_base(x, &two, &ctx),
+                None => false,
+            };
+            if quick(&q) && quick(&p) && test.is_probable_prime(&q, rng) && test.is_probable_prime(&p, rng) {
+                return p;
+            }
+        }
+    }
+}
+
+/// Gordon strong prime: p - 1 has a large prime factor r, p + 1 has a large
+/// prime factor s and r - 1 has a large prime factor t
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrongPrime {
+    pub p: BigInt,
+    pub r: BigInt,
+    pub s: BigInt,
+    pub t: BigInt,
+}
+
+pub fn gen_strong_prime(bits: usize, rng: &mut dyn RngCore, test: &dyn PrimalityTest) -> StrongPrime {
+    assert!(bits >= 128, "strong primes need at least 128 bits");
+    let one = BigInt::one();
+    let two = BigInt::from(2);
+    // r and s around bits/2 - 20 leave ~2^38 multiples of 2rs to search
+    let s = gen_prime(bits / 2 - 16, rng, test);
+    let t = gen_prime(bits / 2 - 24, rng, test);
+    // r = 2it + 1
+    let mut r = (&t << 1) + &one;
+    while !test.is_probable_prime(&r, rng) {
+        r += &t << 1;
+    }
+    // p0 = 2 (s^(r-2) mod r) s - 1 ≡ 1 (mod r), ≡ -1 (mod s)
+    let s_inv = modpow_bigint(&s, &(&r - &two), &r);
+    let p0 = (&two * s_inv * &s) - &one;
+    let step: BigInt = &two * &r * &s;
+    let low = BigInt::one() << (bits - 1);
+    let high = BigInt::one() << bits;
+    loop {
+        // random multiple inside [2^(bits-1), 2^bits)
+        let j_lo = (&low - &p0).div_ceil(&step);
+        let j_hi = (&high - &p0) / &step;
+        let mut p = &p0 + rng.gen_bigint_range(&j_lo, &j_hi) * &step;
+        while p < high {
+            if trial_division(&p) != Some(false) && test.is_probable_prime(&p, rng) {
+                return StrongPrime { p, r, s, t };
+            }
+            p += &step;
+        }
+    }
+}
+
+/// Smallest prime >= n (Baillie–PSW)
+pub fn next_prime(n: &BigInt) -> BigInt {
+    let two = BigInt::from(2);
+    if n <= &two {
+        return two;
+    }
+    let mut x = if n.is_even() { n + BigInt::one() } else { n.clone() };
+    while !is_prime(&x) {
+        x += 2;
+    }
+    x
+}
+
+pub fn random_prime(bits: usize) -> BigInt {
+    gen_prime(bits, &mut OsRng, &BailliePsw)
+}
+
+/// p ≡ 3 (mod 4)
+pub fn random_blum_prime(bits: usize) -> BigInt {
+    random_blum_prime_with_rng(bits, &mut OsRng)
+}
+
+pub fn random_blum_prime_with_rng(bits: usize, rng: &mut dyn RngCore) -> BigInt {
+    gen_prime_congruent(bits, 3, 4, rng, &BailliePsw)
+}
+
+pub fn random_safe_prime(bits: usize) -> BigInt {
+    random_safe_prime_with_rng(bits, &mut OsRng)
+}
+
+pub fn random_safe_prime_with_rng(bits: usize, rng: &mut dyn RngCore) -> BigInt {
+    gen_safe_prime(bits, rng, &BailliePsw)
+}
+
+pub fn random_strong_prime(bits: usize) -> BigInt {
+    gen_strong_prime(bits, &mut OsRng, &BailliePsw).p
+}
+
+// random integer with exactly `bits` bits and its top two bits set
+fn random_start(bits: usize, rng: &mut dyn RngCore) -> BigInt {
+    let mut x = rng.gen_bigint(bits as u64);
+    if x.sign() == Sign::Minus {
+        x = -x;
+    }
+    x.set_bit(bits as u64 - 1, true);
+    if bits >= 2 {
+        x.set_bit(bits as u64 - 2, true);
+    }
+    x
+}
+
+fn sieve_residues(start: &BigInt) -> Vec<u64> {
+    small_primes()
+        .iter()
+        .map(|&p| {
+            let r = start % p;
+            r.to_u64_digits().1.first().copied().unwrap_or(0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // trial-division reference for small n
+    fn is_prime_naive(n: u64) -> bool {
+        n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+    }
+
+    #[test]
+    fn bpsw_matches_trial_division() {
+        for n in 0u64..20_000 {
+            assert_eq!(is_prime(&BigInt::from(n)), is_prime_naive(n), "n = {n}");
+        }
+        // just above the trial-division shortcut
+        let base = (SIEVE_BOUND * SIEVE_BOUND) as u64;
+        for n in base..base + 2_000 {
+            assert_eq!(is_prime(&BigInt::from(n)), is_prime_naive(n), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn known_pseudoprimes_are_rejected() {
+        // strong pseudoprimes to base 2 (caught by the Lucas half)
+        for n in [2047u64, 3277, 4033, 4681, 8321, 3_215_031_751, 2_152_302_898_747] {
+            let n = BigInt::from(n);
+            let ctx = Montgomery::new(n.magnitude()).unwrap();
+            assert!(miller_rabin_base(&n, &BigInt::from(2), &ctx), "{n} is a strong pseudoprime to base 2");
+            assert!(!is_prime(&n), "{n}");
+        }
+        // strong Lucas pseudoprimes (caught by the base-2 half)
+        for n in [5459u64, 5777, 10877, 16109, 18971] {
+            let n = BigInt::from(n);
+            assert!(strong_lucas(&n), "{n} is a strong Lucas pseudoprime");
+            assert!(!is_prime(&n));
+        }
+        // Carmichael numbers and a square
+        for n in [561u64, 41_041, 825_265, 321_197_185, 1_234_567u64 * 1_234_567] {
+            assert!(!is_prime(&BigInt::from(n)));
+        }
+        // Mersenne primes 2^127 - 1 and 2^521 - 1, and a composite neighbour
+        assert!(is_prime(&((BigInt::one() << 127usize) - 1)));
+        assert!(is_prime(&((BigInt::one() << 521usize) - 1)));
+        assert!(!is_prime(&((BigInt::one() << 523usize) - 1)));
+    }
+
+    #[test]
+    fn miller_rabin_agrees() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mr = MillerRabin { rounds: 20 };
+        for n in 0u64..5_000 {
+            assert_eq!(mr.is_probable_prime(&BigInt::from(n), &mut rng), is_prime_naive(n));
+        }
+    }
+
+    #[test]
+    fn generators_hit_exact_sizes_and_forms() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let p = gen_prime(256, &mut rng, &BailliePsw);
+        assert_eq!(p.bits(), 256);
+        assert!(p.bit(254));
+        assert!(MillerRabin { rounds: 32 }.is_probable_prime(&p, &mut rng));
+
+        let b = gen_prime_congruent(200, 3, 4, &mut rng, &BailliePsw);
+        assert_eq!(b.mod_floor(&BigInt::from(4)), BigInt::from(3));
+
+        let sp = gen_safe_prime(256, &mut rng, &BailliePsw);
+        assert_eq!(sp.bits(), 256);
+        assert!(is_prime(&((&sp - 1) >> 1usize)));
+
+        let strong = gen_strong_prime(256, &mut rng, &BailliePsw);
+        assert_eq!(strong.p.bits(), 256);
+        assert!(is_prime(&strong.p));
+        let one = BigInt::one();
+        assert!((&strong.p - &one).is_multiple_of(&strong.r));
+        assert!((&strong.p + &one).is_multiple_of(&strong.s));
+        assert!((&strong.r - &one).is_multiple_of(&strong.t));
+
+        // small sizes still work for toy parameters
+        let tiny = gen_prime(12, &mut rng, &BailliePsw);
+        assert_eq!(tiny.bits(), 12);
+        assert!(is_prime_naive(tiny.to_u64_digits().1[0]));
+    }
+
+    #[test]
+    fn seeded_rng_is_reproducible() {
+        let a = gen_prime(128, &mut StdRng::seed_from_u64(1), &BailliePsw);
+        let b = gen_prime(128, &mut StdRng::seed_from_u64(1), &BailliePsw);
+        let c = gen_prime(128, &mut StdRng::seed_from_u64(2), &BailliePsw);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        let blum = |s| random_blum_prime_with_rng(128, &mut StdRng::seed_from_u64(s));
+        assert_eq!(blum(3), blum(3));
+        assert_eq!(blum(3).mod_floor(&BigInt::from(4)), BigInt::from(3));
+        let safe = |s| random_safe_prime_with_rng(96, &mut StdRng::seed_from_u64(s));
+        assert_eq!(safe(4), safe(4));
+        assert_ne!(safe(4), safe(5));
+        assert_eq!(next_prime(&BigInt::from(1_000_000)), BigInt::from(1_000_003));
+        assert_eq!(next_prime(&BigInt::from(0)), BigInt::from(2));
+    }
+}
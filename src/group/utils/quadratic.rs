@@ -0,0 +1,321 @@
+//! src/group/utils/quadratic.rs
+//! Quadratic residues and modular square roots.
+//!
+//! - Legendre / Jacobi symbol by binary quadratic reciprocity (no
+//!   factoring), used by the strong Lucas test and the Π^mod proof.
+//! - Square roots mod an odd prime p: p ≡ 3 (mod 4) is a single exponentiation
+//!   a^((p+1)/4); otherwise Tonelli–Shanks, which walks the 2-Sylow subgroup
+//!   and costs O(s²) multiplications for p - 1 = 2^s·q. Cipolla (arithmetic in
+//!   F_p[ω] with ω² = t² - a a non-residue) is O(log p) for every p and is
+//!   kept as an alternative for primes with large s.
+//! - Prime powers by Hensel lifting (Newton steps for odd p, bit-by-bit for
+//!   p = 2), with the p-adic valuation of a handled separately. Composites
+//!   with known factorization recombine one root per prime power by CRT.
+//!
+//! Every root function returns one root, or None when a is not a square. The
+//! others follow: -x, and for composites every CRT mix of ± per factor.
+
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use super::montgomery::modpow_bigint;
+use super::number_theory::{crt, mod_inverse};
+
+/// Jacobi symbol (a/n) for odd n > 0; 0 when gcd(a, n) > 1
+pub fn jacobi(a: &BigInt, n: &BigInt) -> i32 {
+    assert!(n.sign() == Sign::Plus && n.is_odd(), "Jacobi symbol needs odd n > 0");
+    let mut a = a.mod_floor(n);
+    let mut n = n.clone();
+    let mut result = 1;
+    while !a.is_zero() {
+        let tz = a.trailing_zeros().expect("a != 0");
+        a >>= tz;
+        // (2/n) = -1 exactly when n ≡ 3, 5 (mod 8)
+        if tz % 2 == 1 && (n.bit(1) ^ n.bit(2)) {
+            result = -result;
+        }
+        // reciprocity: flip when both are 3 mod 4
+        std::mem::swap(&mut a, &mut n);
+        if a.bit(1) && n.bit(1) {
+            result = -result;
+        }
+        a = a.mod_floor(&n);
+    }
+    if n.is_one() { result } else { 0 }
+}
+
+/// Legendre symbol (a/p) for an odd prime p: 1 for a nonzero square, -1 for a
+/// non-square, 0 when p | a. Equal to the Jacobi symbol, which is cheaper than
+/// Euler's criterion a^((p-1)/2).
+pub fn legendre(a: &BigInt, p: &BigInt) -> i32 {
+    jacobi(a, p)
+}
+
+/// Some x with x² ≡ a (mod p), p prime (2 allowed)
+pub fn sqrt_mod_prime(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    if p == &BigInt::from(2) {
+        return Some(a.mod_floor(p));
+    }
+    if p.bit(0) && p.bit(1) {
+        let a = a.mod_floor(p);
+        let x = modpow_bigint(&a, &((p + 1) >> 2), p);
+        return (&x * &x % p == a).then_some(x);
+    }
+    tonelli_shanks(a, p)
+}
+
+/// Tonelli–Shanks square root mod an odd prime p
+pub fn tonelli_shanks(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let a = a.mod_floor(p);
+    if a.is_zero() {
+        return Some(a);
+    }
+    if legendre(&a, p) != 1 {
+        return None;
+    }
+    // p - 1 = 2^s·q with q odd
+    let p_minus_1: BigInt = p - 1;
+    let s = p_minus_1.trailing_zeros().expect("p > 1");
+    let q = &p_minus_1 >> s;
+    // any non-residue z generates the 2-Sylow subgroup through z^q
+    let mut z = BigInt::from(2);
+    while legendre(&z, p) != -1 {
+        z += 1;
+    }
+    let mut m = s;
+    let mut c = modpow_bigint(&z, &q, p);
+    let mut t = modpow_bigint(&a, &q, p);
+    let mut x = modpow_bigint(&a, &((&q + 1) >> 1), p);
+    // invariant: x² = a·t, and t has order dividing 2^(m-1)
+    while !t.is_one() {
+        // least i with t^(2^i) = 1
+        let mut i = 0;
+        let mut t2 = t.clone();
+        while !t2.is_one() {
+            t2 = &t2 * &t2 % p;
+            i += 1;
+        }
+        let mut b = c;
+        for _ in 0..m - i - 1 {
+            b = &b * &b % p;
+        }
+        m = i;
+        c = &b * &b % p;
+        t = t * &c % p;
+        x = x * b % p;
+    }
+    Some(x)
+}
+
+/// Cipolla square root mod an odd prime p
+pub fn cipolla(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let a = a.mod_floor(p);
+    if a.is_zero() {
+        return Some(a);
+    }
+    if legendre(&a, p) != 1 {
+        return None;
+    }
+    // t with w = t² - a a non-residue, so F_p[ω]/(ω² - w) is F_{p²}
+    let mut t = BigInt::zero();
+    let w = loop {
+        let w = (&t * &t - &a).mod_floor(p);
+        if legendre(&w, p) == -1 {
+            break w;
+        }
+        t += 1;
+    };
+    // (t + ω)^((p+1)/2) lands in F_p and is a root
+    let mul = |(x1, y1): &(BigInt, BigInt), (x2, y2): &(BigInt, BigInt)| {
+        ((x1 * x2 + y1 * y2 % p * &w) % p, (x1 * y2 + y1 * x2) % p)
+    };
+    let e: BigInt = (p + 1) >> 1;
+    let mut acc = (BigInt::one(), BigInt::zero());
+    let mut base = (t, BigInt::one());
+    for i in 0..e.bits() {
+        if e.bit(i) {
+            acc = mul(&acc, &base);
+        }
+        base = mul(&base, &base);
+    }
+    debug_assert!(acc.1.is_zero());
+    Some(acc.0)
+}
+
+/// Some x with x² ≡ a (mod p^k), p prime, k >= 1
+pub fn sqrt_mod_prime_power(a: &BigInt, p: &BigInt, k: u32) -> Option<BigInt> {
+    assert!(k >= 1, "exponent must be positive");
+    let pk = p.pow(k);
+    let mut a = a.mod_floor(&pk);
+    if a.is_zero() {
+        return Some(a);
+    }
+    // a = p^v·b with p ∤ b: a square needs v even, then x = p^(v/2)·sqrt(b)
+    let mut v = 0;
+    while a.is_multiple_of(p) {
+        a /= p;
+        v += 1;
+    }
+    if v % 2 == 1 {
+        return None;
+    }
+    let root = if p == &BigInt::from(2) { sqrt_unit_mod_2k(&a, k - v)? } else { hensel_lift(&a, p, k - v)? };
+    Some((p.pow(v / 2) * root).mod_floor(&pk))
+}
+
+// odd p, p ∤ a: Newton steps x ← x - (x² - a)/(2x) double the precision
+fn hensel_lift(a: &BigInt, p: &BigInt, k: u32) -> Option<BigInt> {
+    let mut x = sqrt_mod_prime(a, p)?;
+    let mut e = 1;
+    while e < k {
+        e = (2 * e).min(k);
+        let pe = p.pow(e);
+        let inv: BigInt = mod_inverse(&(2 * &x), &pe).expect("2x is a unit for odd p");
+        x = (&x - (&x * &x - a) * inv).mod_floor(&pe);
+    }
+    Some(x)
+}
+
+// odd a: a square mod 2^k iff a ≡ 1 mod min(2^k, 8); fix one bit per step
+fn sqrt_unit_mod_2k(a: &BigInt, k: u32) -> Option<BigInt> {
+    let need = BigInt::from(1u32 << k.min(3));
+    if !(a - BigInt::one()).is_multiple_of(&need) {
+        return None;
+    }
+    let mut x = BigInt::one();
+    // x² ≡ a (mod 2^i) ⇒ x or x + 2^(i-1) works mod 2^(i+1)
+    for i in 3..k {
+        if !(&x * &x - a).is_multiple_of(&(BigInt::one() << (i + 1))) {
+            x += BigInt::one() << (i - 1);
+        }
+    }
+    Some(x)
+}
+
+/// Some x with x² ≡ a (mod n) where n = ∏ p_i^k_i is given by its factors
+pub fn sqrt_mod_composite(a: &BigInt, factors: &[(BigInt, u32)]) -> Option<BigInt> {
+    let mut roots = Vec::with_capacity(factors.len());
+    let mut moduli = Vec::with_capacity(factors.len());
+    for (p, k) in factors {
+        roots.push(sqrt_mod_prime_power(a, p, *k)?);
+        moduli.push(p.pow(*k));
+    }
+    crt(&roots, &moduli).map(|(x, _)| x)
+}
+
+/// Quadratic residuosity mod n from its factorization: a is a square mod n
+/// iff it is one mod every prime power (the Jacobi symbol alone cannot tell)
+pub fn is_square_mod(a: &BigInt, factors: &[(BigInt, u32)]) -> bool {
+    factors.iter().all(|(p, k)| sqrt_mod_prime_power(a, p, *k).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::ToPrimitive;
+
+    const SMALL_PRIMES: [i64; 12] = [3, 5, 7, 11, 13, 17, 41, 73, 97, 113, 193, 257];
+
+    fn squares(n: i64) -> Vec<bool> {
+        let mut sq = vec![false; n as usize];
+        for x in 0..n {
+            sq[(x * x % n) as usize] = true;
+        }
+        sq
+    }
+
+    fn check_root(x: Option<BigInt>, a: i64, n: i64, is_square: bool) {
+        assert_eq!(x.is_some(), is_square, "a = {a}, n = {n}");
+        if let Some(x) = x {
+            let x = x.to_i64().unwrap();
+            assert!((0..n).contains(&x), "a = {a}, n = {n}");
+            assert_eq!(x * x % n, a.rem_euclid(n), "a = {a}, n = {n}");
+        }
+    }
+
+    #[test]
+    fn symbols_match_brute_force() {
+        for p in SMALL_PRIMES {
+            let sq = squares(p);
+            for a in 0..p {
+                let expected = if a == 0 { 0 } else if sq[a as usize] { 1 } else { -1 };
+                assert_eq!(legendre(&BigInt::from(a), &BigInt::from(p)), expected, "({a}/{p})");
+            }
+        }
+        // Jacobi = product of Legendre symbols over the factorization
+        for (n, f) in [(15i64, [3i64, 5]), (21, [3, 7]), (91, [7, 13]), (221, [13, 17])] {
+            for a in -30..30 {
+                let prod: i32 = f.iter().map(|&p| legendre(&BigInt::from(a), &BigInt::from(p))).product();
+                assert_eq!(jacobi(&BigInt::from(a), &BigInt::from(n)), prod, "({a}/{n})");
+            }
+        }
+        let p = BigInt::from(1_000_003);
+        for a in 1..200 {
+            let a = BigInt::from(a);
+            let euler = modpow_bigint(&a, &((&p - 1) / 2), &p);
+            assert_eq!(jacobi(&a, &p), if euler.is_one() { 1 } else { -1 });
+        }
+    }
+
+    #[test]
+    fn prime_roots_match_brute_force() {
+        for p in SMALL_PRIMES {
+            let sq = squares(p);
+            let big_p = BigInt::from(p);
+            for a in -3..p {
+                let is_square = sq[a.rem_euclid(p) as usize];
+                let a_big = BigInt::from(a);
+                check_root(sqrt_mod_prime(&a_big, &big_p), a, p, is_square);
+                check_root(tonelli_shanks(&a_big, &big_p), a, p, is_square);
+                check_root(cipolla(&a_big, &big_p), a, p, is_square);
+            }
+        }
+        check_root(sqrt_mod_prime(&BigInt::from(3), &BigInt::from(2)), 3, 2, true);
+    }
+
+    #[test]
+    fn large_prime_with_deep_two_adic_part() {
+        // p = 3·2^66 + 1 is prime, so Tonelli–Shanks runs 66 levels deep
+        let p = BigInt::from(3) * (BigInt::one() << 66) + 1;
+        assert!(crate::group::utils::primes::is_prime(&p));
+        for a in [2u32, 5, 10, 12345] {
+            let a = BigInt::from(a).pow(2) * 7 % &p;
+            for root in [tonelli_shanks(&a, &p), cipolla(&a, &p)] {
+                match root {
+                    Some(x) => assert_eq!(&x * &x % &p, a),
+                    None => assert_eq!(legendre(&a, &p), -1),
+                }
+            }
+            let sq = &a * &a % &p;
+            let x = sqrt_mod_prime(&sq, &p).unwrap();
+            assert_eq!(&x * &x % &p, sq);
+        }
+    }
+
+    #[test]
+    fn prime_powers_match_brute_force() {
+        for (p, k) in [(2i64, 1u32), (2, 2), (2, 3), (2, 5), (2, 7), (3, 2), (3, 5), (5, 3), (7, 2), (13, 2)] {
+            let n = p.pow(k);
+            let sq = squares(n);
+            for a in 0..n {
+                let x = sqrt_mod_prime_power(&BigInt::from(a), &BigInt::from(p), k);
+                check_root(x, a, n, sq[a as usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn composites_match_brute_force() {
+        let cases: [&[(i64, u32)]; 5] = [&[(3, 1), (5, 1)], &[(2, 2), (7, 1)], &[(2, 3), (3, 2), (5, 1)], &[(7, 1), (11, 1), (13, 1)], &[(3, 3), (17, 1)]];
+        for factors in cases {
+            let n: i64 = factors.iter().map(|&(p, k)| p.pow(k)).product();
+            let big: Vec<(BigInt, u32)> = factors.iter().map(|&(p, k)| (BigInt::from(p), k)).collect();
+            let sq = squares(n);
+            for a in 0..n {
+                let a_big = BigInt::from(a);
+                check_root(sqrt_mod_composite(&a_big, &big), a, n, sq[a as usize]);
+                assert_eq!(is_square_mod(&a_big, &big), sq[a as usize]);
+            }
+        }
+    }
+}
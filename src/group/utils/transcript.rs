@@ -0,0 +1,103 @@
+//! src/group/utils/transcript.rs
+//! Fiat–Shamir transcript over SHA-256 for the non-interactive proofs.
+//!
+//! Every message is absorbed as `label || len(bytes) || bytes` so two
+//! different sequences of messages can never hash the same way. Challenges
+//! are squeezed in counter mode from a snapshot of the running hash and then
+//! absorbed back, so consecutive challenges are independent.
+
+use num_bigint::{BigInt, BigUint, Sign};
+use sha2::{Digest, Sha256};
+
+#[derive(Clone)]
+pub struct Transcript {
+    hasher: Sha256,
+}
+
+impl Transcript {
+    /// Start a transcript bound to a protocol name
+    pub fn new(domain: &[u8]) -> Self {
+        let mut t = Transcript { hasher: Sha256::new() };
+        t.append_bytes(b"domain", domain);
+        t
+    }
+
+    pub fn append_bytes(&mut self, label: &[u8], bytes: &[u8]) {
+        self.hasher.update((label.len() as u64).to_be_bytes());
+        self.hasher.update(label);
+        self.hasher.update((bytes.len() as u64).to_be_bytes());
+        self.hasher.update(bytes);
+    }
+
+    /// Absorb an integer with its sign
+    pub fn append_int(&mut self, label: &[u8], x: &BigInt) {
+        let (sign, mag) = x.to_bytes_be();
+        let mut bytes = vec![if sign == Sign::Minus { 1u8 } else { 0u8 }];
+        bytes.extend_from_slice(&mag);
+        self.append_bytes(label, &bytes);
+    }
+
+    pub fn append_u64(&mut self, label: &[u8], x: u64) {
+        self.append_bytes(label, &x.to_be_bytes());
+    }
+
+    /// Squeeze a uniformly random integer in [0, 2^bits)
+    pub fn challenge_bits(&mut self, label: &[u8], bits: u64) -> BigInt {
+        let n_bytes = bits.div_ceil(8) as usize;
+        let mut out = Vec::with_capacity(n_bytes + 32);
+        let mut counter: u64 = 0;
+        while out.len() < n_bytes {
+            let mut h = self.hasher.clone();
+            h.update((label.len() as u64).to_be_bytes());
+            h.update(label);
+            h.update(counter.to_be_bytes());
+            out.extend_from_slice(&h.finalize());
+            counter += 1;
+        }
+        out.truncate(n_bytes);
+        let mut x = BigUint::from_bytes_be(&out);
+        let excess = (n_bytes as u64) * 8 - bits;
+        x >>= excess;
+        let x = BigInt::from_biguint(Sign::Plus, x);
+        self.append_int(label, &x);
+        x
+    }
+
+    /// Squeeze an integer in [0, m) with statistical distance < 2^-128 from uniform
+    pub fn challenge_mod(&mut self, label: &[u8], m: &BigInt) -> BigInt {
+        let wide = self.challenge_bits(label, m.bits() + 128);
+        ((wide % m) + m) % m
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic_and_label_sensitive() {
+        let mut a = Transcript::new(b"test");
+        let mut b = Transcript::new(b"test");
+        a.append_int(b"x", &BigInt::from(5));
+        b.append_int(b"x", &BigInt::from(5));
+        assert_eq!(a.clone().challenge_bits(b"e", 256), b.clone().challenge_bits(b"e", 256));
+
+        let mut c = Transcript::new(b"test");
+        c.append_int(b"x", &BigInt::from(-5));
+        assert_ne!(a.clone().challenge_bits(b"e", 256), c.challenge_bits(b"e", 256));
+        assert_ne!(a.clone().challenge_bits(b"e", 256), a.clone().challenge_bits(b"f", 256));
+    }
+
+    #[test]
+    fn challenge_sizes() {
+        let mut t = Transcript::new(b"sizes");
+        let e1 = t.challenge_bits(b"e", 13);
+        let e2 = t.challenge_bits(b"e", 13);
+        assert!(e1 < BigInt::from(1 << 13));
+        assert_ne!(e1, e2); // consecutive challenges differ
+        let m = BigInt::from(1_000_003);
+        let e = t.challenge_mod(b"m", &m);
+        assert!(e >= BigInt::from(0) && e < m);
+        assert!(t.challenge_bits(b"big", 1000).bits() <= 1000);
+    }
+}
@@ -83,6 +83,10 @@ fn main() {
     use group::dsa::dsa::demo as dsa_demo;
     dsa_demo();
 
+    // --- Discrete log: recover the toy private keys ---
+    use group::ecc::dlog::demo as dlog_demo;
+    dlog_demo();
+
         let secp = Secp256k1::new();
     let g_aff = AffinePoint { x: secp.gx.clone(), y: secp.gy.clone(), infinity: false };
     let g_jac = JacobianPoint::from_affine(&g_aff);